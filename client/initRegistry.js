@@ -1,10 +1,9 @@
 import { readFileSync } from 'fs';
-import fetch from 'node-fetch';
+import { homedir } from 'os';
+import { join } from 'path';
 
 import {
   createInstructionInitializeRegistry,
-  createInstructionCreateEntry,
-  getAllTokens,
   PROGRAM_ID
 } from 'solana-token-registry';
 import {
@@ -14,9 +13,9 @@ import {
   Transaction
 } from '@solana/web3.js';
 
-const connection = new Connection('https://api.devnet.solana.com', 'confirmed')
+const connection = new Connection(process.env.RPC_URL || 'https://api.devnet.solana.com', 'confirmed')
 
-const configFile = '/Users/chris/.config/solana/id.json'
+const configFile = process.env.KEYPAIR || join(homedir(), '.config', 'solana', 'id.json')
 const userPrivateKeyString = readFileSync(configFile).toString()
 const userKeypair = Keypair.fromSecretKey(new Uint8Array(JSON.parse(userPrivateKeyString)))
 const DEST_PUBLICKEY = new PublicKey("3Xz7i56YzDA5TMDV3opGCQZFjVz5J61DfwXcdyp5WNxA")
@@ -46,37 +45,6 @@ console.log(
     BigInt(20000000)
   ))
 )
-fetch("https://raw.githubusercontent.com/solana-labs/token-list/main/src/tokens/solana.tokenlist.json").then(
-  tokensList => tokensList.json()
-).then(
-  async (tokensList) => {
-    for (const token of tokensList.tokens) {
-      console.log(token)
-      const extensions = []
-      if (token.extensions !== undefined) {
-        for (const [key, value] of Object.entries(token.extensions)) {
-          extensions.push([key, value])
-        }
-      }
-      console.log(extensions)
-      try {
-        const sig = await sendAndConfirmTx(connection, await createInstructionCreateEntry(
-          connection,
-          PROGRAM_ID,
-          userKeypair.publicKey,
-          new PublicKey(token.address),
-          token.symbol,
-          token.name,
-          token.logoURI,
-          token.tags,
-          extensions,
-        ))
-        console.log("CreateEntry for:", token.address)
-        console.log("Signature:", sig)
-      } catch (error) {
-        continue
-      }
-    }
-  }
-)
 
+// Entries are seeded from a token list with the importer, which validates and diffs them first:
+// cargo run --manifest-path importer/Cargo.toml -- solana.tokenlist.json --url $RPC_URL --keypair $KEYPAIR
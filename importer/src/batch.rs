@@ -0,0 +1,70 @@
+//! Splitting entries into `CreateEntries` transactions that fit in a packet.
+
+use registry::{
+    instruction::{self, BatchEntry},
+    state::RegistryMetaAccount,
+};
+use solana_sdk::{packet::PACKET_DATA_SIZE, pubkey::Pubkey, transaction::Transaction};
+use std::convert::TryInto;
+
+/// The size of a `CreateEntries` transaction of `entries` paid for by `payer`, once signed.
+pub fn transaction_size(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    registry_meta: &RegistryMetaAccount,
+    registry_first: &Pubkey,
+    entries: Vec<BatchEntry>,
+) -> usize {
+    let create_entries =
+        instruction::create_entries(program_id, payer, registry_meta, registry_first, entries);
+    /* Unsigned transactions hold a placeholder for each signature, so the size is final. */
+    let transaction = Transaction::new_with_payer(&[create_entries], Some(payer));
+    bincode::serialized_size(&transaction).expect("transactions serialize") as usize
+}
+
+/// Split `entries`, in order, into chunks of at most `max_entries` whose `CreateEntries`
+/// transactions fit in a packet. An entry that does not fit in a transaction by itself still gets
+/// a chunk of its own, which will fail to send.
+pub fn chunk_entries(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    registry_meta: &RegistryMetaAccount,
+    entries: Vec<BatchEntry>,
+    max_entries: usize,
+) -> Vec<Vec<BatchEntry>> {
+    /* The first node depends on the chunks sent before, but any first node takes 32 bytes. */
+    let registry_first =
+        instruction::get_registry_tail_address(program_id, &registry_meta.registry_id);
+    let mut chunks = vec![];
+    let mut chunk: Vec<BatchEntry> = vec![];
+    for entry in entries {
+        chunk.push(entry);
+        let full = chunk.len() > max_entries
+            || transaction_size(
+                program_id,
+                payer,
+                registry_meta,
+                &registry_first,
+                chunk.clone(),
+            ) > PACKET_DATA_SIZE;
+        if full && chunk.len() > 1 {
+            let entry = chunk.pop().unwrap();
+            chunks.push(std::mem::replace(&mut chunk, vec![entry]));
+        }
+    }
+    if !chunk.is_empty() {
+        chunks.push(chunk);
+    }
+    chunks
+}
+
+/// The number of entries a `CreateEntries` created, from the return data in its log messages.
+pub fn created_entries(program_id: &Pubkey, log_messages: &[String]) -> Option<u32> {
+    let prefix = format!("Program return: {} ", program_id);
+    let data = log_messages
+        .iter()
+        .rev()
+        .find_map(|log_message| log_message.strip_prefix(&prefix))?;
+    let data = base64::decode(data).ok()?;
+    Some(u32::from_le_bytes(data.try_into().ok()?))
+}
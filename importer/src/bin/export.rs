@@ -0,0 +1,140 @@
+use chrono::{SecondsFormat, Utc};
+use clap::{crate_version, value_t_or_exit, App, Arg};
+use registry_importer::{export, rpc, tokenlist::TokenList};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey};
+use std::{error::Error, fs, path::Path, process::exit, str::FromStr};
+
+fn main() {
+    let matches = App::new("registry-export")
+        .version(crate_version!())
+        .about("Exports the entries of the registry as a solana.tokenlist.json file")
+        .arg(
+            Arg::with_name("url")
+                .long("url")
+                .short("u")
+                .value_name("URL")
+                .required(true)
+                .help("JSON RPC URL of the cluster"),
+        )
+        .arg(
+            Arg::with_name("registry_id")
+                .long("registry-id")
+                .value_name("ID")
+                .default_value("")
+                .help("Id of the registry to export"),
+        )
+        .arg(
+            Arg::with_name("program_id")
+                .long("program-id")
+                .value_name("PUBKEY")
+                .help("Address of the registry program [default: the deployed program]"),
+        )
+        .arg(
+            Arg::with_name("name")
+                .long("name")
+                .value_name("NAME")
+                .default_value("Solana Token Registry")
+                .help("Name of the token list"),
+        )
+        .arg(
+            Arg::with_name("chain_id")
+                .long("chain-id")
+                .value_name("ID")
+                .default_value("101")
+                .help("chainId of the exported tokens, 101 being mainnet-beta"),
+        )
+        .arg(
+            Arg::with_name("previous")
+                .long("previous")
+                .value_name("PATH")
+                .help("The previous export, which the version is bumped from"),
+        )
+        .arg(
+            Arg::with_name("output")
+                .long("output")
+                .short("o")
+                .value_name("PATH")
+                .help("File to write the token list to [default: stdout]"),
+        )
+        .arg(
+            Arg::with_name("scan")
+                .long("scan")
+                .help("Find the entries with getProgramAccounts, instead of walking the list"),
+        )
+        .get_matches();
+
+    let program_id = matches
+        .value_of("program_id")
+        .map(|program_id| {
+            Pubkey::from_str(program_id).unwrap_or_else(|_| exit_with("Invalid program id"))
+        })
+        .unwrap_or_else(registry::id);
+    let rpc_client = RpcClient::new_with_commitment(
+        matches.value_of("url").unwrap().to_string(),
+        CommitmentConfig::confirmed(),
+    );
+    let options = ExportOptions {
+        registry_id: matches.value_of("registry_id").unwrap(),
+        name: matches.value_of("name").unwrap(),
+        chain_id: value_t_or_exit!(matches, "chain_id", u64),
+        previous: matches.value_of("previous").map(Path::new),
+        output: matches.value_of("output").map(Path::new),
+        scan: matches.is_present("scan"),
+    };
+
+    if let Err(error) = export_registry(&rpc_client, &program_id, &options) {
+        exit_with(&error.to_string());
+    }
+}
+
+fn exit_with(message: &str) -> ! {
+    eprintln!("{}", message);
+    exit(1)
+}
+
+struct ExportOptions<'a> {
+    registry_id: &'a str,
+    name: &'a str,
+    chain_id: u64,
+    previous: Option<&'a Path>,
+    output: Option<&'a Path>,
+    scan: bool,
+}
+
+fn export_registry(
+    rpc_client: &RpcClient,
+    program_id: &Pubkey,
+    options: &ExportOptions,
+) -> Result<(), Box<dyn Error>> {
+    let registry_meta = rpc::registry_meta(rpc_client, program_id, options.registry_id)?;
+    let registry_nodes = if options.scan {
+        /* Scans come back in no particular order, so sort them for stable output. */
+        let mut registry_nodes =
+            rpc::scan_registry_nodes(rpc_client, program_id, options.registry_id)?;
+        registry_nodes.sort_by_key(|registry_node| registry_node.token_mint);
+        registry_nodes
+    } else {
+        rpc::walk_registry_nodes(rpc_client, program_id, &registry_meta)?
+    };
+    let previous = options.previous.map(TokenList::from_file).transpose()?;
+
+    let token_list = export::token_list(
+        options.name,
+        &registry_nodes,
+        options.chain_id,
+        Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+        previous.as_ref(),
+    );
+    let json = serde_json::to_string_pretty(&token_list)?;
+    match options.output {
+        Some(path) => fs::write(path, json + "\n")?,
+        None => println!("{}", json),
+    }
+    eprintln!(
+        "Exported {} tokens, version {}",
+        token_list.tokens.len(),
+        token_list.version
+    );
+    Ok(())
+}
@@ -0,0 +1,238 @@
+//! A dry run of an import: what importing each token would do, given the current registry state.
+
+use crate::tokenlist::TokenInfo;
+use registry::{
+    error::RegistryError,
+    instruction::BatchEntry,
+    state::{
+        CreateUpdateEntryInstructionData, EntryState, RegistryMetaAccount, RegistryNodeAccount,
+    },
+    validation,
+};
+use solana_sdk::{program_option::COption, pubkey::Pubkey};
+use spl_token::state::Mint;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    str::FromStr,
+};
+use thiserror::Error;
+
+/// The accounts that a diff is computed against.
+#[derive(Debug, Default)]
+pub struct RegistryState {
+    pub registry_meta: RegistryMetaAccount,
+    /// The RegistryNodeAccounts of the mints that have one.
+    pub nodes: HashMap<Pubkey, RegistryNodeAccount>,
+    /// The mints that exist.
+    pub mints: HashMap<Pubkey, Mint>,
+    /// The mint that holds the claim on each normalized symbol. Only consulted if the registry has
+    /// `unique_symbols` set.
+    pub symbol_claims: HashMap<String, Pubkey>,
+}
+
+/// What importing a token would do.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Change {
+    /// A new entry would be created.
+    Create,
+    /// The Deleted entry of the mint would be replaced.
+    Replace,
+    /// The mint has an Active entry that matches the token.
+    Unchanged,
+    /// The mint has an Active entry that differs from the token in these fields. Importing skips
+    /// it, as only its `token_update_authority` can update it.
+    Conflict(Vec<&'static str>),
+    /// The token would be rejected.
+    Invalid(InvalidToken),
+}
+
+/// Why a token would be rejected.
+#[derive(Clone, Debug, Error, PartialEq)]
+pub enum InvalidToken {
+    #[error("the address is not a valid public key")]
+    InvalidAddress,
+    #[error("the mint is listed more than once")]
+    DuplicateMint,
+    #[error("{0}")]
+    InvalidEntry(RegistryError),
+    #[error("the mint does not exist")]
+    MissingMint,
+    #[error("the token list has {token} decimals, but the mint has {mint}")]
+    MismatchedDecimals { token: u8, mint: u8 },
+    #[error("the importer cannot sign as the mint authority")]
+    InvalidMintAuthority,
+    #[error("the symbol is claimed by {0}")]
+    SymbolAlreadyClaimed(Pubkey),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TokenDiff {
+    pub address: String,
+    pub data: CreateUpdateEntryInstructionData,
+    pub change: Change,
+}
+
+impl TokenDiff {
+    /// The entry to send in a `CreateEntries`, if importing this token creates or replaces one.
+    pub fn batch_entry(&self, mint_authority: &Pubkey) -> Option<BatchEntry> {
+        match self.change {
+            Change::Create | Change::Replace => Some(BatchEntry {
+                mint: Pubkey::from_str(&self.address).ok()?,
+                mint_authority: *mint_authority,
+                signers: vec![],
+                data: self.data.clone(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for TokenDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (symbol, address) = (&self.data.token_symbol, &self.address);
+        match &self.change {
+            Change::Create => write!(f, "+ {} {}", symbol, address),
+            Change::Replace => write!(f, "+ {} {} (replaces a deleted entry)", symbol, address),
+            Change::Unchanged => write!(f, "= {} {}", symbol, address),
+            Change::Conflict(fields) => write!(
+                f,
+                "~ {} {}: differs in {}, not updated",
+                symbol,
+                address,
+                fields.join(", ")
+            ),
+            Change::Invalid(error) => write!(f, "! {} {}: {}", symbol, address, error),
+        }
+    }
+}
+
+/// What importing each of `tokens`, in order, would do. `mint_authority` signs as the mint
+/// authority of every token.
+pub fn diff(
+    tokens: &[TokenInfo],
+    state: &RegistryState,
+    mint_authority: &Pubkey,
+) -> Vec<TokenDiff> {
+    let mut mints = HashSet::new();
+    let mut symbol_claims = state.symbol_claims.clone();
+    tokens
+        .iter()
+        .map(|token| {
+            let data = token.entry_data();
+            let change = diff_token(
+                token,
+                &data,
+                state,
+                mint_authority,
+                &mut mints,
+                &mut symbol_claims,
+            )
+            .unwrap_or_else(Change::Invalid);
+            TokenDiff {
+                address: token.address.clone(),
+                data,
+                change,
+            }
+        })
+        .collect()
+}
+
+/// The checks of CreateEntries, for a single token. `mints` and `symbol_claims` accumulate the
+/// mints and symbols of the tokens before it.
+fn diff_token(
+    token: &TokenInfo,
+    data: &CreateUpdateEntryInstructionData,
+    state: &RegistryState,
+    mint_authority: &Pubkey,
+    mints: &mut HashSet<Pubkey>,
+    symbol_claims: &mut HashMap<String, Pubkey>,
+) -> Result<Change, InvalidToken> {
+    let registry_meta = &state.registry_meta;
+    let mint = Pubkey::from_str(&token.address).or(Err(InvalidToken::InvalidAddress))?;
+    if !mints.insert(mint) {
+        return Err(InvalidToken::DuplicateMint);
+    }
+    validation::validate_entry(
+        registry_meta,
+        &data.token_symbol,
+        &data.token_name,
+        &data.token_logo_url,
+        &data.token_tags,
+        &data.token_extensions,
+    )
+    .map_err(InvalidToken::InvalidEntry)?;
+
+    let registry_node = state.nodes.get(&mint);
+    if let Some(registry_node) = registry_node.filter(|node| node.state == EntryState::Active) {
+        let fields = changed_fields(registry_node, data);
+        if fields.is_empty() {
+            return Ok(Change::Unchanged);
+        }
+        return Ok(Change::Conflict(fields));
+    }
+
+    let mint_account = state.mints.get(&mint).ok_or(InvalidToken::MissingMint)?;
+    if mint_account.decimals != token.decimals {
+        return Err(InvalidToken::MismatchedDecimals {
+            token: token.decimals,
+            mint: mint_account.decimals,
+        });
+    }
+    if !can_sign_for_mint(mint_account, registry_meta, mint_authority) {
+        return Err(InvalidToken::InvalidMintAuthority);
+    }
+    if registry_meta.unique_symbols {
+        let normalized_symbol =
+            validation::normalize_symbol(&data.token_symbol).map_err(InvalidToken::InvalidEntry)?;
+        match symbol_claims.get(&normalized_symbol) {
+            Some(claimant) if *claimant != mint => {
+                return Err(InvalidToken::SymbolAlreadyClaimed(*claimant))
+            }
+            _ => {
+                symbol_claims.insert(normalized_symbol, mint);
+            }
+        }
+    }
+
+    if registry_node.is_some() {
+        return Ok(Change::Replace);
+    }
+    Ok(Change::Create)
+}
+
+/// Mints without a mint authority are signed for by the registry's `fee_update_authority`.
+fn can_sign_for_mint(
+    mint: &Mint,
+    registry_meta: &RegistryMetaAccount,
+    mint_authority: &Pubkey,
+) -> bool {
+    match mint.mint_authority {
+        COption::Some(authority) => authority == *mint_authority,
+        COption::None => registry_meta.fee_update_authority == mint_authority.to_bytes(),
+    }
+}
+
+/// The fields of `registry_node` that differ from `data`.
+fn changed_fields(
+    registry_node: &RegistryNodeAccount,
+    data: &CreateUpdateEntryInstructionData,
+) -> Vec<&'static str> {
+    let mut fields = vec![];
+    if registry_node.token_symbol != data.token_symbol {
+        fields.push("token_symbol");
+    }
+    if registry_node.token_name != data.token_name {
+        fields.push("token_name");
+    }
+    if registry_node.token_logo_url != data.token_logo_url {
+        fields.push("token_logo_url");
+    }
+    if registry_node.token_tags != data.token_tags {
+        fields.push("token_tags");
+    }
+    if registry_node.token_extensions != data.token_extensions {
+        fields.push("token_extensions");
+    }
+    fields
+}
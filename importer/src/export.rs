@@ -0,0 +1,118 @@
+//! Exporting the entries of the registry as a token list.
+
+use crate::tokenlist::{TagDetails, TokenInfo, TokenList, Version};
+use registry::state::{EntryState, RegistryNodeAccount};
+use serde_json::{Map, Value};
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+
+/// The version of a list that is exported without a previous version.
+pub const INITIAL_VERSION: Version = Version {
+    major: 1,
+    minor: 0,
+    patch: 0,
+};
+
+/// The token of an entry, with the decimals of its last mint snapshot.
+pub fn token_info(registry_node: &RegistryNodeAccount, chain_id: u64) -> TokenInfo {
+    let extensions: Map<String, Value> = registry_node
+        .token_extensions
+        .iter()
+        .filter_map(|token_extension| match &token_extension[..] {
+            [key, value] => Some((key.clone(), Value::String(value.clone()))),
+            _ => None,
+        })
+        .collect();
+    TokenInfo {
+        chain_id,
+        address: Pubkey::new_from_array(registry_node.token_mint).to_string(),
+        symbol: registry_node.token_symbol.clone(),
+        name: registry_node.token_name.clone(),
+        decimals: registry_node.mint_decimals,
+        logo_uri: Some(registry_node.token_logo_url.clone()).filter(|url| !url.is_empty()),
+        tags: registry_node.token_tags.clone(),
+        extensions,
+    }
+}
+
+/// A token list of the Active entries of `registry_nodes`, in order. Deleted entries are skipped.
+/// Every tag in use is defined, with the definitions of `previous` where it has one. The version
+/// follows `previous`, or is `INITIAL_VERSION` without one.
+pub fn token_list(
+    name: &str,
+    registry_nodes: &[RegistryNodeAccount],
+    chain_id: u64,
+    timestamp: String,
+    previous: Option<&TokenList>,
+) -> TokenList {
+    let tokens: Vec<TokenInfo> = registry_nodes
+        .iter()
+        .filter(|registry_node| registry_node.state == EntryState::Active)
+        .map(|registry_node| token_info(registry_node, chain_id))
+        .collect();
+    let tags = tokens
+        .iter()
+        .flat_map(|token| token.tags.iter())
+        .map(|tag| {
+            let details = previous
+                .and_then(|previous| previous.tags.get(tag))
+                .cloned()
+                .unwrap_or_else(|| TagDetails {
+                    name: tag.clone(),
+                    description: tag.clone(),
+                });
+            (tag.clone(), details)
+        })
+        .collect();
+    let version = match previous {
+        Some(previous) => next_version(previous, &tokens),
+        None => INITIAL_VERSION,
+    };
+    TokenList {
+        name: name.to_string(),
+        logo_uri: previous.and_then(|previous| previous.logo_uri.clone()),
+        keywords: previous
+            .map(|previous| previous.keywords.clone())
+            .unwrap_or_default(),
+        tags,
+        timestamp,
+        version,
+        tokens,
+    }
+}
+
+/// The version of a list of `tokens` that follows `previous`: a major bump if tokens were removed,
+/// a minor bump if tokens were added, a patch bump if tokens changed, and the same version
+/// otherwise. Tokens are identified by their chain id and address.
+pub fn next_version(previous: &TokenList, tokens: &[TokenInfo]) -> Version {
+    let by_address = |tokens: &[TokenInfo]| -> HashMap<(u64, String), TokenInfo> {
+        tokens
+            .iter()
+            .map(|token| ((token.chain_id, token.address.clone()), token.clone()))
+            .collect()
+    };
+    let previous_tokens = by_address(&previous.tokens);
+    let tokens = by_address(tokens);
+    let version = previous.version;
+    if previous_tokens.keys().any(|key| !tokens.contains_key(key)) {
+        return Version {
+            major: version.major + 1,
+            minor: 0,
+            patch: 0,
+        };
+    }
+    if tokens.keys().any(|key| !previous_tokens.contains_key(key)) {
+        return Version {
+            minor: version.minor + 1,
+            patch: 0,
+            ..version
+        };
+    }
+    if tokens != previous_tokens {
+        return Version {
+            patch: version.patch + 1,
+            ..version
+        };
+    }
+    version
+}
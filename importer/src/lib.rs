@@ -0,0 +1,9 @@
+//! Imports token lists into the registry, and exports the registry as a token list. Imported
+//! tokens are validated against the limits of the registry and diffed against its current state
+//! before any `CreateEntries` transactions are sent.
+
+pub mod batch;
+pub mod diff;
+pub mod export;
+pub mod rpc;
+pub mod tokenlist;
@@ -0,0 +1,256 @@
+use clap::{crate_name, crate_version, value_t_or_exit, App, Arg};
+use registry::{
+    instruction::{self, BatchEntry},
+    state::RegistryMetaAccount,
+};
+use registry_importer::{
+    batch,
+    diff::{self, Change, RegistryState, TokenDiff},
+    rpc,
+    tokenlist::{TokenInfo, TokenList},
+};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair, Signer},
+    transaction::Transaction,
+};
+use solana_transaction_status::UiTransactionEncoding;
+use std::{error::Error, path::Path, process::exit, str::FromStr};
+
+/// The most entries sent in one transaction, to stay within the compute budget.
+const DEFAULT_MAX_ENTRIES: &str = "8";
+
+fn main() {
+    let matches = App::new(crate_name!())
+        .version(crate_version!())
+        .about("Registers the tokens of a solana.tokenlist.json file in the registry")
+        .arg(
+            Arg::with_name("tokenlist")
+                .value_name("TOKENLIST")
+                .required(true)
+                .help("Path to a solana.tokenlist.json file"),
+        )
+        .arg(
+            Arg::with_name("url")
+                .long("url")
+                .short("u")
+                .value_name("URL")
+                .required(true)
+                .help("JSON RPC URL of the cluster"),
+        )
+        .arg(
+            Arg::with_name("keypair")
+                .long("keypair")
+                .short("k")
+                .value_name("PATH")
+                .required(true)
+                .help("Keypair that pays for the entries and signs as their mint authority"),
+        )
+        .arg(
+            Arg::with_name("registry_id")
+                .long("registry-id")
+                .value_name("ID")
+                .default_value("")
+                .help("Id of the registry to import into"),
+        )
+        .arg(
+            Arg::with_name("program_id")
+                .long("program-id")
+                .value_name("PUBKEY")
+                .help("Address of the registry program [default: the deployed program]"),
+        )
+        .arg(
+            Arg::with_name("chain_id")
+                .long("chain-id")
+                .value_name("ID")
+                .default_value("101")
+                .help("Only import the tokens of this chainId, 101 being mainnet-beta"),
+        )
+        .arg(
+            Arg::with_name("max_entries")
+                .long("max-entries")
+                .value_name("COUNT")
+                .default_value(DEFAULT_MAX_ENTRIES)
+                .help("Most entries to create in one transaction"),
+        )
+        .arg(
+            Arg::with_name("dry_run")
+                .long("dry-run")
+                .help("Only print what importing would change, without sending transactions"),
+        )
+        .get_matches();
+
+    let program_id = matches
+        .value_of("program_id")
+        .map(|program_id| {
+            Pubkey::from_str(program_id).unwrap_or_else(|_| exit_with("Invalid program id"))
+        })
+        .unwrap_or_else(registry::id);
+    let importer = Importer {
+        rpc_client: RpcClient::new_with_commitment(
+            matches.value_of("url").unwrap().to_string(),
+            CommitmentConfig::confirmed(),
+        ),
+        program_id,
+        payer: read_keypair_file(matches.value_of("keypair").unwrap())
+            .unwrap_or_else(|error| exit_with(&format!("Invalid keypair: {}", error))),
+        registry_id: matches.value_of("registry_id").unwrap().to_string(),
+    };
+    let options = ImportOptions {
+        chain_id: value_t_or_exit!(matches, "chain_id", u64),
+        max_entries: value_t_or_exit!(matches, "max_entries", usize),
+        dry_run: matches.is_present("dry_run"),
+    };
+
+    if let Err(error) = importer.import(Path::new(matches.value_of("tokenlist").unwrap()), &options)
+    {
+        exit_with(&error.to_string());
+    }
+}
+
+fn exit_with(message: &str) -> ! {
+    eprintln!("{}", message);
+    exit(1)
+}
+
+struct ImportOptions {
+    chain_id: u64,
+    max_entries: usize,
+    dry_run: bool,
+}
+
+struct Importer {
+    rpc_client: RpcClient,
+    program_id: Pubkey,
+    payer: Keypair,
+    registry_id: String,
+}
+
+impl Importer {
+    fn import(&self, path: &Path, options: &ImportOptions) -> Result<(), Box<dyn Error>> {
+        let tokens: Vec<TokenInfo> = TokenList::from_file(path)?
+            .tokens
+            .into_iter()
+            .filter(|token| token.chain_id == options.chain_id)
+            .collect();
+
+        /* Print what the import would change before sending anything. */
+        let state = self.registry_state(&tokens)?;
+        let diffs = diff::diff(&tokens, &state, &self.payer.pubkey());
+        for token_diff in &diffs {
+            println!("{}", token_diff);
+        }
+        print_summary(&diffs);
+        if options.dry_run {
+            return Ok(());
+        }
+
+        let registry_meta = &state.registry_meta;
+        let entries: Vec<BatchEntry> = diffs
+            .iter()
+            .filter_map(|token_diff| token_diff.batch_entry(&self.payer.pubkey()))
+            .collect();
+        let total = entries.len();
+        let (mut created, mut failed) = (0, 0);
+        for chunk in batch::chunk_entries(
+            &self.program_id,
+            &self.payer.pubkey(),
+            registry_meta,
+            entries,
+            options.max_entries,
+        ) {
+            let symbols: Vec<String> = chunk
+                .iter()
+                .map(|entry| entry.data.token_symbol.clone())
+                .collect();
+            match self.create_entries(registry_meta, chunk) {
+                Ok(chunk_created) => {
+                    println!(
+                        "Created {} of {}: {}",
+                        chunk_created,
+                        symbols.len(),
+                        symbols.join(", ")
+                    );
+                    created += chunk_created;
+                }
+                Err(error) => {
+                    eprintln!("Failed to create {}: {}", symbols.join(", "), error);
+                    failed += symbols.len();
+                }
+            }
+        }
+
+        println!("Created {} of {} entries", created, total);
+        if failed != 0 {
+            return Err(format!("{} entries were not sent", failed).into());
+        }
+        Ok(())
+    }
+
+    fn registry_state(&self, tokens: &[TokenInfo]) -> Result<RegistryState, Box<dyn Error>> {
+        let registry_meta =
+            rpc::registry_meta(&self.rpc_client, &self.program_id, &self.registry_id)?;
+        let mints: Vec<Pubkey> = tokens
+            .iter()
+            .filter_map(|token| Pubkey::from_str(&token.address).ok())
+            .collect();
+        let token_symbols: Vec<String> = tokens.iter().map(|token| token.symbol.clone()).collect();
+        rpc::registry_state(
+            &self.rpc_client,
+            &self.program_id,
+            registry_meta,
+            &mints,
+            &token_symbols,
+        )
+    }
+
+    /// Send a `CreateEntries` of `entries`, and return how many entries it created.
+    fn create_entries(
+        &self,
+        registry_meta: &RegistryMetaAccount,
+        entries: Vec<BatchEntry>,
+    ) -> Result<u32, Box<dyn Error>> {
+        let create_entries = instruction::create_entries(
+            &self.program_id,
+            &self.payer.pubkey(),
+            registry_meta,
+            &rpc::registry_first(&self.rpc_client, &self.program_id, &self.registry_id)?,
+            entries,
+        );
+        let transaction = Transaction::new_signed_with_payer(
+            &[create_entries],
+            Some(&self.payer.pubkey()),
+            &[&self.payer],
+            self.rpc_client.get_latest_blockhash()?,
+        );
+        let signature = self.rpc_client.send_and_confirm_transaction(&transaction)?;
+        let log_messages = self
+            .rpc_client
+            .get_transaction(&signature, UiTransactionEncoding::Json)?
+            .transaction
+            .meta
+            .and_then(|meta| meta.log_messages)
+            .unwrap_or_default();
+        batch::created_entries(&self.program_id, &log_messages)
+            .ok_or_else(|| format!("No return data in {}", signature).into())
+    }
+}
+
+fn print_summary(diffs: &[TokenDiff]) {
+    let count = |is_change: fn(&Change) -> bool| {
+        diffs
+            .iter()
+            .filter(|token_diff| is_change(&token_diff.change))
+            .count()
+    };
+    println!(
+        "{} to create, {} to replace, {} unchanged, {} conflicting, {} invalid",
+        count(|change| *change == Change::Create),
+        count(|change| *change == Change::Replace),
+        count(|change| *change == Change::Unchanged),
+        count(|change| matches!(change, Change::Conflict(_))),
+        count(|change| matches!(change, Change::Invalid(_))),
+    );
+}
@@ -0,0 +1,173 @@
+//! Reading the registry over JSON RPC.
+
+use crate::diff::RegistryState;
+use registry::{
+    instruction,
+    state::{
+        RegistryMetaAccount, RegistryNodeAccount, SymbolClaimAccount, REGISTRY_NODE_DISCRIMINATOR,
+    },
+    validation,
+};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::{
+    rpc_client::RpcClient,
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    rpc_filter::{Memcmp, RpcFilterType},
+    rpc_request::MAX_MULTIPLE_ACCOUNTS,
+};
+use solana_sdk::{account::Account, program_pack::Pack, pubkey::Pubkey};
+use std::{collections::HashSet, error::Error};
+
+pub fn registry_meta(
+    rpc_client: &RpcClient,
+    program_id: &Pubkey,
+    registry_id: &str,
+) -> Result<RegistryMetaAccount, Box<dyn Error>> {
+    let address = instruction::get_registry_meta_address(program_id, registry_id);
+    let account = rpc_client.get_account(&address)?;
+    Ok(RegistryMetaAccount::unpack_account_data(&account.data)?)
+}
+
+/// The first node of the linked list, which every CreateEntries changes.
+pub fn registry_first(
+    rpc_client: &RpcClient,
+    program_id: &Pubkey,
+    registry_id: &str,
+) -> Result<Pubkey, Box<dyn Error>> {
+    let address = instruction::get_registry_head_address(program_id, registry_id);
+    let registry_head = get_registry_node(rpc_client, &address)?;
+    Ok(Pubkey::new_from_array(registry_head.next_registry_node))
+}
+
+/// The nodes of the linked list of the registry, from the first to the last, found by walking it from
+/// `head_registry_node`. Takes a request per node.
+pub fn walk_registry_nodes(
+    rpc_client: &RpcClient,
+    program_id: &Pubkey,
+    registry_meta: &RegistryMetaAccount,
+) -> Result<Vec<RegistryNodeAccount>, Box<dyn Error>> {
+    let tail = instruction::get_registry_tail_address(program_id, &registry_meta.registry_id);
+    let head = Pubkey::new_from_array(registry_meta.head_registry_node);
+    let mut address =
+        Pubkey::new_from_array(get_registry_node(rpc_client, &head)?.next_registry_node);
+    let mut visited = HashSet::new();
+    let mut registry_nodes = vec![];
+    while address != tail {
+        if !visited.insert(address) {
+            return Err(format!("The linked list loops back to {}", address).into());
+        }
+        let registry_node = get_registry_node(rpc_client, &address)?;
+        address = Pubkey::new_from_array(registry_node.next_registry_node);
+        registry_nodes.push(registry_node);
+    }
+    Ok(registry_nodes)
+}
+
+/// The nodes of the registry, found with a single `getProgramAccounts`, in no particular order.
+/// Nodes of other registries of the program, and nodes that have not been migrated, are left out.
+pub fn scan_registry_nodes(
+    rpc_client: &RpcClient,
+    program_id: &Pubkey,
+    registry_id: &str,
+) -> Result<Vec<RegistryNodeAccount>, Box<dyn Error>> {
+    let config = RpcProgramAccountsConfig {
+        filters: Some(vec![RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+            0,
+            REGISTRY_NODE_DISCRIMINATOR.to_vec(),
+        ))]),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            ..RpcAccountInfoConfig::default()
+        },
+        ..RpcProgramAccountsConfig::default()
+    };
+    let mut registry_nodes = vec![];
+    for (address, account) in rpc_client.get_program_accounts_with_config(program_id, config)? {
+        let registry_node = RegistryNodeAccount::unpack_account_data(&account.data)?;
+        /* The head and tail are nodes too, but are not derived from a mint. */
+        let mint = Pubkey::new_from_array(registry_node.token_mint);
+        if address == instruction::get_registry_node_address(program_id, registry_id, &mint) {
+            registry_nodes.push(registry_node);
+        }
+    }
+    Ok(registry_nodes)
+}
+
+fn get_registry_node(
+    rpc_client: &RpcClient,
+    address: &Pubkey,
+) -> Result<RegistryNodeAccount, Box<dyn Error>> {
+    let account = rpc_client.get_account(address)?;
+    Ok(RegistryNodeAccount::unpack_account_data(&account.data)?)
+}
+
+/// The accounts of the registry and of `mints` that a diff of importing them is computed against.
+pub fn registry_state(
+    rpc_client: &RpcClient,
+    program_id: &Pubkey,
+    registry_meta: RegistryMetaAccount,
+    mints: &[Pubkey],
+    token_symbols: &[String],
+) -> Result<RegistryState, Box<dyn Error>> {
+    let registry_id = &registry_meta.registry_id;
+    let mut state = RegistryState::default();
+
+    let node_addresses: Vec<Pubkey> = mints
+        .iter()
+        .map(|mint| instruction::get_registry_node_address(program_id, registry_id, mint))
+        .collect();
+    for (mint, account) in mints.iter().zip(get_accounts(rpc_client, &node_addresses)?) {
+        if let Some(account) = account.filter(|account| account.owner == *program_id) {
+            let registry_node = RegistryNodeAccount::unpack_account_data(&account.data)
+                .map_err(|error| format!("The entry of {} cannot be read: {}", mint, error))?;
+            state.nodes.insert(*mint, registry_node);
+        }
+    }
+
+    for (mint, account) in mints.iter().zip(get_accounts(rpc_client, mints)?) {
+        if let Some(account) = account.filter(|account| account.owner == spl_token::id()) {
+            if let Ok(mint_account) = spl_token::state::Mint::unpack(&account.data) {
+                state.mints.insert(*mint, mint_account);
+            }
+        }
+    }
+
+    if registry_meta.unique_symbols {
+        let (normalized_symbols, claim_addresses): (Vec<String>, Vec<Pubkey>) = token_symbols
+            .iter()
+            .filter_map(|token_symbol| {
+                let normalized_symbol = validation::normalize_symbol(token_symbol).ok()?;
+                let address =
+                    instruction::get_symbol_claim_address(program_id, registry_id, token_symbol)?;
+                Some((normalized_symbol, address))
+            })
+            .unzip();
+        for (normalized_symbol, account) in normalized_symbols
+            .into_iter()
+            .zip(get_accounts(rpc_client, &claim_addresses)?)
+        {
+            if let Some(account) = account.filter(|account| account.owner == *program_id) {
+                let symbol_claim = SymbolClaimAccount::unpack_account_data(&account.data)?;
+                state.symbol_claims.insert(
+                    normalized_symbol,
+                    Pubkey::new_from_array(symbol_claim.token_mint),
+                );
+            }
+        }
+    }
+
+    state.registry_meta = registry_meta;
+    Ok(state)
+}
+
+/// `getMultipleAccounts`, for any number of addresses.
+fn get_accounts(
+    rpc_client: &RpcClient,
+    addresses: &[Pubkey],
+) -> Result<Vec<Option<Account>>, Box<dyn Error>> {
+    let mut accounts = Vec::with_capacity(addresses.len());
+    for chunk in addresses.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        accounts.extend(rpc_client.get_multiple_accounts(chunk)?);
+    }
+    Ok(accounts)
+}
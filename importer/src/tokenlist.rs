@@ -0,0 +1,102 @@
+//! The token list format of <https://github.com/solana-labs/token-list>.
+
+use registry::state::CreateUpdateEntryInstructionData;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::{collections::BTreeMap, fmt, fs::File, io::BufReader, path::Path};
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenList {
+    pub name: String,
+    #[serde(rename = "logoURI", default, skip_serializing_if = "Option::is_none")]
+    pub logo_uri: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keywords: Vec<String>,
+    /// The definitions of the tags that tokens may use, by tag.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tags: BTreeMap<String, TagDetails>,
+    /// When the list was generated, in ISO 8601 format.
+    #[serde(default)]
+    pub timestamp: String,
+    #[serde(default)]
+    pub version: Version,
+    pub tokens: Vec<TokenInfo>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct TagDetails {
+    pub name: String,
+    pub description: String,
+}
+
+/// The version of a token list. Lists bump `major` when tokens are removed, `minor` when tokens
+/// are added, and `patch` when tokens are changed.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Version {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenInfo {
+    pub chain_id: u64,
+    pub address: String,
+    pub symbol: String,
+    pub name: String,
+    pub decimals: u8,
+    #[serde(rename = "logoURI", default, skip_serializing_if = "Option::is_none")]
+    pub logo_uri: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub extensions: Map<String, Value>,
+}
+
+impl TokenList {
+    pub fn from_slice(data: &[u8]) -> serde_json::Result<Self> {
+        serde_json::from_slice(data)
+    }
+
+    /// Read a token list from a file, such as a copy of `solana.tokenlist.json`.
+    pub fn from_file(path: &Path) -> serde_json::Result<Self> {
+        let file = File::open(path).map_err(serde_json::Error::io)?;
+        serde_json::from_reader(BufReader::new(file))
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+impl TokenInfo {
+    /// The registry entry of this token. Each extension becomes a `[key, value]` pair, except for
+    /// those without a value, which the registry cannot store.
+    pub fn entry_data(&self) -> CreateUpdateEntryInstructionData {
+        CreateUpdateEntryInstructionData {
+            token_symbol: self.symbol.clone(),
+            token_name: self.name.clone(),
+            token_logo_url: self.logo_uri.clone().unwrap_or_default(),
+            token_tags: self.tags.clone(),
+            token_extensions: self
+                .extensions
+                .iter()
+                .filter_map(|(key, value)| Some(vec![key.clone(), extension_value(value)?]))
+                .collect(),
+        }
+    }
+}
+
+/// Extension values are strings, but are not required to be, so others are stored as JSON.
+fn extension_value(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => None,
+        Value::String(value) if value.is_empty() => None,
+        Value::String(value) => Some(value.clone()),
+        value => Some(value.to_string()),
+    }
+}
@@ -0,0 +1,219 @@
+//! Tests of the dry-run diff of an import against the registry state.
+
+use registry::{
+    error::RegistryError,
+    state::{
+        EntryState, RegistryMetaAccount, RegistryNodeAccount, DEFAULT_MAX_EXTENSIONS,
+        DEFAULT_MAX_TAGS,
+    },
+};
+use registry_importer::{
+    diff::{self, Change, InvalidToken, RegistryState},
+    tokenlist::TokenInfo,
+};
+use serde_json::{json, Map};
+use solana_sdk::{program_option::COption, pubkey::Pubkey};
+use spl_token::state::Mint;
+
+fn token(mint: &Pubkey, token_symbol: &str) -> TokenInfo {
+    let mut extensions = Map::new();
+    extensions.insert("website".to_string(), json!("https://example.com"));
+    TokenInfo {
+        chain_id: 101,
+        address: mint.to_string(),
+        symbol: token_symbol.to_string(),
+        name: format!("{} Token", token_symbol),
+        decimals: 6,
+        logo_uri: Some("https://example.com/logo.png".to_string()),
+        tags: vec!["stablecoin".to_string()],
+        extensions,
+    }
+}
+
+fn mint(mint_authority: Option<&Pubkey>) -> Mint {
+    Mint {
+        mint_authority: mint_authority.copied().into(),
+        supply: 0,
+        decimals: 6,
+        is_initialized: true,
+        freeze_authority: COption::None,
+    }
+}
+
+fn registry_node(token: &TokenInfo, state: EntryState) -> RegistryNodeAccount {
+    let data = token.entry_data();
+    RegistryNodeAccount {
+        state,
+        token_symbol: data.token_symbol,
+        token_name: data.token_name,
+        token_logo_url: data.token_logo_url,
+        token_tags: data.token_tags,
+        token_extensions: data.token_extensions,
+        ..RegistryNodeAccount::default()
+    }
+}
+
+/// A registry with default limits, whose `fee_update_authority` is `signer`.
+fn registry_state(signer: &Pubkey) -> RegistryState {
+    RegistryState {
+        registry_meta: RegistryMetaAccount {
+            fee_update_authority: signer.to_bytes(),
+            max_tags: DEFAULT_MAX_TAGS,
+            max_extensions: DEFAULT_MAX_EXTENSIONS,
+            ..RegistryMetaAccount::default()
+        },
+        ..RegistryState::default()
+    }
+}
+
+#[test]
+fn diff_changes() {
+    let signer = Pubkey::new_unique();
+    let mut state = registry_state(&signer);
+    let mints: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+    for mint_address in &mints {
+        state.mints.insert(*mint_address, mint(Some(&signer)));
+    }
+    let tokens = vec![
+        token(&mints[0], "NEW"),
+        token(&mints[1], "DELETED"),
+        token(&mints[2], "SAME"),
+        token(&mints[3], "CHANGED"),
+        token(&mints[4], "REVOKED"),
+    ];
+    state.nodes.insert(
+        mints[1],
+        registry_node(&token(&mints[1], "OLD"), EntryState::Deleted),
+    );
+    state
+        .nodes
+        .insert(mints[2], registry_node(&tokens[2], EntryState::Active));
+    let mut changed = registry_node(&tokens[3], EntryState::Active);
+    changed.token_name = "Renamed".to_string();
+    changed.token_tags = vec![];
+    state.nodes.insert(mints[3], changed);
+    /* Mints without a mint authority are signed for by the fee_update_authority. */
+    state.mints.insert(mints[4], mint(None));
+
+    let diffs = diff::diff(&tokens, &state, &signer);
+    let changes: Vec<Change> = diffs
+        .iter()
+        .map(|token_diff| token_diff.change.clone())
+        .collect();
+    assert_eq!(
+        changes,
+        vec![
+            Change::Create,
+            Change::Replace,
+            Change::Unchanged,
+            Change::Conflict(vec!["token_name", "token_tags"]),
+            Change::Create,
+        ]
+    );
+    assert_eq!(diffs[0].to_string(), format!("+ NEW {}", mints[0]));
+    assert_eq!(
+        diffs[3].to_string(),
+        format!(
+            "~ CHANGED {}: differs in token_name, token_tags, not updated",
+            mints[3]
+        )
+    );
+
+    /* Only created and replaced entries are sent. */
+    let entries: Vec<Pubkey> = diffs
+        .iter()
+        .filter_map(|token_diff| token_diff.batch_entry(&signer))
+        .map(|entry| entry.mint)
+        .collect();
+    assert_eq!(entries, vec![mints[0], mints[1], mints[4]]);
+    assert_eq!(
+        diffs[1].batch_entry(&signer).unwrap().data.token_symbol,
+        "DELETED"
+    );
+}
+
+#[test]
+fn diff_invalid_tokens() {
+    let signer = Pubkey::new_unique();
+    let mut state = registry_state(&signer);
+    let mints: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+    for mint_address in &mints {
+        state.mints.insert(*mint_address, mint(Some(&signer)));
+    }
+    state.mints.remove(&mints[2]);
+    let mut mismatched = mint(Some(&signer));
+    mismatched.decimals = 9;
+    state.mints.insert(mints[3], mismatched);
+    state
+        .mints
+        .insert(mints[4], mint(Some(&Pubkey::new_unique())));
+
+    let mut invalid_address = token(&mints[0], "BAD");
+    invalid_address.address = "not-a-mint".to_string();
+    let tokens = vec![
+        invalid_address,
+        token(&mints[0], "ONCE"),
+        token(&mints[0], "TWICE"),
+        token(&mints[1], "SYMBOL_TOO_LONG_FOR_REGISTRY"),
+        token(&mints[2], "MISSING"),
+        token(&mints[3], "DECIMALS"),
+        token(&mints[4], "FOREIGN"),
+    ];
+
+    let changes: Vec<Change> = diff::diff(&tokens, &state, &signer)
+        .into_iter()
+        .map(|token_diff| token_diff.change)
+        .collect();
+    assert_eq!(
+        changes,
+        vec![
+            Change::Invalid(InvalidToken::InvalidAddress),
+            Change::Create,
+            Change::Invalid(InvalidToken::DuplicateMint),
+            Change::Invalid(InvalidToken::InvalidEntry(RegistryError::SymbolTooLong)),
+            Change::Invalid(InvalidToken::MissingMint),
+            Change::Invalid(InvalidToken::MismatchedDecimals { token: 6, mint: 9 }),
+            Change::Invalid(InvalidToken::InvalidMintAuthority),
+        ]
+    );
+
+    /* The registry's limits apply, rather than the defaults. */
+    let tokens = vec![token(&mints[1], "TAGS")];
+    state.registry_meta.max_tags = 0;
+    assert_eq!(
+        diff::diff(&tokens, &state, &signer)[0].change,
+        Change::Invalid(InvalidToken::InvalidEntry(RegistryError::TooManyTags))
+    );
+}
+
+#[test]
+fn diff_unique_symbols() {
+    let signer = Pubkey::new_unique();
+    let mut state = registry_state(&signer);
+    state.registry_meta.unique_symbols = true;
+    let mints: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+    for mint_address in &mints {
+        state.mints.insert(*mint_address, mint(Some(&signer)));
+    }
+    let claimant = Pubkey::new_unique();
+    state.symbol_claims.insert("usdc".to_string(), claimant);
+
+    /* Symbols are claimed by registered entries, and by earlier tokens of the list. */
+    let tokens = vec![
+        token(&mints[0], "USDC"),
+        token(&mints[1], "NEW"),
+        token(&mints[2], "new"),
+    ];
+    let changes: Vec<Change> = diff::diff(&tokens, &state, &signer)
+        .into_iter()
+        .map(|token_diff| token_diff.change)
+        .collect();
+    assert_eq!(
+        changes,
+        vec![
+            Change::Invalid(InvalidToken::SymbolAlreadyClaimed(claimant)),
+            Change::Create,
+            Change::Invalid(InvalidToken::SymbolAlreadyClaimed(mints[1])),
+        ]
+    );
+}
@@ -0,0 +1,173 @@
+//! Tests of exporting registry entries as a token list.
+
+use registry::state::{EntryState, RegistryNodeAccount};
+use registry_importer::{
+    export::{self, INITIAL_VERSION},
+    tokenlist::{TagDetails, TokenList, Version},
+};
+use serde_json::json;
+use solana_sdk::pubkey::Pubkey;
+
+const TIMESTAMP: &str = "2022-05-01T00:00:00Z";
+
+fn registry_node(token_symbol: &str, state: EntryState) -> RegistryNodeAccount {
+    RegistryNodeAccount {
+        token_mint: Pubkey::new_unique().to_bytes(),
+        state,
+        mint_decimals: 6,
+        token_symbol: token_symbol.to_string(),
+        token_name: format!("{} Token", token_symbol),
+        token_logo_url: "https://example.com/logo.png".to_string(),
+        token_tags: vec!["stablecoin".to_string()],
+        token_extensions: vec![vec![
+            "website".to_string(),
+            "https://example.com".to_string(),
+        ]],
+        ..RegistryNodeAccount::default()
+    }
+}
+
+#[test]
+fn export_token_list() {
+    let mut bare = registry_node("BARE", EntryState::Active);
+    bare.token_logo_url = String::new();
+    bare.token_tags = vec![];
+    bare.token_extensions = vec![];
+    let registry_nodes = vec![
+        registry_node("FIRST", EntryState::Active),
+        registry_node("GONE", EntryState::Deleted),
+        bare,
+    ];
+
+    let token_list = export::token_list(
+        "Registry",
+        &registry_nodes,
+        101,
+        TIMESTAMP.to_string(),
+        None,
+    );
+    let json = serde_json::to_value(&token_list).unwrap();
+    let first = Pubkey::new_from_array(registry_nodes[0].token_mint).to_string();
+    let bare = Pubkey::new_from_array(registry_nodes[2].token_mint).to_string();
+    assert_eq!(
+        json,
+        json!({
+            "name": "Registry",
+            "tags": {
+                "stablecoin": {"name": "stablecoin", "description": "stablecoin"}
+            },
+            "timestamp": TIMESTAMP,
+            "version": {"major": 1, "minor": 0, "patch": 0},
+            "tokens": [
+                {
+                    "chainId": 101,
+                    "address": first,
+                    "symbol": "FIRST",
+                    "name": "FIRST Token",
+                    "decimals": 6,
+                    "logoURI": "https://example.com/logo.png",
+                    "tags": ["stablecoin"],
+                    "extensions": {"website": "https://example.com"}
+                },
+                {
+                    "chainId": 101,
+                    "address": bare,
+                    "symbol": "BARE",
+                    "name": "BARE Token",
+                    "decimals": 6
+                }
+            ]
+        })
+    );
+
+    /* Exports import back into the same entries. */
+    let imported = TokenList::from_slice(&serde_json::to_vec(&token_list).unwrap()).unwrap();
+    assert_eq!(imported, token_list);
+    let data = imported.tokens[0].entry_data();
+    assert_eq!(data.token_symbol, registry_nodes[0].token_symbol);
+    assert_eq!(data.token_name, registry_nodes[0].token_name);
+    assert_eq!(data.token_logo_url, registry_nodes[0].token_logo_url);
+    assert_eq!(data.token_tags, registry_nodes[0].token_tags);
+    assert_eq!(data.token_extensions, registry_nodes[0].token_extensions);
+}
+
+#[test]
+fn export_versions() {
+    let registry_nodes = vec![
+        registry_node("FIRST", EntryState::Active),
+        registry_node("SECOND", EntryState::Active),
+    ];
+    let mut previous = export::token_list(
+        "Registry",
+        &registry_nodes,
+        101,
+        TIMESTAMP.to_string(),
+        None,
+    );
+    assert_eq!(previous.version, INITIAL_VERSION);
+    previous.version = Version {
+        major: 1,
+        minor: 2,
+        patch: 3,
+    };
+    previous.tags.insert(
+        "stablecoin".to_string(),
+        TagDetails {
+            name: "Stablecoin".to_string(),
+            description: "Tokens that are fixed to an external asset".to_string(),
+        },
+    );
+    let version = |registry_nodes: &[RegistryNodeAccount]| {
+        export::token_list(
+            "Registry",
+            registry_nodes,
+            101,
+            TIMESTAMP.to_string(),
+            Some(&previous),
+        )
+        .version
+    };
+
+    assert_eq!(version(&registry_nodes), previous.version);
+    let mut changed = registry_nodes.clone();
+    changed[1].token_name = "Renamed".to_string();
+    assert_eq!(
+        version(&changed),
+        Version {
+            major: 1,
+            minor: 2,
+            patch: 4
+        }
+    );
+    let mut added = changed.clone();
+    added.push(registry_node("THIRD", EntryState::Active));
+    assert_eq!(
+        version(&added),
+        Version {
+            major: 1,
+            minor: 3,
+            patch: 0
+        }
+    );
+    /* Deleting an entry removes its token. */
+    added[0].state = EntryState::Deleted;
+    assert_eq!(
+        version(&added),
+        Version {
+            major: 2,
+            minor: 0,
+            patch: 0
+        }
+    );
+
+    /* Tag definitions are kept from the previous export. */
+    let token_list = export::token_list(
+        "Registry",
+        &registry_nodes,
+        101,
+        TIMESTAMP.to_string(),
+        Some(&previous),
+    );
+    assert_eq!(token_list.tags["stablecoin"].name, "Stablecoin");
+    assert_eq!(token_list.version.to_string(), "1.2.3");
+}
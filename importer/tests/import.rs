@@ -0,0 +1,144 @@
+//! Tests of reading token lists and of splitting them into transactions.
+
+use registry::{instruction::BatchEntry, state::RegistryMetaAccount};
+use registry_importer::{batch, tokenlist::TokenList};
+use solana_sdk::{packet::PACKET_DATA_SIZE, pubkey::Pubkey};
+
+const TOKEN_LIST: &str = r#"{
+    "name": "Solana Token List",
+    "logoURI": "https://cdn.jsdelivr.net/gh/trustwallet/assets@master/blockchains/solana/info/logo.png",
+    "keywords": ["solana", "spl"],
+    "tags": {"stablecoin": {"name": "stablecoin", "description": "Tokens that are fixed to an external asset"}},
+    "timestamp": "2021-03-03T19:57:21+0000",
+    "version": {"major": 0, "minor": 3, "patch": 3},
+    "tokens": [
+        {
+            "chainId": 101,
+            "address": "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+            "symbol": "USDC",
+            "name": "USD Coin",
+            "decimals": 6,
+            "logoURI": "https://example.com/usdc.png",
+            "tags": ["stablecoin"],
+            "extensions": {"coingeckoId": "usd-coin", "serumV3Usdt": "77quYg4MGneUdjgXCunt9GgM1usmrxKY31twEy3WHwcS"}
+        },
+        {
+            "chainId": 103,
+            "address": "So11111111111111111111111111111111111111112",
+            "symbol": "SOL",
+            "name": "Wrapped SOL",
+            "decimals": 9
+        }
+    ]
+}"#;
+
+fn batch_entry(mint_authority: &Pubkey, token_symbol: &str) -> BatchEntry {
+    let token_list = TokenList::from_slice(TOKEN_LIST.as_bytes()).unwrap();
+    let mut data = token_list.tokens[0].entry_data();
+    data.token_symbol = token_symbol.to_string();
+    BatchEntry {
+        mint: Pubkey::new_unique(),
+        mint_authority: *mint_authority,
+        signers: vec![],
+        data,
+    }
+}
+
+#[test]
+fn token_list_entry_data() {
+    let token_list = TokenList::from_slice(TOKEN_LIST.as_bytes()).unwrap();
+    assert_eq!(token_list.name, "Solana Token List");
+    let usdc = token_list.tokens[0].entry_data();
+    assert_eq!(usdc.token_symbol, "USDC");
+    assert_eq!(usdc.token_name, "USD Coin");
+    assert_eq!(usdc.token_logo_url, "https://example.com/usdc.png");
+    assert_eq!(usdc.token_tags, vec!["stablecoin"]);
+    assert_eq!(
+        usdc.token_extensions,
+        vec![
+            vec!["coingeckoId", "usd-coin"],
+            vec![
+                "serumV3Usdt",
+                "77quYg4MGneUdjgXCunt9GgM1usmrxKY31twEy3WHwcS"
+            ],
+        ]
+    );
+
+    /* logoURI, tags and extensions are optional. */
+    let sol = token_list.tokens[1].entry_data();
+    assert_eq!(token_list.tokens[1].chain_id, 103);
+    assert_eq!(sol.token_logo_url, "");
+    assert!(sol.token_tags.is_empty());
+    assert!(sol.token_extensions.is_empty());
+}
+
+#[test]
+fn chunks_fit_in_a_packet() {
+    let payer = Pubkey::new_unique();
+    let registry_meta = RegistryMetaAccount::default();
+    let entries: Vec<BatchEntry> = (0..40)
+        .map(|i| batch_entry(&payer, &format!("TOKEN{}", i)))
+        .collect();
+
+    let chunks = batch::chunk_entries(&registry::id(), &payer, &registry_meta, entries.clone(), 40);
+    assert!(chunks.len() > 1);
+    let first = Pubkey::new_unique();
+    for chunk in &chunks {
+        let size = batch::transaction_size(
+            &registry::id(),
+            &payer,
+            &registry_meta,
+            &first,
+            chunk.clone(),
+        );
+        assert!(size <= PACKET_DATA_SIZE);
+    }
+    /* Chunks are only split where the next entry would not fit. */
+    for pair in chunks.windows(2) {
+        let joined = [pair[0].clone(), vec![pair[1][0].clone()]].concat();
+        let size = batch::transaction_size(&registry::id(), &payer, &registry_meta, &first, joined);
+        assert!(size > PACKET_DATA_SIZE);
+    }
+    assert_eq!(chunks.concat(), entries);
+
+    let chunks = batch::chunk_entries(&registry::id(), &payer, &registry_meta, entries, 2);
+    assert!(chunks.iter().all(|chunk| chunk.len() <= 2));
+}
+
+#[test]
+fn oversized_entry_gets_own_chunk() {
+    let payer = Pubkey::new_unique();
+    let registry_meta = RegistryMetaAccount::default();
+    let mut oversized = batch_entry(&payer, "HUGE");
+    oversized.data.token_name = "x".repeat(PACKET_DATA_SIZE);
+    let entries = vec![
+        batch_entry(&payer, "A"),
+        oversized.clone(),
+        batch_entry(&payer, "B"),
+    ];
+
+    let chunks = batch::chunk_entries(&registry::id(), &payer, &registry_meta, entries, 8);
+    assert_eq!(chunks.len(), 3);
+    assert_eq!(chunks[1], vec![oversized]);
+}
+
+#[test]
+fn created_entries_from_return_data() {
+    let program_id = registry::id();
+    let log_messages = vec![
+        format!("Program {} invoke [1]", program_id),
+        "Program log: RegistryInstruction::CreateEntries".to_string(),
+        "Program log: Created 3 of 4 entries".to_string(),
+        format!("Program return: {} AwAAAA==", program_id),
+        format!("Program {} success", program_id),
+    ];
+    assert_eq!(batch::created_entries(&program_id, &log_messages), Some(3));
+    assert_eq!(
+        batch::created_entries(&program_id, &log_messages[..3]),
+        None
+    );
+    assert_eq!(
+        batch::created_entries(&Pubkey::new_unique(), &log_messages),
+        None
+    );
+}
@@ -265,7 +265,9 @@ describe('CreateEntry', () => {
       'NAME_1',
       'LOGO_URL_1',
       ['TAGS_1_1', 'TAGS_1_2'],
-      [['EXTENSIONS_1_KEY', 'EXTENSIONS_1_VAL']]
+      [['EXTENSIONS_1_KEY', 'EXTENSIONS_1_VAL']],
+      '',
+      userKeypair.publicKey
     ), [userKeypair, userKeypair2])
     const balanceAfter = (await feeMint.getAccountInfo(sourceATA)).amount
     expect(balanceBefore.sub(balanceAfter).toString()).toEqual(ARBITRARY_BIGINT_1.toString())
@@ -315,7 +317,9 @@ describe('CreateEntry', () => {
       'NAME_1',
       'LOGO_URL_1',
       ['TAGS_1_1', 'TAGS_1_2'],
-      [['EXTENSIONS_1_KEY', 'EXTENSIONS_1_VAL']]
+      [['EXTENSIONS_1_KEY', 'EXTENSIONS_1_VAL']],
+      '',
+      userKeypair.publicKey
     ), [userKeypair, userKeypair2])
     const balanceAfter1 = (await feeMint.getAccountInfo(sourceATAKeypair2)).amount
     expect(balanceBefore1.sub(balanceAfter1).toString()).toEqual(ARBITRARY_BIGINT_1.toString())
@@ -330,7 +334,9 @@ describe('CreateEntry', () => {
       'NAME_2',
       'LOGO_URL_2',
       ['TAGS_2_1', 'TAGS_2_2'],
-      [['EXTENSIONS_2_KEY', 'EXTENSIONS_2_VAL']]
+      [['EXTENSIONS_2_KEY', 'EXTENSIONS_2_VAL']],
+      '',
+      userKeypair.publicKey
     ), [userKeypair, userKeypair2])
     const balanceAfter2 = (await feeMint.getAccountInfo(sourceATAKeypair2)).amount
     expect(balanceBefore2.sub(balanceAfter2).toString()).toEqual(ARBITRARY_BIGINT_1.toString())
@@ -345,7 +351,9 @@ describe('CreateEntry', () => {
       'NAME_3',
       'LOGO_URL_3',
       ['TAGS_3_1', 'TAGS_3_2'],
-      [['EXTENSIONS_3_KEY', 'EXTENSIONS_3_VAL']]
+      [['EXTENSIONS_3_KEY', 'EXTENSIONS_3_VAL']],
+      '',
+      userKeypair.publicKey
     ), [userKeypair, userKeypair3])
     const balanceAfter3 = (await feeMint.getAccountInfo(sourceATAKeypair3)).amount
     expect(balanceBefore3.sub(balanceAfter3).toString()).toEqual(ARBITRARY_BIGINT_1.toString())
@@ -125,7 +125,9 @@ describe('DeleteEntry', () => {
       'NAME_2',
       'LOGO_URL_2',
       ['TAGS_2_1', 'TAGS_2_2'],
-      [['EXTENSIONS_2_KEY', 'EXTENSIONS_2_VAL']]
+      [['EXTENSIONS_2_KEY', 'EXTENSIONS_2_VAL']],
+      '',
+      userKeypair.publicKey
     ), [userKeypair, userKeypair2])
     expect(await getAllTokens(connection, programId)).toEqual(new Set([
       {
@@ -245,7 +247,9 @@ describe('DeleteEntry', () => {
       'NAME_2',
       'LOGO_URL_2',
       ['TAGS_2_1', 'TAGS_2_2'],
-      [['EXTENSIONS_2_KEY', 'EXTENSIONS_2_VAL']]
+      [['EXTENSIONS_2_KEY', 'EXTENSIONS_2_VAL']],
+      '',
+      userKeypair.publicKey
     ), [userKeypair, userKeypair2])
     expect(await getAllTokens(connection, programId)).toEqual(new Set([
       {
@@ -318,7 +322,9 @@ describe('DeleteEntry', () => {
       'NAME_1',
       'LOGO_URL_1',
       ['TAGS_1_1', 'TAGS_1_2'],
-      [['EXTENSIONS_1_KEY', 'EXTENSIONS_1_VAL']]
+      [['EXTENSIONS_1_KEY', 'EXTENSIONS_1_VAL']],
+      '',
+      userKeypair.publicKey
     ), [userKeypair, userKeypair3])
     expect(await getAllTokens(connection, programId)).toEqual(new Set([
       {
@@ -6,6 +6,7 @@ import {
   createInstructionUpdateEntry,
   createInstructionTransferFeeAuthority,
   createInstructionTransferTokenAuthority,
+  EntryState,
   RegistryMetaAccount,
   RegistryNodeAccount,
   getRegistryState,
@@ -39,6 +40,16 @@ function randomElement (rng: any, array: any[]): any {
   return array[rng.range(array.length)]
 }
 
+/* Tags and extension keys must be identifiers. */
+function randomIdentifier (rng: any, length: number): string {
+  const characters = 'ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_'
+  let identifier = ''
+  for (let i = 0; i < length; i++) {
+    identifier += randomElement(rng, Array.from(characters))
+  }
+  return identifier
+}
+
 const VERBOSE = false
 const NUM_INSTRUCTIONS = 200
 
@@ -127,7 +138,14 @@ describe('Fuzz', () => {
                 feeAmount: BIGINTS[0],
                 feeMint: MINTS[0],
                 feeDestination: SYSTEM_ACCTS[0],
-                feeUpdateAuthority: USERS[0].publicKey
+                feeUpdateAuthority: USERS[0].publicKey,
+                feeExemptAccounts: [],
+                maxTags: 8,
+                maxExtensions: 16,
+                registryId: '',
+                uniqueSymbols: false,
+                curators: [],
+                pendingFeeUpdateAuthority: null
               },
               [
                 {
@@ -140,7 +158,11 @@ describe('Fuzz', () => {
                   tags: [],
                   extensions: [],
                   updateAuthority: PublicKey.default,
-                  deleted: false
+                  pendingUpdateAuthority: null,
+                  state: EntryState.Active,
+                  deleted: false,
+                  decimals: 0,
+                  verified: false
                 },
                 {
                   publicKey: await getPDA('tail', programId),
@@ -152,7 +174,11 @@ describe('Fuzz', () => {
                   tags: [],
                   extensions: [],
                   updateAuthority: PublicKey.default,
-                  deleted: false
+                  pendingUpdateAuthority: null,
+                  state: EntryState.Active,
+                  deleted: false,
+                  decimals: 0,
+                  verified: false
                 }
               ]
             ]
@@ -223,10 +249,10 @@ describe('Fuzz', () => {
           const tokenSymbol = rng.string(8)
           const tokenName = rng.string(16)
           const tokenLogoURL = rng.string(128)
-          const tokenTags = [rng.string(16), rng.string(16)]
+          const tokenTags = [randomIdentifier(rng, 16), randomIdentifier(rng, 16)]
           const tokenExtensions = [
-            [rng.string(16), rng.string(64)],
-            [rng.string(16), rng.string(64)]
+            [randomIdentifier(rng, 16), rng.string(64)],
+            [randomIdentifier(rng, 16), rng.string(64)]
           ] as Array<[string, string]>
 
           let ix
@@ -240,7 +266,9 @@ describe('Fuzz', () => {
               tokenName,
               tokenLogoURL,
               tokenTags,
-              tokenExtensions
+              tokenExtensions,
+              '',
+              tempKeypair.publicKey
             )
           } catch (error) {
             if (localState == null) {
@@ -258,14 +286,14 @@ describe('Fuzz', () => {
               (node) => node.mint.toString() === mint.toString() && !node.deleted
             )) {
               try {
-                await sendAndConfirmTx(connection, ix, [user])
+                await sendAndConfirmTx(connection, ix, [user, tempKeypair])
                 unreachable()
               } catch (error) {
                 const txLogs = ((error as SendTransactionError).logs as string[]).join(' ')
                 expect(txLogs).toMatch(/RegistryError::PreviouslyRegisteredMint/)
               }
             } else {
-              await sendAndConfirmTx(connection, ix, [user])
+              await sendAndConfirmTx(connection, ix, [user, tempKeypair])
               if (localState[1].some(
                 (node) => node.mint.toString() === mint.toString() && node.deleted
               )) {
@@ -277,6 +305,8 @@ describe('Fuzz', () => {
                     node.tags = tokenTags
                     node.extensions = tokenExtensions
                     node.updateAuthority = user.publicKey
+                    node.pendingUpdateAuthority = null
+                    node.state = EntryState.Active
                     node.deleted = false
                     break
                   }
@@ -293,7 +323,11 @@ describe('Fuzz', () => {
                   tags: tokenTags,
                   extensions: tokenExtensions,
                   updateAuthority: user.publicKey,
-                  deleted: false
+                  pendingUpdateAuthority: null,
+                  state: EntryState.Active,
+                  deleted: false,
+                  decimals: 9,
+                  verified: false
                 })
               }
             }
@@ -334,6 +368,7 @@ describe('Fuzz', () => {
               await sendAndConfirmTx(connection, ix, [user])
               for (const node of localState[1]) {
                 if (node.mint.toString() === mint.toString()) {
+                  node.state = EntryState.Deleted
                   node.deleted = true
                   break
                 }
@@ -385,10 +420,10 @@ describe('Fuzz', () => {
           const tokenSymbol = rng.string(8)
           const tokenName = rng.string(16)
           const tokenLogoURL = rng.string(128)
-          const tokenTags = [rng.string(16), rng.string(16)]
+          const tokenTags = [randomIdentifier(rng, 16), randomIdentifier(rng, 16)]
           const tokenExtensions = [
-            [rng.string(16), rng.string(64)],
-            [rng.string(16), rng.string(64)]
+            [randomIdentifier(rng, 16), rng.string(64)],
+            [randomIdentifier(rng, 16), rng.string(64)]
           ] as Array<[string, string]>
 
           const ix = await createInstructionUpdateEntry(
@@ -435,6 +470,17 @@ describe('Fuzz', () => {
                 const txLogs = ((error as SendTransactionError).logs as string[]).join(' ')
                 expect(txLogs).toMatch(/RegistryError::InvalidTokenUpdateAuthority/)
               }
+            } else if (localState[1].some(
+              (node) => node.mint.toString() === mint.toString() && node.deleted
+            )) {
+              // If the mint exists but has been deleted, tx should yield PreviouslyDeletedMint.
+              try {
+                await sendAndConfirmTx(connection, ix, [user])
+                unreachable()
+              } catch (error) {
+                const txLogs = ((error as SendTransactionError).logs as string[]).join(' ')
+                expect(txLogs).toMatch(/RegistryError::PreviouslyDeletedMint/)
+              }
             } else {
               await sendAndConfirmTx(connection, ix, [user])
               for (const node of localState[1]) {
@@ -485,8 +531,9 @@ describe('Fuzz', () => {
                 expect(txLogs).toMatch(/RegistryError::InvalidFeeUpdateAuthority/)
               }
             } else {
+              // The transfer only takes effect once the new authority accepts it.
               await sendAndConfirmTx(connection, ix, [user])
-              localState[0].feeUpdateAuthority = newFeeAuthority
+              localState[0].pendingFeeUpdateAuthority = newFeeAuthority
             }
           }
           break
@@ -541,11 +588,22 @@ describe('Fuzz', () => {
                 const txLogs = ((error as SendTransactionError).logs as string[]).join(' ')
                 expect(txLogs).toMatch(/RegistryError::InvalidTokenUpdateAuthority/)
               }
+            } else if (localState[1].some(
+              (node) => node.mint.toString() === mint.toString() && node.deleted
+            )) {
+              // If the mint exists but has been deleted, tx should yield PreviouslyDeletedMint.
+              try {
+                await sendAndConfirmTx(connection, ix, [user])
+                unreachable()
+              } catch (error) {
+                const txLogs = ((error as SendTransactionError).logs as string[]).join(' ')
+                expect(txLogs).toMatch(/RegistryError::PreviouslyDeletedMint/)
+              }
             } else {
               await sendAndConfirmTx(connection, ix, [user])
               for (const node of localState[1]) {
                 if (node.mint.toString() === mint.toString()) {
-                  node.updateAuthority = newTokenAuthority
+                  node.pendingUpdateAuthority = newTokenAuthority
                   break
                 }
               }
@@ -1,6 +1,7 @@
 import {
   createInstructionInitializeRegistry,
-  createInstructionTransferFeeAuthority
+  createInstructionTransferFeeAuthority,
+  createInstructionAcceptFeeAuthority
 } from '../index'
 
 import {
@@ -49,6 +50,11 @@ describe('TransferFeeAuthority', () => {
       userKeypair.publicKey,
       userKeypair2.publicKey
     ))
+    await sendAndConfirmTx(connection, await createInstructionAcceptFeeAuthority(
+      connection,
+      programId,
+      userKeypair2.publicKey
+    ), [userKeypair, userKeypair2])
     await assertMetaAccountEquals(
       connection,
       programId,
@@ -86,6 +92,11 @@ describe('TransferFeeAuthority', () => {
       userKeypair.publicKey,
       userKeypair2.publicKey
     ))
+    await sendAndConfirmTx(connection, await createInstructionAcceptFeeAuthority(
+      connection,
+      programId,
+      userKeypair2.publicKey
+    ), [userKeypair, userKeypair2])
     await assertMetaAccountEquals(
       connection,
       programId,
@@ -101,6 +112,11 @@ describe('TransferFeeAuthority', () => {
       userKeypair2.publicKey,
       userKeypair.publicKey
     ), [userKeypair, userKeypair2])
+    await sendAndConfirmTx(connection, await createInstructionAcceptFeeAuthority(
+      connection,
+      programId,
+      userKeypair.publicKey
+    ))
     await assertMetaAccountEquals(
       connection,
       programId,
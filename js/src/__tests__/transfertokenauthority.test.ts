@@ -2,7 +2,8 @@ import {
   getAllTokens,
   createInstructionInitializeRegistry,
   createInstructionCreateEntry,
-  createInstructionTransferTokenAuthority
+  createInstructionTransferTokenAuthority,
+  createInstructionAcceptTokenAuthority
 } from '../index'
 
 import {
@@ -67,6 +68,12 @@ describe('TransferTokenAuthority', () => {
       ARBITRARY_MINTS[1],
       userKeypair2.publicKey
     ))
+    await sendAndConfirmTx(connection, await createInstructionAcceptTokenAuthority(
+      connection,
+      programId,
+      userKeypair2.publicKey,
+      ARBITRARY_MINTS[1]
+    ), [userKeypair, userKeypair2])
     expect(await getAllTokens(connection, programId)).toEqual(new Set([
       {
         mint: ARBITRARY_MINTS[1],
@@ -123,6 +130,12 @@ describe('TransferTokenAuthority', () => {
       ARBITRARY_MINTS[1],
       userKeypair2.publicKey
     ))
+    await sendAndConfirmTx(connection, await createInstructionAcceptTokenAuthority(
+      connection,
+      programId,
+      userKeypair2.publicKey,
+      ARBITRARY_MINTS[1]
+    ), [userKeypair, userKeypair2])
     expect(await getAllTokens(connection, programId)).toEqual(new Set([
       {
         mint: ARBITRARY_MINTS[1],
@@ -142,6 +155,12 @@ describe('TransferTokenAuthority', () => {
       ARBITRARY_MINTS[1],
       userKeypair3.publicKey
     ), [userKeypair, userKeypair2])
+    await sendAndConfirmTx(connection, await createInstructionAcceptTokenAuthority(
+      connection,
+      programId,
+      userKeypair3.publicKey,
+      ARBITRARY_MINTS[1]
+    ), [userKeypair, userKeypair3])
     expect(await getAllTokens(connection, programId)).toEqual(new Set([
       {
         mint: ARBITRARY_MINTS[1],
@@ -161,6 +180,12 @@ describe('TransferTokenAuthority', () => {
       ARBITRARY_MINTS[1],
       userKeypair.publicKey
     ), [userKeypair, userKeypair3])
+    await sendAndConfirmTx(connection, await createInstructionAcceptTokenAuthority(
+      connection,
+      programId,
+      userKeypair.publicKey,
+      ARBITRARY_MINTS[1]
+    ))
     expect(await getAllTokens(connection, programId)).toEqual(new Set([
       {
         mint: ARBITRARY_MINTS[1],
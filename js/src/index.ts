@@ -7,19 +7,54 @@ import {
   SYSVAR_RENT_PUBKEY
 } from '@solana/web3.js'
 
-import { serialize, deserialize } from 'borsh'
+import { BinaryReader, BinaryWriter } from 'borsh'
+import { sha256 } from 'crypto-hash'
 import { registryPrivateKey } from './registryKeypair'
 
 export const PROGRAM_ID = Keypair.fromSecretKey(new Uint8Array(registryPrivateKey)).publicKey
 const TOKEN_PROGRAM_ID = new PublicKey('TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA')
 const ATA_PROGRAM_ID = new PublicKey('ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL')
 
+/* The account layout that the program reads and writes, see `REGISTRY_ACCOUNT_VERSION` in state.rs. */
+const REGISTRY_ACCOUNT_VERSION = 5
+const REGISTRY_META_DISCRIMINATOR = Buffer.from('regmeta\0')
+const REGISTRY_NODE_DISCRIMINATOR = Buffer.from('regnode\0')
+const ACCOUNT_HEADER_LENGTH = 14
+
+/* The versioned wire format of instructions, see `RegistryInstruction` in instruction.rs. */
+const VERSIONED_INSTRUCTION_MARKER = 0xff
+const INSTRUCTION_VERSION = 1
+enum Instruction {
+  InitializeRegistry = 0,
+  UpdateFees = 1,
+  CreateEntry = 2,
+  DeleteEntry = 3,
+  UpdateEntry = 4,
+  TransferFeeAuthority = 5,
+  TransferTokenAuthority = 6,
+  AcceptFeeAuthority = 18,
+  AcceptTokenAuthority = 19
+}
+
+export enum EntryState {
+  Active = 0,
+  Deleted = 1,
+  Closed = 2
+}
+
 export interface RegistryMetaAccount {
   publicKey: PublicKey
   feeAmount: bigint
   feeMint: PublicKey
   feeDestination: PublicKey
   feeUpdateAuthority: PublicKey
+  feeExemptAccounts: PublicKey[]
+  maxTags: number
+  maxExtensions: number
+  registryId: string
+  uniqueSymbols: boolean
+  curators: PublicKey[]
+  pendingFeeUpdateAuthority: PublicKey | null
 }
 export interface RegistryNodeAccount {
   publicKey: PublicKey
@@ -31,7 +66,11 @@ export interface RegistryNodeAccount {
   tags: string[]
   extensions: string[][]
   updateAuthority: PublicKey
+  pendingUpdateAuthority: PublicKey | null
+  state: EntryState
   deleted: boolean
+  decimals: number
+  verified: boolean
 }
 export interface TokenEntry {
   mint: PublicKey
@@ -43,206 +82,31 @@ export interface TokenEntry {
   updateAuthority: PublicKey
 }
 
-class BorshCreateEntryInstructionData {
-  token_symbol = ''
-  token_name = ''
-  token_logo_url = ''
-  token_tags = ['']
-  token_extensions = [['']]
-  constructor (fields: {
-    token_symbol: string
-    token_name: string
-    token_logo_url: string
-    token_tags: string[]
-    token_extensions: string[][]
-  } | undefined = undefined) {
-    if (fields != null) {
-      this.token_symbol = fields.token_symbol
-      this.token_name = fields.token_name
-      this.token_logo_url = fields.token_logo_url
-      this.token_tags = fields.token_tags
-      this.token_extensions = fields.token_extensions
-    }
-  }
-}
-const BorshCreateEntryInstructionDataSchema = new Map([
-  [BorshCreateEntryInstructionData, {
-    kind: 'struct',
-    fields: [
-      ['token_symbol', 'String'],
-      ['token_name', 'String'],
-      ['token_logo_url', 'String'],
-      ['token_tags', ['String']],
-      ['token_extensions', [['String']]]
-    ]
-  }]
-])
-
 /**
  * Returns the RegistryMetaAccount and a generator for all the RegistryMetaNodes.
  *
  */
 export async function getRegistryStateGenerator (
   connection: Connection,
-  programId: PublicKey
+  programId: PublicKey,
+  registryId = ''
 ): Promise<null | [RegistryMetaAccount, AsyncGenerator<RegistryNodeAccount>]> {
-  class BorshRegistryMetaAccount {
-    head_registry_node = new Uint8Array(32)
-    fee_amount = 0
-    fee_mint = new Uint8Array(32)
-    fee_destination = new Uint8Array(32)
-    fee_update_authority = new Uint8Array(32)
-    constructor (fields: {
-      head_registry_node: Uint8Array
-      fee_amount: number
-      fee_mint: Uint8Array
-      fee_destination: Uint8Array
-      fee_update_authority: Uint8Array
-    } | undefined = undefined) {
-      if (fields != null) {
-        this.head_registry_node = fields.head_registry_node
-        this.fee_amount = fields.fee_amount
-        this.fee_mint = fields.fee_mint
-        this.fee_destination = fields.fee_destination
-        this.fee_update_authority = fields.fee_update_authority
-      }
-    }
-  }
-  const BorshRegistryMetaAccountSchema = new Map([
-    [BorshRegistryMetaAccount, {
-      kind: 'struct',
-      fields: [
-        ['head_registry_node', [32]],
-        ['fee_amount', 'u64'],
-        ['fee_mint', [32]],
-        ['fee_destination', [32]],
-        ['fee_update_authority', [32]]
-      ]
-    }]
-  ])
-
-  class BorshRegistryNodeAccount {
-    next_registry_node = new Uint8Array(32)
-    prev_registry_node = new Uint8Array(32)
-    token_mint = new Uint8Array(32)
-    token_symbol = ''
-    token_name = ''
-    token_logo_url = ''
-    token_tags = ['']
-    token_extensions = [['']]
-    token_update_authority = new Uint8Array(32)
-    deleted = false
-    constructor (fields: {
-      next_registry_node: Uint8Array
-      prev_registry_node: Uint8Array
-      token_mint: Uint8Array
-      token_symbol: string
-      token_name: string
-      token_logo_url: string
-      token_tags: string[]
-      token_extensions: string[][]
-      token_update_authority: Uint8Array
-      deleted: boolean
-    } | undefined = undefined) {
-      if (fields != null) {
-        this.next_registry_node = fields.next_registry_node
-        this.prev_registry_node = fields.prev_registry_node
-        this.token_mint = fields.token_mint
-        this.token_symbol = fields.token_symbol
-        this.token_name = fields.token_name
-        this.token_logo_url = fields.token_logo_url
-        this.token_tags = fields.token_tags
-        this.token_extensions = fields.token_extensions
-        this.token_update_authority = fields.token_update_authority
-        this.deleted = fields.deleted
-      }
-    }
-  }
-  const BorshRegistryNodeAccountSchema = new Map([
-    [BorshRegistryNodeAccount, {
-      kind: 'struct',
-      fields: [
-        ['next_registry_node', [32]],
-        ['prev_registry_node', [32]],
-        ['token_mint', [32]],
-        ['token_symbol', 'String'],
-        ['token_name', 'String'],
-        ['token_logo_url', 'String'],
-        ['token_tags', ['String']],
-        ['token_extensions', [['String']]],
-        ['token_update_authority', [32]],
-        ['deleted', 'u8']
-      ]
-    }]
-  ])
-
-  const registryMetaPublicKey = await getPDA('meta', programId)
-  const registryHeadPublicKey = await getPDA('head', programId)
-  const registryMetaAccountInfo = await connection.getAccountInfo(registryMetaPublicKey)
-  const registryHeadAccountInfo = await connection.getAccountInfo(registryHeadPublicKey)
+  const registryMetaAccount = await getRegistryMeta(connection, programId, registryId)
+  const registryHeadPublicKey = await getRegistryHeadAddress(programId, registryId)
+  const registryHeadAccount = await getRegistryNode(connection, registryHeadPublicKey)
 
   /* If the registry has not yet been initialized, return null. */
-  if (registryMetaAccountInfo === null || registryHeadAccountInfo === null) {
+  if (registryMetaAccount === null || registryHeadAccount === null) {
     return null
   }
 
-  const borshRegistryMetaAccount = deserialize(
-    BorshRegistryMetaAccountSchema,
-    BorshRegistryMetaAccount,
-    registryMetaAccountInfo.data
-  )
-  let length = registryHeadAccountInfo.data.readUInt32BE(0)
-  const borshRegistryHeadAccount = deserialize(
-    BorshRegistryNodeAccountSchema,
-    BorshRegistryNodeAccount,
-    registryHeadAccountInfo.data.slice(4, 4 + length)
-  )
-
-  const registryMetaAccount = {
-    publicKey: registryMetaPublicKey,
-    feeAmount: BigInt(borshRegistryMetaAccount.fee_amount),
-    feeMint: new PublicKey(borshRegistryMetaAccount.fee_mint),
-    feeDestination: new PublicKey(borshRegistryMetaAccount.fee_destination),
-    feeUpdateAuthority: new PublicKey(borshRegistryMetaAccount.fee_update_authority)
-  }
-  const registryHeadAccount = {
-    publicKey: registryHeadPublicKey,
-    nextRegistryNode: new PublicKey(borshRegistryHeadAccount.next_registry_node),
-    mint: new PublicKey(borshRegistryHeadAccount.token_mint),
-    symbol: borshRegistryHeadAccount.token_symbol,
-    name: borshRegistryHeadAccount.token_name,
-    logoURL: borshRegistryHeadAccount.token_logo_url,
-    tags: borshRegistryHeadAccount.token_tags,
-    extensions: borshRegistryHeadAccount.token_extensions,
-    updateAuthority: new PublicKey(borshRegistryHeadAccount.token_update_authority),
-    deleted: +borshRegistryHeadAccount.deleted !== 0
-  }
-
   async function * registryNodeAccountsIterator (): AsyncGenerator<RegistryNodeAccount> {
-    yield registryHeadAccount
-    let prevRegistryNode = registryHeadAccount
+    yield registryHeadAccount as RegistryNodeAccount
+    let prevRegistryNode = registryHeadAccount as RegistryNodeAccount
     while (prevRegistryNode.nextRegistryNode.toString() !== PublicKey.default.toString()) {
-      const registryNodePublicKey = prevRegistryNode.nextRegistryNode
-      const registryNodeAccountInfo = await connection.getAccountInfo(registryNodePublicKey)
-      // @ts-expect-error
-      length = registryNodeAccountInfo.data.readUInt32BE(0)
-      const borshRegistryNodeAccount = deserialize(
-        BorshRegistryNodeAccountSchema,
-        BorshRegistryNodeAccount,
-        // @ts-expect-error
-        registryNodeAccountInfo.data.slice(4, 4 + length)
-      )
-      const registryNodeAccount = {
-        publicKey: registryNodePublicKey,
-        nextRegistryNode: new PublicKey(borshRegistryNodeAccount.next_registry_node),
-        mint: new PublicKey(borshRegistryNodeAccount.token_mint),
-        symbol: borshRegistryNodeAccount.token_symbol,
-        name: borshRegistryNodeAccount.token_name,
-        logoURL: borshRegistryNodeAccount.token_logo_url,
-        tags: borshRegistryNodeAccount.token_tags,
-        extensions: borshRegistryNodeAccount.token_extensions,
-        updateAuthority: new PublicKey(borshRegistryNodeAccount.token_update_authority),
-        deleted: +borshRegistryNodeAccount.deleted !== 0
+      const registryNodeAccount = await getRegistryNode(connection, prevRegistryNode.nextRegistryNode)
+      if (registryNodeAccount === null) {
+        throw Error('Registry node ' + prevRegistryNode.nextRegistryNode.toString() + ' does not exist.')
       }
       yield registryNodeAccount
       prevRegistryNode = registryNodeAccount
@@ -257,9 +121,10 @@ export async function getRegistryStateGenerator (
  */
 export async function getRegistryState (
   connection: Connection,
-  programId: PublicKey
+  programId: PublicKey,
+  registryId = ''
 ): Promise<null | [RegistryMetaAccount, RegistryNodeAccount[]]> {
-  const registryStateGenerator = await getRegistryStateGenerator(connection, programId)
+  const registryStateGenerator = await getRegistryStateGenerator(connection, programId, registryId)
   if (registryStateGenerator === null) {
     return null
   } else {
@@ -278,9 +143,10 @@ export async function getRegistryState (
  */
 export async function getAllTokens (
   connection: Connection,
-  programId: PublicKey
+  programId: PublicKey,
+  registryId = ''
 ): Promise<Set<TokenEntry>> {
-  const registryState = await getRegistryState(connection, programId)
+  const registryState = await getRegistryState(connection, programId, registryId)
   if (registryState !== null) {
     const registryNodeAccounts = registryState[1]
     const tokenEntries = new Set<TokenEntry>()
@@ -309,9 +175,10 @@ export async function getAllTokens (
  */
 export async function getAllTokensGenerator (
   connection: Connection,
-  programId: PublicKey
+  programId: PublicKey,
+  registryId = ''
 ): Promise<AsyncGenerator<TokenEntry>> {
-  const registryStateGenerator = await getRegistryStateGenerator(connection, programId)
+  const registryStateGenerator = await getRegistryStateGenerator(connection, programId, registryId)
   async function * allTokensIterator (): AsyncGenerator<TokenEntry> {
     if (registryStateGenerator !== null) {
       const registryNodeAccountsGenerator = registryStateGenerator[1]
@@ -325,15 +192,17 @@ export async function getAllTokensGenerator (
             hasSkippedOne = true
           }
         }
-        prevTokenEntry = {
-          mint: registryNodeAccount.mint,
-          symbol: registryNodeAccount.symbol,
-          name: registryNodeAccount.name,
-          logoURL: registryNodeAccount.logoURL,
-          tags: registryNodeAccount.tags,
-          extensions: registryNodeAccount.extensions,
-          updateAuthority: registryNodeAccount.updateAuthority
-        }
+        prevTokenEntry = registryNodeAccount.deleted
+          ? null
+          : {
+              mint: registryNodeAccount.mint,
+              symbol: registryNodeAccount.symbol,
+              name: registryNodeAccount.name,
+              logoURL: registryNodeAccount.logoURL,
+              tags: registryNodeAccount.tags,
+              extensions: registryNodeAccount.extensions,
+              updateAuthority: registryNodeAccount.updateAuthority
+            }
       }
     }
   }
@@ -348,9 +217,107 @@ export async function getAllTokensGenerator (
  */
 export async function getAllTokensSanitized (
   connection: Connection,
-  programId: PublicKey
+  programId: PublicKey,
+  registryId = ''
 ): Promise<Set<TokenEntry>> {
-  return await getAllTokens(connection, programId)
+  return await getAllTokens(connection, programId, registryId)
+}
+
+/**
+ * Returns the RegistryMetaAccount of the registry, or null if it has not yet
+ * been initialized.
+ *
+ */
+export async function getRegistryMeta (
+  connection: Connection,
+  programId: PublicKey,
+  registryId = ''
+): Promise<null | RegistryMetaAccount> {
+  const registryMetaPublicKey = await getRegistryMetaAddress(programId, registryId)
+  const registryMetaAccountInfo = await connection.getAccountInfo(registryMetaPublicKey)
+  if (registryMetaAccountInfo === null) {
+    return null
+  }
+
+  const reader = unpackAccount(registryMetaAccountInfo.data, REGISTRY_META_DISCRIMINATOR)
+  reader.readFixedArray(32) // head_registry_node
+  const feeAmount = readU64(reader)
+  const feeMint = readPublicKey(reader)
+  const feeDestination = readPublicKey(reader)
+  const feeUpdateAuthority = readPublicKey(reader)
+  const feeExemptAccounts = readPublicKeys(reader, 8)
+  const maxTags = reader.readU8()
+  const maxExtensions = reader.readU8()
+  const storedRegistryId = reader.readString()
+  const uniqueSymbols = reader.readU8() !== 0
+  const curators = readPublicKeys(reader, 8)
+  const pendingFeeUpdateAuthority = readOptionalPublicKey(reader)
+  return {
+    publicKey: registryMetaPublicKey,
+    feeAmount,
+    feeMint,
+    feeDestination,
+    feeUpdateAuthority,
+    feeExemptAccounts,
+    maxTags,
+    maxExtensions,
+    registryId: storedRegistryId,
+    uniqueSymbols,
+    curators,
+    pendingFeeUpdateAuthority
+  }
+}
+
+/**
+ * Returns the RegistryNodeAccount (or RegistryHeadAccount/RegistryTailAccount)
+ * at the given address, or null if it does not exist.
+ *
+ */
+export async function getRegistryNode (
+  connection: Connection,
+  registryNodePublicKey: PublicKey
+): Promise<null | RegistryNodeAccount> {
+  const registryNodeAccountInfo = await connection.getAccountInfo(registryNodePublicKey)
+  if (registryNodeAccountInfo === null) {
+    return null
+  }
+
+  const reader = unpackAccount(registryNodeAccountInfo.data, REGISTRY_NODE_DISCRIMINATOR)
+  const nextRegistryNode = readPublicKey(reader)
+  reader.readFixedArray(32) // prev_registry_node
+  const mint = readPublicKey(reader)
+  const updateAuthority = readPublicKey(reader)
+  const state = reader.readU8() as EntryState
+  const decimals = reader.readU8()
+  readU64(reader) // mint_supply
+  reader.readFixedArray(32) // mint_authority
+  reader.readFixedArray(32) // mint_freeze_authority
+  readU64(reader) // mint_snapshot_slot
+  const symbol = reader.readString()
+  const name = reader.readString()
+  const logoURL = reader.readString()
+  const tags = reader.readArray(() => reader.readString())
+  const extensions = reader.readArray(() => reader.readArray(() => reader.readString()))
+  const verified = reader.readU8() !== 0
+  reader.readFixedArray(32) // verifier
+  reader.readFixedArray(8) // verified_at
+  const pendingUpdateAuthority = readOptionalPublicKey(reader)
+  return {
+    publicKey: registryNodePublicKey,
+    nextRegistryNode,
+    mint,
+    symbol,
+    name,
+    logoURL,
+    tags,
+    extensions,
+    updateAuthority,
+    pendingUpdateAuthority,
+    state,
+    deleted: state === EntryState.Deleted,
+    decimals,
+    verified
+  }
 }
 
 /**
@@ -364,11 +331,12 @@ export async function createInstructionInitializeRegistry (
   userPublicKey: PublicKey,
   feeMintPublicKey: PublicKey,
   feeDestinationPublicKey: PublicKey,
-  feeAmount: bigint
+  feeAmount: bigint,
+  registryId = ''
 ): Promise<TransactionInstruction> {
-  const buffer = Buffer.alloc(9)
-  buffer.writeUInt8(0)
-  buffer.writeBigUInt64BE(feeAmount, 1)
+  const writer = instructionWriter(Instruction.InitializeRegistry)
+  writeU64(writer, feeAmount)
+  writer.writeString(registryId)
 
   const destinationTokenAccount = await getATA(
     connection,
@@ -385,13 +353,13 @@ export async function createInstructionInitializeRegistry (
     { isSigner: false, isWritable: false, pubkey: TOKEN_PROGRAM_ID },
     { isSigner: false, isWritable: false, pubkey: ATA_PROGRAM_ID },
     { isSigner: false, isWritable: false, pubkey: SYSVAR_RENT_PUBKEY },
-    { isSigner: false, isWritable: true, pubkey: await getPDA('meta', programId) },
-    { isSigner: false, isWritable: true, pubkey: await getPDA('head', programId) },
-    { isSigner: false, isWritable: true, pubkey: await getPDA('tail', programId) }
+    { isSigner: false, isWritable: true, pubkey: await getRegistryMetaAddress(programId, registryId) },
+    { isSigner: false, isWritable: true, pubkey: await getRegistryHeadAddress(programId, registryId) },
+    { isSigner: false, isWritable: true, pubkey: await getRegistryTailAddress(programId, registryId) }
   ]
 
   return new TransactionInstruction({
-    data: buffer,
+    data: Buffer.from(writer.toArray()),
     keys: keys,
     programId: programId
   })
@@ -399,7 +367,9 @@ export async function createInstructionInitializeRegistry (
 
 /**
  * Creates a TransactionInstruction corresponding to the UpdateFees contract
- * instruction.
+ * instruction. The user is the fee_update_authority, and pays for the ATA of
+ * the fee destination if it has to be created. If the fee_update_authority is
+ * an SPL Token multisig, pass its signers, and a separate fee-payer.
  *
  */
 export async function createInstructionUpdateFees (
@@ -408,11 +378,13 @@ export async function createInstructionUpdateFees (
   userPublicKey: PublicKey,
   feeMintPublicKey: PublicKey,
   feeDestinationPublicKey: PublicKey,
-  feeAmount: bigint
+  feeAmount: bigint,
+  registryId = '',
+  signers: PublicKey[] = [],
+  payerPublicKey: PublicKey = userPublicKey
 ): Promise<TransactionInstruction> {
-  const buffer = Buffer.alloc(9)
-  buffer.writeUInt8(1)
-  buffer.writeBigUInt64BE(feeAmount, 1)
+  const writer = instructionWriter(Instruction.UpdateFees)
+  writeU64(writer, feeAmount)
 
   const destinationTokenAccount = await getATA(
     connection,
@@ -421,7 +393,7 @@ export async function createInstructionUpdateFees (
   )
 
   const keys = [
-    { isSigner: true, isWritable: false, pubkey: userPublicKey },
+    { isSigner: signers.length === 0, isWritable: false, pubkey: userPublicKey },
     { isSigner: false, isWritable: false, pubkey: feeMintPublicKey },
     { isSigner: false, isWritable: false, pubkey: feeDestinationPublicKey },
     { isSigner: false, isWritable: true, pubkey: destinationTokenAccount },
@@ -429,11 +401,13 @@ export async function createInstructionUpdateFees (
     { isSigner: false, isWritable: false, pubkey: TOKEN_PROGRAM_ID },
     { isSigner: false, isWritable: false, pubkey: ATA_PROGRAM_ID },
     { isSigner: false, isWritable: false, pubkey: SYSVAR_RENT_PUBKEY },
-    { isSigner: false, isWritable: true, pubkey: await getPDA('meta', programId) }
+    { isSigner: false, isWritable: true, pubkey: await getRegistryMetaAddress(programId, registryId) },
+    { isSigner: true, isWritable: true, pubkey: payerPublicKey },
+    ...multisigSigners(signers)
   ]
 
   return new TransactionInstruction({
-    data: buffer,
+    data: Buffer.from(writer.toArray()),
     keys: keys,
     programId: programId
   })
@@ -441,7 +415,10 @@ export async function createInstructionUpdateFees (
 
 /**
  * Creates a TransactionInstruction corresponding to the CreateEntry contract
- * instruction.
+ * instruction. The user pays the fee and the rent of the new node, and is
+ * given its token_update_authority. The mint authority of the mint must sign
+ * as well (or the fee_update_authority, if the mint has none), and defaults to
+ * the user. If it is an SPL Token multisig, pass its signers as `signers`.
  *
  */
 export async function createInstructionCreateEntry (
@@ -453,28 +430,19 @@ export async function createInstructionCreateEntry (
   tokenName: string,
   tokenLogoUrl: string,
   tokenTags: string[],
-  tokenExtensions: Array<[string, string]>
+  tokenExtensions: Array<[string, string]>,
+  registryId = '',
+  mintAuthorityPublicKey: PublicKey = userPublicKey,
+  signers: PublicKey[] = []
 ): Promise<TransactionInstruction> {
-  const serializedFlag = Buffer.alloc(1)
-  serializedFlag.writeUInt8(2)
-  const serializedInstructionData = serialize(
-    BorshCreateEntryInstructionDataSchema,
-    new BorshCreateEntryInstructionData({
-      token_symbol: tokenSymbol,
-      token_name: tokenName,
-      token_logo_url: tokenLogoUrl,
-      token_tags: tokenTags,
-      token_extensions: tokenExtensions
-    })
-  )
-  const buffer = Buffer.concat([Buffer.from(serializedFlag), Buffer.from(serializedInstructionData)])
+  const writer = instructionWriter(Instruction.CreateEntry)
+  writeEntryData(writer, tokenSymbol, tokenName, tokenLogoUrl, tokenTags, tokenExtensions)
 
-  const registryState = await getRegistryState(connection, programId)
-  let registryMetaAccount, registryNodeAccounts
-  if (registryState === null) {
+  const registryMetaAccount = await getRegistryMeta(connection, programId, registryId)
+  const registryHeadPublicKey = await getRegistryHeadAddress(programId, registryId)
+  const registryHeadAccount = await getRegistryNode(connection, registryHeadPublicKey)
+  if (registryMetaAccount === null || registryHeadAccount === null) {
     throw Error('Registry has not yet been initialized.')
-  } else {
-    [registryMetaAccount, registryNodeAccounts] = registryState
   }
 
   const sourceTokenAccount = await getATA(connection, userPublicKey, registryMetaAccount.feeMint)
@@ -491,14 +459,17 @@ export async function createInstructionCreateEntry (
     { isSigner: false, isWritable: true, pubkey: destinationTokenAccount },
     { isSigner: false, isWritable: false, pubkey: SystemProgram.programId },
     { isSigner: false, isWritable: false, pubkey: TOKEN_PROGRAM_ID },
-    { isSigner: false, isWritable: false, pubkey: await getPDA('meta', programId) },
-    { isSigner: false, isWritable: true, pubkey: registryNodeAccounts[0].publicKey },
-    { isSigner: false, isWritable: true, pubkey: registryNodeAccounts[1].publicKey },
-    { isSigner: false, isWritable: true, pubkey: await getPDA(mintPublicKey.toBytes(), programId) }
+    { isSigner: false, isWritable: false, pubkey: registryMetaAccount.publicKey },
+    { isSigner: false, isWritable: true, pubkey: registryHeadPublicKey },
+    { isSigner: false, isWritable: true, pubkey: registryHeadAccount.nextRegistryNode },
+    { isSigner: false, isWritable: true, pubkey: await getRegistryNodeAddress(programId, mintPublicKey, registryId) },
+    { isSigner: signers.length === 0, isWritable: false, pubkey: mintAuthorityPublicKey },
+    await symbolClaimAccount(programId, tokenSymbol, registryId),
+    ...multisigSigners(signers)
   ]
 
   return new TransactionInstruction({
-    data: buffer,
+    data: Buffer.from(writer.toArray()),
     keys: keys,
     programId: programId
   })
@@ -506,27 +477,38 @@ export async function createInstructionCreateEntry (
 
 /**
  * Creates a TransactionInstruction corresponding to the DeleteEntry contract
- * instruction.
+ * instruction. If the registry has unique symbols, the lamports of the
+ * released symbol claim go to the destination, which defaults to the user.
  *
  */
 export async function createInstructionDeleteEntry (
   connection: Connection,
   programId: PublicKey,
   userPublicKey: PublicKey,
-  mintPublicKey: PublicKey
+  mintPublicKey: PublicKey,
+  registryId = '',
+  signers: PublicKey[] = [],
+  destinationPublicKey: PublicKey = userPublicKey
 ): Promise<TransactionInstruction> {
-  const buffer = Buffer.alloc(1)
-  buffer.writeUInt8(3)
+  const writer = instructionWriter(Instruction.DeleteEntry)
+
+  /* The claim of the current symbol, which is left to the program to reject if the mint has no node. */
+  const registryNodePublicKey = await getRegistryNodeAddress(programId, mintPublicKey, registryId)
+  const registryNodeAccount = await getRegistryNode(connection, registryNodePublicKey)
+  const currentTokenSymbol = registryNodeAccount?.symbol ?? ''
 
   const keys = [
-    { isSigner: true, isWritable: false, pubkey: userPublicKey },
+    { isSigner: signers.length === 0, isWritable: false, pubkey: userPublicKey },
     { isSigner: false, isWritable: false, pubkey: mintPublicKey },
-    { isSigner: false, isWritable: false, pubkey: await getPDA('meta', programId) },
-    { isSigner: false, isWritable: true, pubkey: await getPDA(mintPublicKey.toBytes(), programId) }
+    { isSigner: false, isWritable: false, pubkey: await getRegistryMetaAddress(programId, registryId) },
+    { isSigner: false, isWritable: true, pubkey: registryNodePublicKey },
+    await symbolClaimAccount(programId, currentTokenSymbol, registryId),
+    { isSigner: false, isWritable: true, pubkey: destinationPublicKey },
+    ...multisigSigners(signers)
   ]
 
   return new TransactionInstruction({
-    data: Buffer.from(buffer),
+    data: Buffer.from(writer.toArray()),
     keys: keys,
     programId: programId
   })
@@ -534,7 +516,9 @@ export async function createInstructionDeleteEntry (
 
 /**
  * Creates a TransactionInstruction corresponding to the UpdateEntry contract
- * instruction.
+ * instruction. The user is the token_update_authority, and settles the rent
+ * difference of the resized node. If the token_update_authority is an SPL
+ * Token multisig, pass its signers, and a separate fee-payer.
  *
  */
 export async function createInstructionUpdateEntry (
@@ -546,31 +530,33 @@ export async function createInstructionUpdateEntry (
   tokenName: string,
   tokenLogoUrl: string,
   tokenTags: string[],
-  tokenExtensions: Array<[string, string]>
+  tokenExtensions: Array<[string, string]>,
+  registryId = '',
+  signers: PublicKey[] = [],
+  payerPublicKey: PublicKey = userPublicKey
 ): Promise<TransactionInstruction> {
-  const serializedFlag = Buffer.alloc(1)
-  serializedFlag.writeUInt8(4)
-  const serializedInstructionData = serialize(
-    BorshCreateEntryInstructionDataSchema,
-    new BorshCreateEntryInstructionData({
-      token_symbol: tokenSymbol,
-      token_name: tokenName,
-      token_logo_url: tokenLogoUrl,
-      token_tags: tokenTags,
-      token_extensions: tokenExtensions
-    })
-  )
-  const buffer = Buffer.concat([Buffer.from(serializedFlag), Buffer.from(serializedInstructionData)])
+  const writer = instructionWriter(Instruction.UpdateEntry)
+  writeEntryData(writer, tokenSymbol, tokenName, tokenLogoUrl, tokenTags, tokenExtensions)
+
+  /* The claim of the current symbol, which is left to the program to reject if the mint has no node. */
+  const registryNodePublicKey = await getRegistryNodeAddress(programId, mintPublicKey, registryId)
+  const registryNodeAccount = await getRegistryNode(connection, registryNodePublicKey)
+  const currentTokenSymbol = registryNodeAccount?.symbol ?? ''
 
   const keys = [
-    { isSigner: true, isWritable: false, pubkey: userPublicKey },
+    { isSigner: signers.length === 0, isWritable: false, pubkey: userPublicKey },
     { isSigner: false, isWritable: false, pubkey: mintPublicKey },
-    { isSigner: false, isWritable: false, pubkey: await getPDA('meta', programId) },
-    { isSigner: false, isWritable: true, pubkey: await getPDA(mintPublicKey.toBytes(), programId) }
+    { isSigner: false, isWritable: false, pubkey: await getRegistryMetaAddress(programId, registryId) },
+    { isSigner: false, isWritable: true, pubkey: registryNodePublicKey },
+    { isSigner: false, isWritable: false, pubkey: SystemProgram.programId },
+    await symbolClaimAccount(programId, currentTokenSymbol, registryId),
+    await symbolClaimAccount(programId, tokenSymbol, registryId),
+    { isSigner: true, isWritable: true, pubkey: payerPublicKey },
+    ...multisigSigners(signers)
   ]
 
   return new TransactionInstruction({
-    data: buffer,
+    data: Buffer.from(writer.toArray()),
     keys: keys,
     programId: programId
   })
@@ -578,26 +564,56 @@ export async function createInstructionUpdateEntry (
 
 /**
  * Creates a TransactionInstruction corresponding to the TransferFeeAuthority
- * contract instruction.
+ * contract instruction. The transfer only takes effect once the new authority
+ * signs the instruction of createInstructionAcceptFeeAuthority.
  *
  */
 export async function createInstructionTransferFeeAuthority (
   connection: Connection,
   programId: PublicKey,
   userPublicKey: PublicKey,
-  newFeeAuthorityPublicKey: PublicKey
+  newFeeAuthorityPublicKey: PublicKey,
+  registryId = '',
+  signers: PublicKey[] = []
 ): Promise<TransactionInstruction> {
-  const buffer = Buffer.alloc(1)
-  buffer.writeUInt8(5)
+  const writer = instructionWriter(Instruction.TransferFeeAuthority)
 
   const keys = [
-    { isSigner: true, isWritable: false, pubkey: userPublicKey },
+    { isSigner: signers.length === 0, isWritable: false, pubkey: userPublicKey },
     { isSigner: false, isWritable: false, pubkey: newFeeAuthorityPublicKey },
-    { isSigner: false, isWritable: true, pubkey: await getPDA('meta', programId) }
+    { isSigner: false, isWritable: true, pubkey: await getRegistryMetaAddress(programId, registryId) },
+    ...multisigSigners(signers)
   ]
 
   return new TransactionInstruction({
-    data: Buffer.from(buffer),
+    data: Buffer.from(writer.toArray()),
+    keys: keys,
+    programId: programId
+  })
+}
+
+/**
+ * Creates a TransactionInstruction corresponding to the AcceptFeeAuthority
+ * contract instruction, signed by the new authority.
+ *
+ */
+export async function createInstructionAcceptFeeAuthority (
+  connection: Connection,
+  programId: PublicKey,
+  userPublicKey: PublicKey,
+  registryId = '',
+  signers: PublicKey[] = []
+): Promise<TransactionInstruction> {
+  const writer = instructionWriter(Instruction.AcceptFeeAuthority)
+
+  const keys = [
+    { isSigner: signers.length === 0, isWritable: false, pubkey: userPublicKey },
+    { isSigner: false, isWritable: true, pubkey: await getRegistryMetaAddress(programId, registryId) },
+    ...multisigSigners(signers)
+  ]
+
+  return new TransactionInstruction({
+    data: Buffer.from(writer.toArray()),
     keys: keys,
     programId: programId
   })
@@ -605,7 +621,8 @@ export async function createInstructionTransferFeeAuthority (
 
 /**
  * Creates a TransactionInstruction corresponding to the TransferTokenAuthority
- * contract instruction.
+ * contract instruction. The transfer only takes effect once the new authority
+ * signs the instruction of createInstructionAcceptTokenAuthority.
  *
  */
 export async function createInstructionTransferTokenAuthority (
@@ -613,33 +630,150 @@ export async function createInstructionTransferTokenAuthority (
   programId: PublicKey,
   userPublicKey: PublicKey,
   mintPublicKey: PublicKey,
-  newTokenAuthorityPublicKey: PublicKey
+  newTokenAuthorityPublicKey: PublicKey,
+  registryId = '',
+  signers: PublicKey[] = []
 ): Promise<TransactionInstruction> {
-  const buffer = Buffer.alloc(1)
-  buffer.writeUInt8(6)
+  const writer = instructionWriter(Instruction.TransferTokenAuthority)
 
   const keys = [
-    { isSigner: true, isWritable: false, pubkey: userPublicKey },
+    { isSigner: signers.length === 0, isWritable: false, pubkey: userPublicKey },
+    { isSigner: false, isWritable: false, pubkey: mintPublicKey },
+    { isSigner: false, isWritable: false, pubkey: await getRegistryMetaAddress(programId, registryId) },
+    { isSigner: false, isWritable: true, pubkey: await getRegistryNodeAddress(programId, mintPublicKey, registryId) },
+    { isSigner: false, isWritable: false, pubkey: newTokenAuthorityPublicKey },
+    ...multisigSigners(signers)
+  ]
+
+  return new TransactionInstruction({
+    data: Buffer.from(writer.toArray()),
+    keys: keys,
+    programId: programId
+  })
+}
+
+/**
+ * Creates a TransactionInstruction corresponding to the AcceptTokenAuthority
+ * contract instruction, signed by the new authority.
+ *
+ */
+export async function createInstructionAcceptTokenAuthority (
+  connection: Connection,
+  programId: PublicKey,
+  userPublicKey: PublicKey,
+  mintPublicKey: PublicKey,
+  registryId = '',
+  signers: PublicKey[] = []
+): Promise<TransactionInstruction> {
+  const writer = instructionWriter(Instruction.AcceptTokenAuthority)
+
+  const keys = [
+    { isSigner: signers.length === 0, isWritable: false, pubkey: userPublicKey },
     { isSigner: false, isWritable: false, pubkey: mintPublicKey },
-    { isSigner: false, isWritable: false, pubkey: await getPDA('meta', programId) },
-    { isSigner: false, isWritable: true, pubkey: await getPDA(mintPublicKey.toBytes(), programId) },
-    { isSigner: false, isWritable: false, pubkey: newTokenAuthorityPublicKey }
+    { isSigner: false, isWritable: false, pubkey: await getRegistryMetaAddress(programId, registryId) },
+    { isSigner: false, isWritable: true, pubkey: await getRegistryNodeAddress(programId, mintPublicKey, registryId) },
+    ...multisigSigners(signers)
   ]
 
   return new TransactionInstruction({
-    data: Buffer.from(buffer),
+    data: Buffer.from(writer.toArray()),
     keys: keys,
     programId: programId
   })
 }
 
+/* Addresses, see `RegistrySeeds` in state.rs */
+
+/**
+ * Returns the address of the RegistryMetaAccount of the registry.
+ *
+ */
+export async function getRegistryMetaAddress (
+  programId: PublicKey,
+  registryId = ''
+): Promise<PublicKey> {
+  return await getPDA(await taggedSeeds('meta', registryId), programId)
+}
+
+/**
+ * Returns the address of the RegistryHeadAccount, the sentinel node at the
+ * front of the linked list.
+ *
+ */
+export async function getRegistryHeadAddress (
+  programId: PublicKey,
+  registryId = ''
+): Promise<PublicKey> {
+  return await getPDA(await taggedSeeds('head', registryId), programId)
+}
+
+/**
+ * Returns the address of the RegistryTailAccount, the sentinel node at the
+ * back of the linked list.
+ *
+ */
+export async function getRegistryTailAddress (
+  programId: PublicKey,
+  registryId = ''
+): Promise<PublicKey> {
+  return await getPDA(await taggedSeeds('tail', registryId), programId)
+}
+
+/**
+ * Returns the address of the RegistryNodeAccount of the mint.
+ *
+ */
+export async function getRegistryNodeAddress (
+  programId: PublicKey,
+  mintPublicKey: PublicKey,
+  registryId = ''
+): Promise<PublicKey> {
+  if (registryId === '') {
+    return await getPDA(mintPublicKey.toBytes(), programId)
+  }
+  return await getPDA(await taggedSeeds('node', registryId, mintPublicKey.toBytes()), programId)
+}
+
+/**
+ * Returns the address of the SymbolClaimAccount of the symbol, or null if the
+ * symbol cannot be normalized, as such symbols have no claim.
+ *
+ */
+export async function getSymbolClaimAddress (
+  programId: PublicKey,
+  tokenSymbol: string,
+  registryId = ''
+): Promise<null | PublicKey> {
+  const normalizedSymbol = normalizeSymbol(tokenSymbol)
+  if (normalizedSymbol === null) {
+    return null
+  }
+  return await getPDA(await taggedSeeds('symbol', registryId, Buffer.from(normalizedSymbol)), programId)
+}
+
+/**
+ * Returns the form of a symbol that unique symbols are enforced on, or null if
+ * it has none. Mirrors `validation::normalize_symbol` of the program.
+ *
+ */
+export function normalizeSymbol (tokenSymbol: string): null | string {
+  const normalizedSymbol = Array.from(tokenSymbol)
+    .filter((c) => !isInvisible(c))
+    .map((c) => Array.from(c.toLowerCase()).map(foldConfusable).join(''))
+    .join('')
+  if (normalizedSymbol.length === 0 || Buffer.byteLength(normalizedSymbol) > 32) {
+    return null
+  }
+  return normalizedSymbol
+}
+
 /* Utilities */
 export async function getPDA (
-  seed: string | Uint8Array,
+  seeds: string | Uint8Array | Array<string | Uint8Array>,
   programId: PublicKey
 ): Promise<PublicKey> {
   const publicKey = (await PublicKey.findProgramAddress(
-    [Buffer.from(seed)],
+    (Array.isArray(seeds) ? seeds : [seeds]).map((seed) => Buffer.from(seed)),
     programId
   ))[0]
   return publicKey
@@ -660,3 +794,164 @@ async function getATA (
   ))[0]
   return associatedTokenAccount
 }
+
+/* The seeds of the default registry have no hash of the registry id. */
+async function taggedSeeds (
+  tag: string,
+  registryId: string,
+  key: Uint8Array | null = null
+): Promise<Uint8Array[]> {
+  const seeds = [Buffer.from(tag)]
+  if (registryId !== '') {
+    seeds.push(Buffer.from(await sha256(registryId, { outputFormat: 'buffer' })))
+  }
+  if (key !== null) {
+    seeds.push(Buffer.from(key))
+  }
+  return seeds
+}
+
+/* The program ignores the claim of a symbol that has none, so the system program takes its place. */
+async function symbolClaimAccount (
+  programId: PublicKey,
+  tokenSymbol: string,
+  registryId: string
+): Promise<{ isSigner: boolean, isWritable: boolean, pubkey: PublicKey }> {
+  const symbolClaimPublicKey = await getSymbolClaimAddress(programId, tokenSymbol, registryId)
+  if (symbolClaimPublicKey === null) {
+    return { isSigner: false, isWritable: false, pubkey: SystemProgram.programId }
+  }
+  return { isSigner: false, isWritable: true, pubkey: symbolClaimPublicKey }
+}
+
+function multisigSigners (
+  signers: PublicKey[]
+): Array<{ isSigner: boolean, isWritable: boolean, pubkey: PublicKey }> {
+  return signers.map((signer) => ({ isSigner: true, isWritable: false, pubkey: signer }))
+}
+
+function isInvisible (c: string): boolean {
+  const code = c.codePointAt(0) as number
+  return code === 0x00ad || code === 0x034f || code === 0x180e ||
+    (code >= 0x200b && code <= 0x200f) || (code >= 0x2060 && code <= 0x2064) || code === 0xfeff
+}
+
+const CONFUSABLES: { [c: string]: string } = {
+  0: 'o',
+  ο: 'o',
+  о: 'o',
+  1: 'l',
+  i: 'l',
+  ı: 'l',
+  ι: 'l',
+  і: 'l',
+  ӏ: 'l',
+  α: 'a',
+  а: 'a',
+  β: 'b',
+  в: 'b',
+  с: 'c',
+  ϲ: 'c',
+  ԁ: 'd',
+  ε: 'e',
+  е: 'e',
+  η: 'h',
+  н: 'h',
+  һ: 'h',
+  ј: 'j',
+  κ: 'k',
+  к: 'k',
+  μ: 'm',
+  м: 'm',
+  ν: 'n',
+  ρ: 'p',
+  р: 'p',
+  ԛ: 'q',
+  ѕ: 's',
+  τ: 't',
+  т: 't',
+  υ: 'y',
+  у: 'y',
+  ԝ: 'w',
+  χ: 'x',
+  х: 'x',
+  ζ: 'z'
+}
+
+function foldConfusable (c: string): string {
+  const code = c.codePointAt(0) as number
+  /* Fullwidth forms of ASCII characters. */
+  if (code >= 0xff01 && code <= 0xff5e) {
+    return foldConfusable(String.fromCodePoint(code - 0xfee0))
+  }
+  return CONFUSABLES[c] ?? c
+}
+
+/* Returns a reader of the borsh serialization following the AccountHeader. */
+function unpackAccount (data: Buffer, discriminator: Buffer): BinaryReader {
+  if (data.length < ACCOUNT_HEADER_LENGTH || !data.slice(0, 8).equals(discriminator)) {
+    throw Error('Account is not a ' + discriminator.toString().replace('\0', '') + ' account.')
+  }
+  const version = data.readUInt8(8)
+  if (version !== REGISTRY_ACCOUNT_VERSION) {
+    throw Error('Account has layout version ' + version.toString() + ', upgrade it with MigrateAccount.')
+  }
+  const dataLength = data.readUInt32LE(10)
+  return new BinaryReader(data.slice(ACCOUNT_HEADER_LENGTH, ACCOUNT_HEADER_LENGTH + dataLength))
+}
+
+function readU64 (reader: BinaryReader): bigint {
+  return Buffer.from(reader.readFixedArray(8)).readBigUInt64LE(0)
+}
+
+function readPublicKey (reader: BinaryReader): PublicKey {
+  return new PublicKey(reader.readFixedArray(32))
+}
+
+/* Unused slots of lists of addresses are zeroed. */
+function readPublicKeys (reader: BinaryReader, length: number): PublicKey[] {
+  const publicKeys = []
+  for (let i = 0; i < length; i++) {
+    const publicKey = readOptionalPublicKey(reader)
+    if (publicKey !== null) {
+      publicKeys.push(publicKey)
+    }
+  }
+  return publicKeys
+}
+
+function readOptionalPublicKey (reader: BinaryReader): PublicKey | null {
+  const publicKey = readPublicKey(reader)
+  return publicKey.equals(PublicKey.default) ? null : publicKey
+}
+
+function instructionWriter (instruction: Instruction): BinaryWriter {
+  const writer = new BinaryWriter()
+  writer.writeU8(VERSIONED_INSTRUCTION_MARKER)
+  writer.writeU8(INSTRUCTION_VERSION)
+  writer.writeU8(instruction)
+  return writer
+}
+
+function writeU64 (writer: BinaryWriter, value: bigint): void {
+  const buffer = Buffer.alloc(8)
+  buffer.writeBigUInt64LE(value)
+  writer.writeFixedArray(buffer)
+}
+
+function writeEntryData (
+  writer: BinaryWriter,
+  tokenSymbol: string,
+  tokenName: string,
+  tokenLogoUrl: string,
+  tokenTags: string[],
+  tokenExtensions: string[][]
+): void {
+  writer.writeString(tokenSymbol)
+  writer.writeString(tokenName)
+  writer.writeString(tokenLogoUrl)
+  writer.writeArray(tokenTags, (tag: string) => writer.writeString(tag))
+  writer.writeArray(tokenExtensions, (extension: string[]) =>
+    writer.writeArray(extension, (field: string) => writer.writeString(field))
+  )
+}
@@ -0,0 +1,7 @@
+fn main() {
+    /* `entrypoint!` checks for the BPF target and the custom heap and panic features of
+    solana-program. Declared here rather than in a `[lints]` table, which needs a newer Cargo than
+    BPF toolchains have. Older Cargo reads these lines as build script metadata and ignores them. */
+    println!("cargo:rustc-check-cfg=cfg(target_os, values(\"solana\"))");
+    println!("cargo:rustc-check-cfg=cfg(feature, values(\"custom-heap\", \"custom-panic\"))");
+}
@@ -0,0 +1,18 @@
+//! Decode arbitrary instruction data. Decoding must never panic, and whatever decodes must survive
+//! a round trip through the versioned wire format.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use registry::{error::RegistryError, instruction::RegistryInstruction};
+
+fuzz_target!(|input: &[u8]| {
+    match RegistryInstruction::unpack(input) {
+        Ok(instruction) => {
+            assert_eq!(
+                RegistryInstruction::unpack(&instruction.pack()),
+                Ok(instruction)
+            );
+        }
+        Err(error) => assert_eq!(error, RegistryError::InvalidInstructionData),
+    }
+});
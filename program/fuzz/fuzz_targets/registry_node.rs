@@ -0,0 +1,34 @@
+//! Deserialize arbitrary account data as a RegistryNodeAccount, the way the processor does before
+//! every node update. Whatever deserializes must serialize back to the same bytes, and the
+//! fixed-offset accessors must agree with it.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use registry::state::{AccountHeader, RegistryNodeAccount, REGISTRY_NODE_DISCRIMINATOR};
+
+fuzz_target!(|input: &[u8]| {
+    let token_mint = RegistryNodeAccount::unpack_token_mint(input);
+    let token_update_authority = RegistryNodeAccount::unpack_token_update_authority(input);
+    let state = RegistryNodeAccount::unpack_state(input);
+    let registry_node = match RegistryNodeAccount::unpack_account_data(input) {
+        Ok(registry_node) => registry_node,
+        Err(_) => return,
+    };
+
+    let header = AccountHeader::unpack(input, &REGISTRY_NODE_DISCRIMINATOR).unwrap();
+    let data = registry_node.pack_account_data(header.bump_seed).unwrap();
+    assert_eq!(&input[..data.len()], &data[..]);
+    assert_eq!(token_mint.unwrap(), &registry_node.token_mint);
+    assert_eq!(
+        token_update_authority.unwrap(),
+        &registry_node.token_update_authority
+    );
+    assert_eq!(state.unwrap(), registry_node.state);
+
+    let mut data = data;
+    RegistryNodeAccount::pack_state(&mut data, registry_node.state).unwrap();
+    assert_eq!(
+        RegistryNodeAccount::unpack_account_data(&data).unwrap(),
+        registry_node
+    );
+});
@@ -50,6 +50,76 @@ pub enum RegistryError {
     InvalidNumberOfAccounts,
     #[error("The provided instruction data cannot be parsed.")]
     InvalidInstructionData,
+    #[error("The provided fee source account does not hold the fee mint.")]
+    InvalidFeeSourceMint,
+    #[error("The provided fee source account is frozen.")]
+    FrozenFeeSourceAccount,
+    #[error("The provided fee source account cannot cover the registration fee.")]
+    InsufficientFeeBalance,
+    #[error("Passed more fee exempt accounts than the registry can store.")]
+    TooManyFeeExemptAccounts,
+    #[error("The token symbol is too long.")]
+    SymbolTooLong,
+    #[error("The token symbol is empty or contains invalid characters.")]
+    InvalidSymbol,
+    #[error("The token name is too long.")]
+    NameTooLong,
+    #[error("The token name is empty or contains invalid characters.")]
+    InvalidName,
+    #[error("The token logo URL is too long.")]
+    LogoUrlTooLong,
+    #[error("The token logo URL contains invalid characters.")]
+    InvalidLogoUrl,
+    #[error("Passed more token tags than a registry node can store.")]
+    TooManyTags,
+    #[error("A token tag is too long.")]
+    TagTooLong,
+    #[error("A token tag is empty or contains invalid characters.")]
+    InvalidTag,
+    #[error("Passed more token extensions than a registry node can store.")]
+    TooManyExtensions,
+    #[error("A token extension is not a key-value pair.")]
+    InvalidExtension,
+    #[error("A token extension key or value is too long.")]
+    ExtensionTooLong,
+    #[error("A token extension key is empty or contains invalid characters.")]
+    InvalidExtensionKey,
+    #[error("A token extension value contains invalid characters.")]
+    InvalidExtensionValue,
+    #[error("The provided neighboring Registry Nodes are not correct.")]
+    InvalidRegistryNodeNeighbor,
+    #[error("The registry node of the provided mint has been closed.")]
+    ClosedMint,
+    #[error("The mint authority of the provided mint has not signed.")]
+    InvalidMintAuthority,
+    #[error(
+        "The provided mint has no mint authority, so the fee update authority must sign instead."
+    )]
+    RevokedMintAuthority,
+    #[error("The account is not of the expected type, or was created before account headers and must be migrated.")]
+    InvalidAccountType,
+    #[error("The account layout version is not supported by this program.")]
+    InvalidAccountVersion,
+    #[error("The account already uses the current layout.")]
+    AccountAlreadyMigrated,
+    #[error("The registry id must be at most 32 ASCII letters, digits, dashes or underscores.")]
+    InvalidRegistryId,
+    #[error("The token symbol is already used by another mint in this registry.")]
+    SymbolAlreadyClaimed,
+    #[error("Passed more curators than the registry can store.")]
+    TooManyCurators,
+    #[error("Attempted to verify or unverify a token without being a curator of the registry.")]
+    InvalidCurator,
+    #[error("There is no pending authority transfer.")]
+    NoPendingAuthority,
+    #[error("The provided account is not the pending authority.")]
+    InvalidPendingAuthority,
+    #[error("Not enough signers of the multisig authority have signed.")]
+    MissingMultisigSigners,
+    #[error("The provided event data cannot be parsed.")]
+    InvalidEventData,
+    #[error("The total fee of the created entries overflows.")]
+    FeeOverflow,
 }
 
 impl PrintProgramError for RegistryError {
@@ -120,6 +190,110 @@ impl PrintProgramError for RegistryError {
             RegistryError::InvalidInstructionData => {
                 msg!("RegistryError::InvalidInstructionData - The provided instruction data cannot be parsed.")
             }
+            RegistryError::InvalidFeeSourceMint => {
+                msg!("RegistryError::InvalidFeeSourceMint - The provided fee source account does not hold the fee mint.")
+            }
+            RegistryError::FrozenFeeSourceAccount => {
+                msg!("RegistryError::FrozenFeeSourceAccount - The provided fee source account is frozen.")
+            }
+            RegistryError::InsufficientFeeBalance => {
+                msg!("RegistryError::InsufficientFeeBalance - The provided fee source account cannot cover the registration fee.")
+            }
+            RegistryError::TooManyFeeExemptAccounts => {
+                msg!("RegistryError::TooManyFeeExemptAccounts - Passed more fee exempt accounts than the registry can store.")
+            }
+            RegistryError::SymbolTooLong => {
+                msg!("RegistryError::SymbolTooLong - The token symbol is too long.")
+            }
+            RegistryError::InvalidSymbol => {
+                msg!("RegistryError::InvalidSymbol - The token symbol is empty or contains invalid characters.")
+            }
+            RegistryError::NameTooLong => {
+                msg!("RegistryError::NameTooLong - The token name is too long.")
+            }
+            RegistryError::InvalidName => {
+                msg!("RegistryError::InvalidName - The token name is empty or contains invalid characters.")
+            }
+            RegistryError::LogoUrlTooLong => {
+                msg!("RegistryError::LogoUrlTooLong - The token logo URL is too long.")
+            }
+            RegistryError::InvalidLogoUrl => {
+                msg!("RegistryError::InvalidLogoUrl - The token logo URL contains invalid characters.")
+            }
+            RegistryError::TooManyTags => {
+                msg!("RegistryError::TooManyTags - Passed more token tags than a registry node can store.")
+            }
+            RegistryError::TagTooLong => {
+                msg!("RegistryError::TagTooLong - A token tag is too long.")
+            }
+            RegistryError::InvalidTag => {
+                msg!("RegistryError::InvalidTag - A token tag is empty or contains invalid characters.")
+            }
+            RegistryError::TooManyExtensions => {
+                msg!("RegistryError::TooManyExtensions - Passed more token extensions than a registry node can store.")
+            }
+            RegistryError::InvalidExtension => {
+                msg!("RegistryError::InvalidExtension - A token extension is not a key-value pair.")
+            }
+            RegistryError::ExtensionTooLong => {
+                msg!(
+                    "RegistryError::ExtensionTooLong - A token extension key or value is too long."
+                )
+            }
+            RegistryError::InvalidExtensionKey => {
+                msg!("RegistryError::InvalidExtensionKey - A token extension key is empty or contains invalid characters.")
+            }
+            RegistryError::InvalidExtensionValue => {
+                msg!("RegistryError::InvalidExtensionValue - A token extension value contains invalid characters.")
+            }
+            RegistryError::InvalidRegistryNodeNeighbor => {
+                msg!("RegistryError::InvalidRegistryNodeNeighbor - The provided neighboring Registry Nodes are not correct.")
+            }
+            RegistryError::ClosedMint => {
+                msg!("RegistryError::ClosedMint - The registry node of the provided mint has been closed.")
+            }
+            RegistryError::InvalidMintAuthority => {
+                msg!("RegistryError::InvalidMintAuthority - The mint authority of the provided mint has not signed.")
+            }
+            RegistryError::RevokedMintAuthority => {
+                msg!("RegistryError::RevokedMintAuthority - The provided mint has no mint authority, so the fee update authority must sign instead.")
+            }
+            RegistryError::InvalidAccountType => {
+                msg!("RegistryError::InvalidAccountType - The account is not of the expected type, or was created before account headers and must be migrated.")
+            }
+            RegistryError::InvalidAccountVersion => {
+                msg!("RegistryError::InvalidAccountVersion - The account layout version is not supported by this program.")
+            }
+            RegistryError::AccountAlreadyMigrated => {
+                msg!("RegistryError::AccountAlreadyMigrated - The account already uses the current layout.")
+            }
+            RegistryError::InvalidRegistryId => {
+                msg!("RegistryError::InvalidRegistryId - The registry id must be at most 32 ASCII letters, digits, dashes or underscores.")
+            }
+            RegistryError::SymbolAlreadyClaimed => {
+                msg!("RegistryError::SymbolAlreadyClaimed - The token symbol is already used by another mint in this registry.")
+            }
+            RegistryError::TooManyCurators => {
+                msg!("RegistryError::TooManyCurators - Passed more curators than the registry can store.")
+            }
+            RegistryError::InvalidCurator => {
+                msg!("RegistryError::InvalidCurator - Attempted to verify or unverify a token without being a curator of the registry.")
+            }
+            RegistryError::NoPendingAuthority => {
+                msg!("RegistryError::NoPendingAuthority - There is no pending authority transfer.")
+            }
+            RegistryError::InvalidPendingAuthority => {
+                msg!("RegistryError::InvalidPendingAuthority - The provided account is not the pending authority.")
+            }
+            RegistryError::MissingMultisigSigners => {
+                msg!("RegistryError::MissingMultisigSigners - Not enough signers of the multisig authority have signed.")
+            }
+            RegistryError::InvalidEventData => {
+                msg!("RegistryError::InvalidEventData - The provided event data cannot be parsed.")
+            }
+            RegistryError::FeeOverflow => {
+                msg!("RegistryError::FeeOverflow - The total fee of the created entries overflows.")
+            }
         }
     }
 }
@@ -0,0 +1,137 @@
+use crate::error::RegistryError;
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{log::sol_log_data, program_error::ProgramError, pubkey::Pubkey};
+
+/// The current version of the event wire format. It is the first byte of every event.
+pub const EVENT_VERSION: u8 = 1;
+
+/// An event logged by the processor after every successful mutation of a registry, so that indexers
+/// do not have to diff account snapshots. Each event is logged with `sol_log_data` as a single field
+/// holding `EVENT_VERSION` followed by the borsh serialization of a RegistryEventLog, which appears
+/// base64-encoded in the "Program data: " line of the transaction logs.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct RegistryEventLog {
+    /// The address of the RegistryMetaAccount of the registry that was mutated.
+    pub registry_meta: [u8; 32],
+    pub event: RegistryEvent,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub enum RegistryEvent {
+    /// Logged by InitializeRegistry.
+    RegistryInitialized {
+        registry_id: String,
+        fee_update_authority: [u8; 32],
+        fee_mint: [u8; 32],
+        fee_destination: [u8; 32],
+        fee_amount: u64,
+    },
+    /// Logged by UpdateFees.
+    FeesUpdated {
+        fee_mint: [u8; 32],
+        fee_destination: [u8; 32],
+        fee_amount: u64,
+    },
+    /// Logged by CreateEntry, including when it replaces a Deleted node.
+    EntryCreated {
+        mint: [u8; 32],
+        authority: [u8; 32],
+        symbol: String,
+    },
+    /// Logged by UpdateEntry. Each field holds the new value if it changed, and is None otherwise.
+    EntryUpdated {
+        mint: [u8; 32],
+        token_symbol: Option<String>,
+        token_name: Option<String>,
+        token_logo_url: Option<String>,
+        token_tags: Option<Vec<String>>,
+        token_extensions: Option<Vec<Vec<String>>>,
+        /// Whether the update cleared the node's verification.
+        unverified: bool,
+    },
+    /// Logged by DeleteEntry.
+    EntryDeleted { mint: [u8; 32] },
+    /// Logged by RestoreEntry.
+    EntryRestored { mint: [u8; 32] },
+    /// Logged by CloseEntry.
+    EntryClosed { mint: [u8; 32] },
+    /// Logged by RefreshMintInfo.
+    MintInfoRefreshed {
+        mint: [u8; 32],
+        decimals: u8,
+        supply: u64,
+    },
+    /// Logged by VerifyEntry.
+    EntryVerified {
+        mint: [u8; 32],
+        verifier: [u8; 32],
+        verified_at: i64,
+    },
+    /// Logged by UnverifyEntry.
+    EntryUnverified { mint: [u8; 32], curator: [u8; 32] },
+    /// Logged by TransferFeeAuthority and TransferTokenAuthority.
+    AuthorityTransferProposed {
+        authority: AuthorityType,
+        current_authority: [u8; 32],
+        pending_authority: [u8; 32],
+    },
+    /// Logged by CancelFeeAuthorityTransfer and CancelTokenAuthorityTransfer.
+    AuthorityTransferCancelled {
+        authority: AuthorityType,
+        pending_authority: [u8; 32],
+    },
+    /// Logged by AcceptFeeAuthority, AcceptTokenAuthority and ReclaimTokenAuthority.
+    AuthorityTransferred {
+        authority: AuthorityType,
+        previous_authority: [u8; 32],
+        new_authority: [u8; 32],
+    },
+    /// Logged by UpdateFeeExemptions.
+    FeeExemptionsUpdated { fee_exempt_accounts: Vec<[u8; 32]> },
+    /// Logged by UpdateEntryLimits.
+    EntryLimitsUpdated { max_tags: u8, max_extensions: u8 },
+    /// Logged by UpdateSymbolUniqueness.
+    SymbolUniquenessUpdated { unique_symbols: bool },
+    /// Logged by UpdateCurators.
+    CuratorsUpdated { curators: Vec<[u8; 32]> },
+    /// Logged by MigrateAccount.
+    AccountMigrated { account: [u8; 32] },
+}
+
+/// The authority that an authority event refers to.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AuthorityType {
+    /// `RegistryMetaAccount::fee_update_authority`.
+    Fee,
+    /// `RegistryNodeAccount::token_update_authority` of the node of `mint`.
+    Token { mint: [u8; 32] },
+}
+
+impl RegistryEventLog {
+    /// Serialize the event in the wire format described on RegistryEventLog.
+    pub fn pack(&self) -> Result<Vec<u8>, ProgramError> {
+        let mut buffer = vec![EVENT_VERSION];
+        self.serialize(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    /// Deserialize an event from the (base64-decoded) data of a "Program data: " log line.
+    pub fn unpack(input: &[u8]) -> Result<Self, RegistryError> {
+        match input {
+            [EVENT_VERSION, rest @ ..] => {
+                Self::try_from_slice(rest).or(Err(RegistryError::InvalidEventData))
+            }
+            _ => Err(RegistryError::InvalidEventData),
+        }
+    }
+}
+
+/// Log `event` for the registry whose RegistryMetaAccount is at `registry_meta`.
+pub fn emit(registry_meta: &Pubkey, event: RegistryEvent) -> Result<(), ProgramError> {
+    let event_log = RegistryEventLog {
+        registry_meta: registry_meta.to_bytes(),
+        event,
+    };
+    sol_log_data(&[&event_log.pack()?]);
+    Ok(())
+}
@@ -1,9 +1,37 @@
-use crate::{error::RegistryError, state::CreateUpdateEntryInstructionData};
-use borsh::BorshDeserialize;
+use crate::{
+    error::RegistryError,
+    state::{
+        CreateUpdateEntryInstructionData, RegistryMetaAccount, RegistryNodeAccount, RegistrySeeds,
+        UpdateEntryLimitsInstructionData, UpdateFeeExemptionsInstructionData,
+    },
+    validation,
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program, sysvar,
+};
+use spl_associated_token_account::get_associated_token_address;
+use std::convert::TryInto;
+
+/// The first byte of an instruction in the versioned wire format. Legacy instructions instead start
+/// with their instruction number, which is always smaller.
+pub const VERSIONED_INSTRUCTION_MARKER: u8 = 0xff;
 
+/// The current version of the versioned wire format.
+pub const INSTRUCTION_VERSION: u8 = 1;
+
+/// Instructions are serialized as `[VERSIONED_INSTRUCTION_MARKER, INSTRUCTION_VERSION]` followed by
+/// the borsh serialization of the RegistryInstruction. The legacy format described under "Legacy
+/// Instruction Data" is still accepted by `RegistryInstruction::unpack`.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
 pub enum RegistryInstruction {
     /**
-     * Initialize the registry.
+     * Initialize a registry. A program can host any number of registries, each with its own fee
+     * settings and entries, told apart by `registry_id`. The accounts of a registry are derived
+     * from the seeds given by `state::RegistrySeeds`. The registry with an empty `registry_id`
+     * lives at the addresses used before registries had ids.
      *
      * Accounts:
      * 0. [signer] Fee-payer. Will be given `fee_update_authority`. Must be owned by the
@@ -19,18 +47,23 @@ pub enum RegistryInstruction {
      * 9. [writable] The RegistryHeadAccount.
      * 10. [writable] The RegistryTailAccount.
      *
-     * Instruction Data:
+     * Legacy Instruction Data:
      * Byte 0: Instruction number (here, it equals 0).
      * Bytes 1-8: The `fee_amount` in big-endian order.
+     * The `registry_id` is empty.
      *
      */
-    InitializeRegistry { fee_amount: u64 },
+    InitializeRegistry {
+        fee_amount: u64,
+        registry_id: String,
+    },
 
     /**
      * Update the fees for token registration.
      *
      * Accounts:
-     * 0. [signer] Fee-payer. Must have pubkey matching `RegistryHeadAccount::fee_update_authority`.
+     * 0. [signer] The `RegistryMetaAccount::fee_update_authority`. If it is an SPL Token multisig, it
+     *    does not sign itself, and at least `m` of its signers follow account 9 instead.
      * 1. [] The new `fee_mint`. Must be owned by the token program.
      * 2. [] The new `fee_destination`. Must be owned by the system program.
      * 3. [writable] The ATA of the fee destination for the fee mint.
@@ -39,8 +72,11 @@ pub enum RegistryInstruction {
      * 6. [] The ATA program.
      * 7. [] The sysvar rent program.
      * 8. [writable] The RegistryMetaAccount.
+     * 9. [signer, writable] Fee-payer. Pays for the ATA of the fee destination, if it has to be
+     *    created. May be the same account as 0.
+     * 10. ..10+M [signer] The signers of the multisig `fee_update_authority`, if it is one.
      *
-     * Instruction Data:
+     * Legacy Instruction Data:
      * Byte 0: Instruction number (here, it equals 1).
      * Bytes 1-8: The new `fee_amount` in big-endian order.
      *
@@ -51,20 +87,33 @@ pub enum RegistryInstruction {
      * Create a new registry node for the supplied mint address.
      *
      * Accounts:
-     * 0. [signer, writable] Fee-payer. Will be given `token_update_authority`.
+     * 0. [signer, writable] Fee-payer. Will be given `token_update_authority`. Pays the rent for the
+     *    new RegistryNodeAccount, which is sized to fit the supplied data.
      * 1. [] Mint address to create a `RegistryNode` for. Must have not been registered before. Must be owned by the token program.
-     * 2. [writable] The source account. Must be the ATA of the fee-payer.
+     * 2. [writable] The source account. Must be the ATA of the fee-payer. Must be initialized and
+     *    hold at least `fee_amount` of `fee_mint`, unless the fee-payer is exempt from fees.
      * 3. [writable] The destination account. Must be the ATA of `fee_destination`.
      * 4. [] The system program.
      * 5. [] The token program.
      * 6. [] The RegistryMetaAccount.
      * 7. [writable] The RegistryHeadAccount.
      * 8. [writable] The first RegistryNodeAccount after the RegistryHeadAccount.
-     * 9. [writable] The new RegistryNodeAccount.
+     * 9. [writable] The new RegistryNodeAccount. Does not need to be funded in advance, but any
+     *    lamports it already holds count towards its rent.
+     * 10. [signer] The `mint_authority` of the mint. If the mint has no mint authority, the
+     *     `fee_update_authority` must sign instead. May be the same account as the fee-payer. If it
+     *     is an SPL Token multisig, it does not sign itself, and at least `m` of its signers follow
+     *     account 11 instead.
+     * 11. [writable] The SymbolClaimAccount of `token_symbol`. Only used if the registry has
+     *     `unique_symbols` set, in which case it is created if needed and must not be held by
+     *     another mint.
+     * 12. ..12+M [signer] The signers of the multisig mint authority, if it is one.
      *
-     * Instruction Data:
+     * Legacy Instruction Data:
      * Byte 0: Instruction number (here, it equals 2).
-     * Bytes 1-?: The borsh serialization of a CreateUpdateEntryInstructionData.
+     * Bytes 1-?: The borsh serialization of a CreateUpdateEntryInstructionData. Every field must
+     *    respect the limits in `state.rs` (e.g, `MAX_SYMBOL_LENGTH`) and the `max_tags` and
+     *    `max_extensions` of the registry, as checked by `validation::validate_entry`.
      *
      */
     CreateEntry {
@@ -76,32 +125,50 @@ pub enum RegistryInstruction {
     },
 
     /**
-     * Delete the (unique) registry node corresponding the supplied mint address.
+     * Delete the (unique) registry node corresponding the supplied mint address. This is a soft
+     * delete: the node is only marked as deleted, and stays in the linked list. See `CloseEntry`.
      *
      * Accounts:
-     * 0. [signer] Fee-payer. Must have pubkey matching `token_update_authority`.
+     * 0. [signer] The `token_update_authority`. If it is an SPL Token multisig, it does not sign
+     *    itself, and at least `m` of its signers follow account 5 instead.
      * 1. [] The address of the mint to be deleted.
      * 2. [] The RegistryMetaAccount.
      * 3. [writable] The RegistryNodeAccount corresponding to the mint to be deleted.
+     * 4. [writable] The SymbolClaimAccount of the node's `token_symbol`. Only used if the registry
+     *    has `unique_symbols` set, in which case it is released and its lamports are refunded to
+     *    the destination.
+     * 5. [writable] The account that receives the lamports of the released SymbolClaimAccount.
+     * 6. ..6+M [signer] The signers of the multisig `token_update_authority`, if it is one.
      *
-     * Instruction Data:
+     * Legacy Instruction Data:
      * Byte 0: Instruction number (here, it equals 3).
      *
      */
     DeleteEntry,
 
     /**
-     * Update the registry node corresponding to the supplied mint address.
+     * Update the registry node corresponding to the supplied mint address. The RegistryNodeAccount is
+     * reallocated to fit the new data, and the rent difference is settled with the fee-payer.
      *
      * Accounts:
-     * 0. [signer] Fee-payer. Must have pubkey matching `token_update_authority`.
+     * 0. [signer] The `token_update_authority`. If it is an SPL Token multisig, it does not sign
+     *    itself, and at least `m` of its signers follow account 7 instead.
      * 1. [] The address of the mint to be updated. Must already be in the registry.
      * 2. [] The RegistryMetaAccount.
-     * 3. [writable] The RegistryNodeAccount to update.
+     * 3. [writable] The RegistryNodeAccount to update. Its verification, if any, is cleared.
+     * 4. [] The system program.
+     * 5. [writable] The SymbolClaimAccount of the node's current `token_symbol`.
+     * 6. [writable] The SymbolClaimAccount of the new `token_symbol`. May be the same account as 5.
+     *    Both are only used if the registry has `unique_symbols` set, in which case the current
+     *    symbol is released and the new one is claimed, as in CreateEntry.
+     * 7. [signer, writable] Fee-payer. Settles the rent difference. May be the same account as 0.
+     * 8. ..8+M [signer] The signers of the multisig `token_update_authority`, if it is one.
      *
-     * Instruction Data:
+     * Legacy Instruction Data:
      * Byte 0: Instruction number (here, it equals 4).
-     * Bytes 1-?: The borsh serialization of a CreateUpdateEntryInstructionData.
+     * Bytes 1-?: The borsh serialization of a CreateUpdateEntryInstructionData. Every field must
+     *    respect the limits in `state.rs` (e.g, `MAX_SYMBOL_LENGTH`) and the `max_tags` and
+     *    `max_extensions` of the registry, as checked by `validation::validate_entry`.
      *
      */
     UpdateEntry {
@@ -113,53 +180,354 @@ pub enum RegistryInstruction {
     },
 
     /**
-     * Transfer the `RegistryMetaAccount::fee_update_authority` to a different account.
+     * Propose a different account as the `RegistryMetaAccount::fee_update_authority`. The transfer
+     * only takes effect once the new account signs AcceptFeeAuthority, and replaces any transfer
+     * that is already pending.
      *
      * Accounts:
-     * 0. [signer] Fee-payer. Must have pubkey matching `fee_update_authority`.
-     * 1. [] The new account to transfer authority to. Must be owned by the system program.
+     * 0. [signer] The `fee_update_authority`. If it is an SPL Token multisig, it does not sign
+     *    itself, and at least `m` of its signers follow account 2 instead.
+     * 1. [] The new account to transfer authority to. May be any account that can sign, such as a
+     *    program derived address, or an SPL Token multisig.
      * 2. [writable] The RegistryMetaAccount.
+     * 3. ..3+M [signer] The signers of the multisig `fee_update_authority`, if it is one.
      *
-     * Instruction Data:
+     * Legacy Instruction Data:
      * Byte 0: Instruction number (here, it equals 5).
      *
      */
     TransferFeeAuthority,
 
     /**
-     * Transfer the `RegistryNodeAccount::token_update_authority` to a different account.
+     * Propose a different account as the `RegistryNodeAccount::token_update_authority`. The transfer
+     * only takes effect once the new account signs AcceptTokenAuthority, and replaces any transfer
+     * that is already pending.
      *
      * Accounts:
-     * 0. [signer] Fee-payer. Must have pubkey matching `token_update_authority`.
+     * 0. [signer] The `token_update_authority`. If it is an SPL Token multisig, it does not sign
+     *    itself, and at least `m` of its signers follow account 4 instead.
      * 1. [] The address of the mint to be updated. Must already be in the registry.
      * 2. [] The RegistryMetaAccount.
      * 3. [writable] The RegistryNodeAccount to update.
-     * 4. [] The new account to transfer authority to. Must be owned by the system program.
+     * 4. [] The new account to transfer authority to. May be any account that can sign, such as a
+     *    program derived address, or an SPL Token multisig.
+     * 5. ..5+M [signer] The signers of the multisig `token_update_authority`, if it is one.
      *
-     * Instruction Data:
+     * Legacy Instruction Data:
      * Byte 0: Instruction number (here, it equals 6).
      *
      */
     TransferTokenAuthority,
+
+    /**
+     * Replace the list of accounts that are exempt from paying the registration fee. The
+     * `fee_update_authority` is always exempt.
+     *
+     * Accounts:
+     * 0. [signer] The `fee_update_authority`. If it is an SPL Token multisig, it does not sign
+     *    itself, and at least `m` of its signers follow account 1 instead.
+     * 1. [writable] The RegistryMetaAccount.
+     * 2. ..2+M [signer] The signers of the multisig `fee_update_authority`, if it is one.
+     *
+     * Legacy Instruction Data:
+     * Byte 0: Instruction number (here, it equals 7).
+     * Bytes 1-?: The borsh serialization of an UpdateFeeExemptionsInstructionData.
+     *
+     */
+    UpdateFeeExemptions { fee_exempt_accounts: Vec<[u8; 32]> },
+
+    /**
+     * Update the maximum number of tags and extensions that a registry node may have. Existing
+     * nodes are only checked against the new limits when they are next updated.
+     *
+     * Accounts:
+     * 0. [signer] The `fee_update_authority`. If it is an SPL Token multisig, it does not sign
+     *    itself, and at least `m` of its signers follow account 1 instead.
+     * 1. [writable] The RegistryMetaAccount.
+     * 2. ..2+M [signer] The signers of the multisig `fee_update_authority`, if it is one.
+     *
+     * Legacy Instruction Data:
+     * Byte 0: Instruction number (here, it equals 8).
+     * Bytes 1-2: The borsh serialization of an UpdateEntryLimitsInstructionData.
+     *
+     */
+    UpdateEntryLimits { max_tags: u8, max_extensions: u8 },
+
+    /**
+     * Permanently delete the registry node corresponding to the supplied mint address. Unlike
+     * `DeleteEntry`, the node is removed from the linked list and its account is closed, with its
+     * lamports sent to the supplied destination. The mint can then be registered again from scratch.
+     *
+     * Accounts:
+     * 0. [signer] The `token_update_authority`. If it is an SPL Token multisig, it does not sign
+     *    itself, and at least `m` of its signers follow account 7 instead.
+     * 1. [] The address of the mint to be deleted.
+     * 2. [] The RegistryMetaAccount.
+     * 3. [writable] The RegistryNodeAccount corresponding to the mint to be deleted.
+     * 4. [writable] The RegistryNodeAccount at `prev_registry_node` of the node to be deleted.
+     * 5. [writable] The RegistryNodeAccount at `next_registry_node` of the node to be deleted.
+     * 6. [writable] The account that receives the lamports of the deleted node.
+     * 7. [writable] The SymbolClaimAccount of the node's `token_symbol`. Only used if the registry
+     *    has `unique_symbols` set, in which case it is released and its lamports are also sent to
+     *    the destination.
+     * 8. ..8+M [signer] The signers of the multisig `token_update_authority`, if it is one.
+     *
+     */
+    CloseEntry,
+
+    /**
+     * Undo a `DeleteEntry` of the registry node corresponding to the supplied mint address.
+     *
+     * Accounts:
+     * 0. [signer] The `token_update_authority`. If it is an SPL Token multisig, it does not sign
+     *    itself, and at least `m` of its signers follow account 6 instead.
+     * 1. [] The address of the mint to be restored. Its node must have been deleted.
+     * 2. [] The RegistryMetaAccount.
+     * 3. [writable] The RegistryNodeAccount corresponding to the mint to be restored.
+     * 4. [writable] The SymbolClaimAccount of the node's `token_symbol`. Only used if the registry
+     *    has `unique_symbols` set, in which case the symbol is claimed again, as in CreateEntry.
+     * 5. [] The system program.
+     * 6. [signer, writable] Fee-payer. Pays for the SymbolClaimAccount. May be the same account as
+     *    0.
+     * 7. ..7+M [signer] The signers of the multisig `token_update_authority`, if it is one.
+     *
+     */
+    RestoreEntry,
+
+    /**
+     * Take back the `token_update_authority` of a registry node by proving control of its mint.
+     * Takes effect immediately, and cancels any pending transfer.
+     *
+     * Accounts:
+     * 0. [signer] The `mint_authority` of the mint. If the mint has no mint authority, the
+     *    `fee_update_authority` must sign instead. If it is an SPL Token multisig, it does not sign
+     *    itself, and at least `m` of its signers follow account 4 instead.
+     * 1. [] The address of the mint. Must already be in the registry.
+     * 2. [] The RegistryMetaAccount.
+     * 3. [writable] The RegistryNodeAccount to update.
+     * 4. [] The new account to transfer authority to.
+     * 5. ..5+M [signer] The signers of the multisig mint authority, if it is one.
+     *
+     */
+    ReclaimTokenAuthority,
+
+    /**
+     * Resync the `mint_*` fields of a registry node (decimals, supply, mint and freeze authority)
+     * with its mint. Anyone may call this.
+     *
+     * Accounts:
+     * 0. [] The address of the mint. Must already be in the registry.
+     * 1. [] The RegistryMetaAccount.
+     * 2. [writable] The RegistryNodeAccount to update.
+     *
+     */
+    RefreshMintInfo,
+
+    /**
+     * Upgrade a RegistryMetaAccount or RegistryNodeAccount (including the head and tail nodes)
+     * stored in an earlier layout version to the current layout, one version at a time,
+     * reallocating it if needed. Anyone may call this. If the account shrinks, the excess rent stays in the account
+     * until the node's next UpdateEntry refunds it to the `token_update_authority`.
+     *
+     * Accounts:
+     * 0. [signer, writable] The account of the user, which pays for any additional rent.
+     * 1. [writable] The account to migrate.
+     * 2. [] The system program.
+     * 3. [] Optional: the RegistryMetaAccount of the registry that the node being migrated belongs
+     *    to, which must already be migrated. Omitted for metas and for nodes of the default
+     *    registry.
+     *
+     */
+    MigrateAccount,
+
+    /**
+     * Turn `RegistryMetaAccount::unique_symbols` on or off. Registry nodes that already exist only
+     * claim their symbol when they are next created, updated or restored.
+     *
+     * Accounts:
+     * 0. [signer] The `fee_update_authority`. If it is an SPL Token multisig, it does not sign
+     *    itself, and at least `m` of its signers follow account 1 instead.
+     * 1. [writable] The RegistryMetaAccount.
+     * 2. ..2+M [signer] The signers of the multisig `fee_update_authority`, if it is one.
+     *
+     */
+    UpdateSymbolUniqueness { unique_symbols: bool },
+
+    /**
+     * Replace the list of curators of the registry, who may verify and unverify registry nodes.
+     * The `fee_update_authority` is always a curator. Nodes verified by a removed curator stay
+     * verified.
+     *
+     * Accounts:
+     * 0. [signer] The `fee_update_authority`. If it is an SPL Token multisig, it does not sign
+     *    itself, and at least `m` of its signers follow account 1 instead.
+     * 1. [writable] The RegistryMetaAccount.
+     * 2. ..2+M [signer] The signers of the multisig `fee_update_authority`, if it is one.
+     *
+     */
+    UpdateCurators { curators: Vec<[u8; 32]> },
+
+    /**
+     * Mark the registry node corresponding to the supplied mint address as verified by a curator,
+     * recording the curator and the current time. The verification is cleared by the next
+     * UpdateEntry.
+     *
+     * Accounts:
+     * 0. [signer] The curator. Must be in `RegistryMetaAccount::curators`, or be the
+     *    `fee_update_authority`. If it is an SPL Token multisig, it does not sign itself, and at
+     *    least `m` of its signers follow account 3 instead.
+     * 1. [] The address of the mint to be verified. Must already be in the registry.
+     * 2. [] The RegistryMetaAccount.
+     * 3. [writable] The RegistryNodeAccount to verify. Must be Active.
+     * 4. ..4+M [signer] The signers of the multisig curator, if it is one.
+     *
+     */
+    VerifyEntry,
+
+    /**
+     * Clear the verification of the registry node corresponding to the supplied mint address.
+     *
+     * Accounts:
+     * 0. [signer] The curator. Must be in `RegistryMetaAccount::curators`, or be the
+     *    `fee_update_authority`. If it is an SPL Token multisig, it does not sign itself, and at
+     *    least `m` of its signers follow account 3 instead.
+     * 1. [] The address of the mint to be unverified. Must already be in the registry.
+     * 2. [] The RegistryMetaAccount.
+     * 3. [writable] The RegistryNodeAccount to unverify.
+     * 4. ..4+M [signer] The signers of the multisig curator, if it is one.
+     *
+     */
+    UnverifyEntry,
+
+    /**
+     * Complete a transfer of the `RegistryMetaAccount::fee_update_authority` proposed by
+     * TransferFeeAuthority.
+     *
+     * Accounts:
+     * 0. [signer] The new authority. Must have pubkey matching `pending_fee_update_authority`. If it
+     *    is an SPL Token multisig, it does not sign itself, and at least `m` of its signers follow
+     *    account 1 instead.
+     * 1. [writable] The RegistryMetaAccount.
+     * 2. ..2+M [signer] The signers of the multisig new authority, if it is one.
+     *
+     */
+    AcceptFeeAuthority,
+
+    /**
+     * Complete a transfer of the `RegistryNodeAccount::token_update_authority` proposed by
+     * TransferTokenAuthority.
+     *
+     * Accounts:
+     * 0. [signer] The new authority. Must have pubkey matching `pending_token_update_authority`. If
+     *    it is an SPL Token multisig, it does not sign itself, and at least `m` of its signers
+     *    follow account 3 instead.
+     * 1. [] The address of the mint. Must already be in the registry.
+     * 2. [] The RegistryMetaAccount.
+     * 3. [writable] The RegistryNodeAccount to update.
+     * 4. ..4+M [signer] The signers of the multisig new authority, if it is one.
+     *
+     */
+    AcceptTokenAuthority,
+
+    /**
+     * Withdraw a transfer of the `RegistryMetaAccount::fee_update_authority` proposed by
+     * TransferFeeAuthority.
+     *
+     * Accounts:
+     * 0. [signer] The `fee_update_authority`. If it is an SPL Token multisig, it does not sign
+     *    itself, and at least `m` of its signers follow account 1 instead.
+     * 1. [writable] The RegistryMetaAccount.
+     * 2. ..2+M [signer] The signers of the multisig `fee_update_authority`, if it is one.
+     *
+     */
+    CancelFeeAuthorityTransfer,
+
+    /**
+     * Withdraw a transfer of the `RegistryNodeAccount::token_update_authority` proposed by
+     * TransferTokenAuthority.
+     *
+     * Accounts:
+     * 0. [signer] The `token_update_authority`. If it is an SPL Token multisig, it does not sign
+     *    itself, and at least `m` of its signers follow account 3 instead.
+     * 1. [] The address of the mint. Must already be in the registry.
+     * 2. [] The RegistryMetaAccount.
+     * 3. [writable] The RegistryNodeAccount to update.
+     * 4. ..4+M [signer] The signers of the multisig `token_update_authority`, if it is one.
+     *
+     */
+    CancelTokenAuthorityTransfer,
+
+    /**
+     * Create registry nodes for several mints at once. Each entry is checked like in CreateEntry,
+     * but an entry that fails its checks (e.g, because its mint is already registered, or its mint
+     * authority has not signed) is skipped instead of failing the transaction. The new nodes are
+     * spliced in after the RegistryHeadAccount together, in the order of `entries`, and
+     * `fee_amount` is charged once for every entry that was created. The number of created entries
+     * is set as the return data, as a little-endian u32.
+     *
+     * Only the checks of an entry are skipped on failure. The batch is all-or-nothing for anything
+     * else: if the fee-payer cannot pay the rent of a node, or the source account cannot cover the
+     * fee of every created entry, the transaction fails and no entry is created.
+     *
+     * Accounts:
+     * 0. [signer, writable] Fee-payer. Will be given `token_update_authority` of every created
+     *    entry, and pays the rent for their RegistryNodeAccounts.
+     * 1. [writable] The source account. Must be the ATA of the fee-payer. Must be initialized and
+     *    hold at least `fee_amount` of `fee_mint` for every created entry, unless the fee-payer is
+     *    exempt from fees.
+     * 2. [writable] The destination account. Must be the ATA of `fee_destination`.
+     * 3. [] The system program.
+     * 4. [] The token program.
+     * 5. [] The RegistryMetaAccount.
+     * 6. [writable] The RegistryHeadAccount.
+     * 7. [writable] The first RegistryNodeAccount after the RegistryHeadAccount.
+     * 8. ..8+4*N Four accounts for every entry i, from account 8+4*i:
+     *    0. [] The mint of the entry.
+     *    1. [writable] The new RegistryNodeAccount of the entry.
+     *    2. [signer] The `mint_authority` of the mint of the entry, as in CreateEntry.
+     *    3. [writable] The SymbolClaimAccount of the symbol of the entry, as in CreateEntry.
+     *
+     * The accounts of the entries are followed by the signers of any multisig mint authorities
+     * among them, each passed once.
+     *
+     */
+    CreateEntries {
+        entries: Vec<CreateUpdateEntryInstructionData>,
+    },
 }
 
 impl RegistryInstruction {
+    /// Deserialize an instruction in either the versioned or the legacy wire format.
     pub fn unpack(input: &[u8]) -> Result<Self, RegistryError> {
+        match input {
+            [VERSIONED_INSTRUCTION_MARKER, INSTRUCTION_VERSION, rest @ ..] => {
+                Self::try_from_slice(rest).or(Err(RegistryError::InvalidInstructionData))
+            }
+            [VERSIONED_INSTRUCTION_MARKER, ..] => Err(RegistryError::InvalidInstructionData),
+            _ => Self::unpack_legacy(input),
+        }
+    }
+
+    /// Deserialize an instruction in the legacy wire format, where the first byte is the
+    /// instruction number and the rest is described under "Legacy Instruction Data" for each
+    /// instruction. Kept so that existing clients keep working while they migrate to `pack`.
+    pub fn unpack_legacy(input: &[u8]) -> Result<Self, RegistryError> {
         let (tag, rest) = input
             .split_first()
             .ok_or(RegistryError::InvalidInstructionData)?;
         Ok(match tag {
             0 | 1 => {
-                let fee_amount = u64::from_be_bytes([
-                    rest[0], rest[1], rest[2], rest[3], rest[4], rest[5], rest[6], rest[7],
-                ]);
+                let fee_amount = rest
+                    .get(..8)
+                    .and_then(|fee_amount_bytes| fee_amount_bytes.try_into().ok())
+                    .map(u64::from_be_bytes)
+                    .ok_or(RegistryError::InvalidInstructionData)?;
                 match tag {
                     0 => Self::InitializeRegistry {
-                        fee_amount: fee_amount,
-                    },
-                    1 => Self::UpdateFees {
-                        fee_amount: fee_amount,
+                        fee_amount,
+                        registry_id: String::new(),
                     },
+                    1 => Self::UpdateFees { fee_amount },
                     _ => {
                         return Err(RegistryError::InvalidInstructionData);
                     }
@@ -192,9 +560,779 @@ impl RegistryInstruction {
             3 => Self::DeleteEntry,
             5 => Self::TransferFeeAuthority,
             6 => Self::TransferTokenAuthority,
+            7 => {
+                let parsed_instruction_data =
+                    UpdateFeeExemptionsInstructionData::try_from_slice(rest)
+                        .or(Err(RegistryError::InvalidInstructionData))?;
+                Self::UpdateFeeExemptions {
+                    fee_exempt_accounts: parsed_instruction_data.fee_exempt_accounts,
+                }
+            }
+            8 => {
+                let parsed_instruction_data =
+                    UpdateEntryLimitsInstructionData::try_from_slice(rest)
+                        .or(Err(RegistryError::InvalidInstructionData))?;
+                Self::UpdateEntryLimits {
+                    max_tags: parsed_instruction_data.max_tags,
+                    max_extensions: parsed_instruction_data.max_extensions,
+                }
+            }
             _ => {
                 return Err(RegistryError::InvalidInstructionData);
             }
         })
     }
+
+    /// Serialize the instruction in the versioned wire format.
+    pub fn pack(&self) -> Vec<u8> {
+        let mut buffer = vec![VERSIONED_INSTRUCTION_MARKER, INSTRUCTION_VERSION];
+        self.serialize(&mut buffer).unwrap();
+        buffer
+    }
+}
+
+/// The address of the RegistryMetaAccount of the registry `registry_id`.
+pub fn get_registry_meta_address(program_id: &Pubkey, registry_id: &str) -> Pubkey {
+    Pubkey::find_program_address(&RegistrySeeds::new(registry_id).meta(), program_id).0
+}
+
+/// The address of the RegistryHeadAccount, the sentinel node at the front of the linked list.
+pub fn get_registry_head_address(program_id: &Pubkey, registry_id: &str) -> Pubkey {
+    Pubkey::find_program_address(&RegistrySeeds::new(registry_id).head(), program_id).0
+}
+
+/// The address of the RegistryTailAccount, the sentinel node at the back of the linked list.
+pub fn get_registry_tail_address(program_id: &Pubkey, registry_id: &str) -> Pubkey {
+    Pubkey::find_program_address(&RegistrySeeds::new(registry_id).tail(), program_id).0
+}
+
+/// The address of the RegistryNodeAccount for `mint` in the registry `registry_id`.
+pub fn get_registry_node_address(program_id: &Pubkey, registry_id: &str, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &RegistrySeeds::new(registry_id).node(mint.as_ref()),
+        program_id,
+    )
+    .0
+}
+
+/// The address of the SymbolClaimAccount for `token_symbol` in the registry `registry_id`, or None
+/// if the symbol cannot be normalized, as such symbols have no claim.
+pub fn get_symbol_claim_address(
+    program_id: &Pubkey,
+    registry_id: &str,
+    token_symbol: &str,
+) -> Option<Pubkey> {
+    let normalized_symbol = validation::normalize_symbol(token_symbol).ok()?;
+    let seeds = RegistrySeeds::new(registry_id);
+    Some(Pubkey::find_program_address(&seeds.symbol_claim(&normalized_symbol), program_id).0)
+}
+
+/// The SymbolClaimAccount of `token_symbol` in an instruction. The program ignores the account of
+/// a symbol without a claim, so the system program takes its place.
+fn symbol_claim_account(program_id: &Pubkey, registry_id: &str, token_symbol: &str) -> AccountMeta {
+    match get_symbol_claim_address(program_id, registry_id, token_symbol) {
+        Some(address) => AccountMeta::new(address, false),
+        None => AccountMeta::new_readonly(system_program::id(), false),
+    }
+}
+
+/// The trailing accounts of an instruction authorized by an SPL Token multisig.
+fn multisig_signers(signers: &[&Pubkey]) -> Vec<AccountMeta> {
+    signers
+        .iter()
+        .map(|signer| AccountMeta::new_readonly(**signer, true))
+        .collect()
+}
+
+/// Creates an `InitializeRegistry` instruction. The `payer` is given `fee_update_authority`.
+pub fn initialize_registry(
+    program_id: &Pubkey,
+    registry_id: &str,
+    payer: &Pubkey,
+    fee_mint: &Pubkey,
+    fee_destination: &Pubkey,
+    fee_amount: u64,
+) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*payer, true),
+            AccountMeta::new_readonly(*fee_mint, false),
+            AccountMeta::new_readonly(*fee_destination, false),
+            AccountMeta::new(
+                get_associated_token_address(fee_destination, fee_mint),
+                false,
+            ),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new(get_registry_meta_address(program_id, registry_id), false),
+            AccountMeta::new(get_registry_head_address(program_id, registry_id), false),
+            AccountMeta::new(get_registry_tail_address(program_id, registry_id), false),
+        ],
+        data: RegistryInstruction::InitializeRegistry {
+            fee_amount,
+            registry_id: registry_id.to_string(),
+        }
+        .pack(),
+    }
+}
+
+/// Creates an `UpdateFees` instruction. `signers` are the signers of the `fee_update_authority`, if
+/// it is an SPL Token multisig, and must otherwise be empty.
+#[allow(clippy::too_many_arguments)]
+pub fn update_fees(
+    program_id: &Pubkey,
+    registry_id: &str,
+    fee_update_authority: &Pubkey,
+    signers: &[&Pubkey],
+    payer: &Pubkey,
+    fee_mint: &Pubkey,
+    fee_destination: &Pubkey,
+    fee_amount: u64,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*fee_update_authority, signers.is_empty()),
+        AccountMeta::new_readonly(*fee_mint, false),
+        AccountMeta::new_readonly(*fee_destination, false),
+        AccountMeta::new(
+            get_associated_token_address(fee_destination, fee_mint),
+            false,
+        ),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new(get_registry_meta_address(program_id, registry_id), false),
+        AccountMeta::new(*payer, true),
+    ];
+    accounts.extend(multisig_signers(signers));
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: RegistryInstruction::UpdateFees { fee_amount }.pack(),
+    }
+}
+
+/// Creates a `CreateEntry` instruction. `registry_first` is `next_registry_node` of the
+/// RegistryHeadAccount, and `mint_authority` is the mint's `mint_authority` (or the
+/// `fee_update_authority`, if the mint has none). `signers` are the signers of the
+/// `mint_authority`, if it is an SPL Token multisig.
+#[allow(clippy::too_many_arguments)]
+pub fn create_entry(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    mint: &Pubkey,
+    mint_authority: &Pubkey,
+    signers: &[&Pubkey],
+    registry_meta: &RegistryMetaAccount,
+    registry_first: &Pubkey,
+    data: CreateUpdateEntryInstructionData,
+) -> Instruction {
+    let registry_id = &registry_meta.registry_id;
+    let fee_mint = &Pubkey::new_from_array(registry_meta.fee_mint);
+    let fee_destination = &Pubkey::new_from_array(registry_meta.fee_destination);
+    let mut accounts = vec![
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new(get_associated_token_address(payer, fee_mint), false),
+        AccountMeta::new(
+            get_associated_token_address(fee_destination, fee_mint),
+            false,
+        ),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(get_registry_meta_address(program_id, registry_id), false),
+        AccountMeta::new(get_registry_head_address(program_id, registry_id), false),
+        AccountMeta::new(*registry_first, false),
+        AccountMeta::new(
+            get_registry_node_address(program_id, registry_id, mint),
+            false,
+        ),
+        AccountMeta::new_readonly(*mint_authority, signers.is_empty()),
+        symbol_claim_account(program_id, registry_id, &data.token_symbol),
+    ];
+    accounts.extend(multisig_signers(signers));
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: RegistryInstruction::CreateEntry {
+            token_symbol: data.token_symbol,
+            token_name: data.token_name,
+            token_logo_url: data.token_logo_url,
+            token_tags: data.token_tags,
+            token_extensions: data.token_extensions,
+        }
+        .pack(),
+    }
+}
+
+/// One entry of a `CreateEntries` instruction.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BatchEntry {
+    pub mint: Pubkey,
+    /// The `mint_authority` of the mint, or the `fee_update_authority` if the mint has none.
+    pub mint_authority: Pubkey,
+    /// The signers of `mint_authority`, if it is an SPL Token multisig.
+    pub signers: Vec<Pubkey>,
+    pub data: CreateUpdateEntryInstructionData,
+}
+
+/// Creates a `CreateEntries` instruction. `registry_first` is `next_registry_node` of the
+/// RegistryHeadAccount, as for `create_entry`. The signers of the entries are passed once each,
+/// after the accounts of the entries, and there may be at most `MAX_SIGNERS` of them in total.
+pub fn create_entries(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    registry_meta: &RegistryMetaAccount,
+    registry_first: &Pubkey,
+    entries: Vec<BatchEntry>,
+) -> Instruction {
+    let registry_id = &registry_meta.registry_id;
+    let fee_mint = &Pubkey::new_from_array(registry_meta.fee_mint);
+    let fee_destination = &Pubkey::new_from_array(registry_meta.fee_destination);
+    let mut accounts = vec![
+        AccountMeta::new(*payer, true),
+        AccountMeta::new(get_associated_token_address(payer, fee_mint), false),
+        AccountMeta::new(
+            get_associated_token_address(fee_destination, fee_mint),
+            false,
+        ),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new_readonly(get_registry_meta_address(program_id, registry_id), false),
+        AccountMeta::new(get_registry_head_address(program_id, registry_id), false),
+        AccountMeta::new(*registry_first, false),
+    ];
+    let mut entries_data = Vec::with_capacity(entries.len());
+    let mut signers: Vec<&Pubkey> = vec![];
+    for entry in &entries {
+        accounts.extend([
+            AccountMeta::new_readonly(entry.mint, false),
+            AccountMeta::new(
+                get_registry_node_address(program_id, registry_id, &entry.mint),
+                false,
+            ),
+            AccountMeta::new_readonly(entry.mint_authority, entry.signers.is_empty()),
+            symbol_claim_account(program_id, registry_id, &entry.data.token_symbol),
+        ]);
+        for signer in &entry.signers {
+            if !signers.contains(&signer) {
+                signers.push(signer);
+            }
+        }
+    }
+    accounts.extend(multisig_signers(&signers));
+    for entry in entries {
+        entries_data.push(entry.data);
+    }
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: RegistryInstruction::CreateEntries {
+            entries: entries_data,
+        }
+        .pack(),
+    }
+}
+
+/// Creates a `DeleteEntry` instruction. `token_symbol` is the current symbol of the entry, and
+/// `signers` are the signers of the `token_update_authority`, if it is an SPL Token multisig.
+pub fn delete_entry(
+    program_id: &Pubkey,
+    registry_id: &str,
+    token_update_authority: &Pubkey,
+    signers: &[&Pubkey],
+    mint: &Pubkey,
+    token_symbol: &str,
+    destination: &Pubkey,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*token_update_authority, signers.is_empty()),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new_readonly(get_registry_meta_address(program_id, registry_id), false),
+        AccountMeta::new(
+            get_registry_node_address(program_id, registry_id, mint),
+            false,
+        ),
+        symbol_claim_account(program_id, registry_id, token_symbol),
+        AccountMeta::new(*destination, false),
+    ];
+    accounts.extend(multisig_signers(signers));
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: RegistryInstruction::DeleteEntry.pack(),
+    }
+}
+
+/// Creates an `UpdateEntry` instruction. `current_token_symbol` is the symbol of the entry before
+/// the update, and `signers` are the signers of the `token_update_authority`, if it is an SPL Token
+/// multisig.
+#[allow(clippy::too_many_arguments)]
+pub fn update_entry(
+    program_id: &Pubkey,
+    registry_id: &str,
+    token_update_authority: &Pubkey,
+    signers: &[&Pubkey],
+    payer: &Pubkey,
+    mint: &Pubkey,
+    current_token_symbol: &str,
+    data: CreateUpdateEntryInstructionData,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*token_update_authority, signers.is_empty()),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new_readonly(get_registry_meta_address(program_id, registry_id), false),
+        AccountMeta::new(
+            get_registry_node_address(program_id, registry_id, mint),
+            false,
+        ),
+        AccountMeta::new_readonly(system_program::id(), false),
+        symbol_claim_account(program_id, registry_id, current_token_symbol),
+        symbol_claim_account(program_id, registry_id, &data.token_symbol),
+        AccountMeta::new(*payer, true),
+    ];
+    accounts.extend(multisig_signers(signers));
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: RegistryInstruction::UpdateEntry {
+            token_symbol: data.token_symbol,
+            token_name: data.token_name,
+            token_logo_url: data.token_logo_url,
+            token_tags: data.token_tags,
+            token_extensions: data.token_extensions,
+        }
+        .pack(),
+    }
+}
+
+/// Creates a `TransferFeeAuthority` instruction, which the new authority must complete with
+/// `accept_fee_authority`. `signers` are the signers of the `fee_update_authority`, if it is an SPL
+/// Token multisig.
+pub fn transfer_fee_authority(
+    program_id: &Pubkey,
+    registry_id: &str,
+    fee_update_authority: &Pubkey,
+    signers: &[&Pubkey],
+    new_fee_update_authority: &Pubkey,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*fee_update_authority, signers.is_empty()),
+        AccountMeta::new_readonly(*new_fee_update_authority, false),
+        AccountMeta::new(get_registry_meta_address(program_id, registry_id), false),
+    ];
+    accounts.extend(multisig_signers(signers));
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: RegistryInstruction::TransferFeeAuthority.pack(),
+    }
+}
+
+/// Creates a `TransferTokenAuthority` instruction, which the new authority must complete with
+/// `accept_token_authority`. `signers` are the signers of the `token_update_authority`, if it is an
+/// SPL Token multisig.
+pub fn transfer_token_authority(
+    program_id: &Pubkey,
+    registry_id: &str,
+    token_update_authority: &Pubkey,
+    signers: &[&Pubkey],
+    mint: &Pubkey,
+    new_token_update_authority: &Pubkey,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*token_update_authority, signers.is_empty()),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new_readonly(get_registry_meta_address(program_id, registry_id), false),
+        AccountMeta::new(
+            get_registry_node_address(program_id, registry_id, mint),
+            false,
+        ),
+        AccountMeta::new_readonly(*new_token_update_authority, false),
+    ];
+    accounts.extend(multisig_signers(signers));
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: RegistryInstruction::TransferTokenAuthority.pack(),
+    }
+}
+
+/// Creates an `UpdateFeeExemptions` instruction. `signers` are the signers of the
+/// `fee_update_authority`, if it is an SPL Token multisig.
+pub fn update_fee_exemptions(
+    program_id: &Pubkey,
+    registry_id: &str,
+    fee_update_authority: &Pubkey,
+    signers: &[&Pubkey],
+    fee_exempt_accounts: &[Pubkey],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*fee_update_authority, signers.is_empty()),
+        AccountMeta::new(get_registry_meta_address(program_id, registry_id), false),
+    ];
+    accounts.extend(multisig_signers(signers));
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: RegistryInstruction::UpdateFeeExemptions {
+            fee_exempt_accounts: fee_exempt_accounts.iter().map(|a| a.to_bytes()).collect(),
+        }
+        .pack(),
+    }
+}
+
+/// Creates an `UpdateEntryLimits` instruction. `signers` are the signers of the
+/// `fee_update_authority`, if it is an SPL Token multisig.
+pub fn update_entry_limits(
+    program_id: &Pubkey,
+    registry_id: &str,
+    fee_update_authority: &Pubkey,
+    signers: &[&Pubkey],
+    max_tags: u8,
+    max_extensions: u8,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*fee_update_authority, signers.is_empty()),
+        AccountMeta::new(get_registry_meta_address(program_id, registry_id), false),
+    ];
+    accounts.extend(multisig_signers(signers));
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: RegistryInstruction::UpdateEntryLimits {
+            max_tags,
+            max_extensions,
+        }
+        .pack(),
+    }
+}
+
+/// Creates a `CloseEntry` instruction. `registry_node` is the current RegistryNodeAccount of the
+/// mint. `signers` are the signers of the `token_update_authority`, if it is an SPL Token multisig.
+pub fn close_entry(
+    program_id: &Pubkey,
+    registry_id: &str,
+    token_update_authority: &Pubkey,
+    signers: &[&Pubkey],
+    mint: &Pubkey,
+    registry_node: &RegistryNodeAccount,
+    destination: &Pubkey,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*token_update_authority, signers.is_empty()),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new_readonly(get_registry_meta_address(program_id, registry_id), false),
+        AccountMeta::new(
+            get_registry_node_address(program_id, registry_id, mint),
+            false,
+        ),
+        AccountMeta::new(
+            Pubkey::new_from_array(registry_node.prev_registry_node),
+            false,
+        ),
+        AccountMeta::new(
+            Pubkey::new_from_array(registry_node.next_registry_node),
+            false,
+        ),
+        AccountMeta::new(*destination, false),
+        symbol_claim_account(program_id, registry_id, &registry_node.token_symbol),
+    ];
+    accounts.extend(multisig_signers(signers));
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: RegistryInstruction::CloseEntry.pack(),
+    }
+}
+
+/// Creates a `RestoreEntry` instruction. `token_symbol` is the current symbol of the entry.
+/// `signers` are the signers of the `token_update_authority`, if it is an SPL Token multisig.
+pub fn restore_entry(
+    program_id: &Pubkey,
+    registry_id: &str,
+    token_update_authority: &Pubkey,
+    signers: &[&Pubkey],
+    payer: &Pubkey,
+    mint: &Pubkey,
+    token_symbol: &str,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*token_update_authority, signers.is_empty()),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new_readonly(get_registry_meta_address(program_id, registry_id), false),
+        AccountMeta::new(
+            get_registry_node_address(program_id, registry_id, mint),
+            false,
+        ),
+        symbol_claim_account(program_id, registry_id, token_symbol),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new(*payer, true),
+    ];
+    accounts.extend(multisig_signers(signers));
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: RegistryInstruction::RestoreEntry.pack(),
+    }
+}
+
+/// Creates a `ReclaimTokenAuthority` instruction. `signers` are the signers of the
+/// `mint_authority`, if it is an SPL Token multisig.
+pub fn reclaim_token_authority(
+    program_id: &Pubkey,
+    registry_id: &str,
+    mint_authority: &Pubkey,
+    signers: &[&Pubkey],
+    mint: &Pubkey,
+    new_token_update_authority: &Pubkey,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*mint_authority, signers.is_empty()),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new_readonly(get_registry_meta_address(program_id, registry_id), false),
+        AccountMeta::new(
+            get_registry_node_address(program_id, registry_id, mint),
+            false,
+        ),
+        AccountMeta::new_readonly(*new_token_update_authority, false),
+    ];
+    accounts.extend(multisig_signers(signers));
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: RegistryInstruction::ReclaimTokenAuthority.pack(),
+    }
+}
+
+/// Creates a `RefreshMintInfo` instruction.
+pub fn refresh_mint_info(program_id: &Pubkey, registry_id: &str, mint: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(get_registry_meta_address(program_id, registry_id), false),
+            AccountMeta::new(
+                get_registry_node_address(program_id, registry_id, mint),
+                false,
+            ),
+        ],
+        data: RegistryInstruction::RefreshMintInfo.pack(),
+    }
+}
+
+/// Creates a `MigrateAccount` instruction for `account`, a meta or node of the registry
+/// `registry_id`.
+pub fn migrate_account(
+    program_id: &Pubkey,
+    registry_id: &str,
+    payer: &Pubkey,
+    account: &Pubkey,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*payer, true),
+        AccountMeta::new(*account, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    let registry_meta = get_registry_meta_address(program_id, registry_id);
+    if !registry_id.is_empty() && *account != registry_meta {
+        accounts.push(AccountMeta::new_readonly(registry_meta, false));
+    }
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: RegistryInstruction::MigrateAccount.pack(),
+    }
+}
+
+/// Creates an `UpdateSymbolUniqueness` instruction. `signers` are the signers of the
+/// `fee_update_authority`, if it is an SPL Token multisig.
+pub fn update_symbol_uniqueness(
+    program_id: &Pubkey,
+    registry_id: &str,
+    fee_update_authority: &Pubkey,
+    signers: &[&Pubkey],
+    unique_symbols: bool,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*fee_update_authority, signers.is_empty()),
+        AccountMeta::new(get_registry_meta_address(program_id, registry_id), false),
+    ];
+    accounts.extend(multisig_signers(signers));
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: RegistryInstruction::UpdateSymbolUniqueness { unique_symbols }.pack(),
+    }
+}
+
+/// Creates an `UpdateCurators` instruction. `signers` are the signers of the
+/// `fee_update_authority`, if it is an SPL Token multisig.
+pub fn update_curators(
+    program_id: &Pubkey,
+    registry_id: &str,
+    fee_update_authority: &Pubkey,
+    signers: &[&Pubkey],
+    curators: &[Pubkey],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*fee_update_authority, signers.is_empty()),
+        AccountMeta::new(get_registry_meta_address(program_id, registry_id), false),
+    ];
+    accounts.extend(multisig_signers(signers));
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: RegistryInstruction::UpdateCurators {
+            curators: curators.iter().map(|a| a.to_bytes()).collect(),
+        }
+        .pack(),
+    }
+}
+
+/// Creates a `VerifyEntry` instruction. `signers` are the signers of the `curator`, if it is an SPL
+/// Token multisig.
+pub fn verify_entry(
+    program_id: &Pubkey,
+    registry_id: &str,
+    curator: &Pubkey,
+    signers: &[&Pubkey],
+    mint: &Pubkey,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*curator, signers.is_empty()),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new_readonly(get_registry_meta_address(program_id, registry_id), false),
+        AccountMeta::new(
+            get_registry_node_address(program_id, registry_id, mint),
+            false,
+        ),
+    ];
+    accounts.extend(multisig_signers(signers));
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: RegistryInstruction::VerifyEntry.pack(),
+    }
+}
+
+/// Creates an `UnverifyEntry` instruction. `signers` are the signers of the `curator`, if it is an SPL
+/// Token multisig.
+pub fn unverify_entry(
+    program_id: &Pubkey,
+    registry_id: &str,
+    curator: &Pubkey,
+    signers: &[&Pubkey],
+    mint: &Pubkey,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*curator, signers.is_empty()),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new_readonly(get_registry_meta_address(program_id, registry_id), false),
+        AccountMeta::new(
+            get_registry_node_address(program_id, registry_id, mint),
+            false,
+        ),
+    ];
+    accounts.extend(multisig_signers(signers));
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: RegistryInstruction::UnverifyEntry.pack(),
+    }
+}
+
+/// Creates an `AcceptFeeAuthority` instruction. `signers` are the signers of the new authority, if
+/// it is an SPL Token multisig.
+pub fn accept_fee_authority(
+    program_id: &Pubkey,
+    registry_id: &str,
+    new_fee_update_authority: &Pubkey,
+    signers: &[&Pubkey],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*new_fee_update_authority, signers.is_empty()),
+        AccountMeta::new(get_registry_meta_address(program_id, registry_id), false),
+    ];
+    accounts.extend(multisig_signers(signers));
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: RegistryInstruction::AcceptFeeAuthority.pack(),
+    }
+}
+
+/// Creates an `AcceptTokenAuthority` instruction. `signers` are the signers of the new authority,
+/// if it is an SPL Token multisig.
+pub fn accept_token_authority(
+    program_id: &Pubkey,
+    registry_id: &str,
+    new_token_update_authority: &Pubkey,
+    signers: &[&Pubkey],
+    mint: &Pubkey,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*new_token_update_authority, signers.is_empty()),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new_readonly(get_registry_meta_address(program_id, registry_id), false),
+        AccountMeta::new(
+            get_registry_node_address(program_id, registry_id, mint),
+            false,
+        ),
+    ];
+    accounts.extend(multisig_signers(signers));
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: RegistryInstruction::AcceptTokenAuthority.pack(),
+    }
+}
+
+/// Creates a `CancelFeeAuthorityTransfer` instruction. `signers` are the signers of the
+/// `fee_update_authority`, if it is an SPL Token multisig.
+pub fn cancel_fee_authority_transfer(
+    program_id: &Pubkey,
+    registry_id: &str,
+    fee_update_authority: &Pubkey,
+    signers: &[&Pubkey],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*fee_update_authority, signers.is_empty()),
+        AccountMeta::new(get_registry_meta_address(program_id, registry_id), false),
+    ];
+    accounts.extend(multisig_signers(signers));
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: RegistryInstruction::CancelFeeAuthorityTransfer.pack(),
+    }
+}
+
+/// Creates a `CancelTokenAuthorityTransfer` instruction. `signers` are the signers of the
+/// `token_update_authority`, if it is an SPL Token multisig.
+pub fn cancel_token_authority_transfer(
+    program_id: &Pubkey,
+    registry_id: &str,
+    token_update_authority: &Pubkey,
+    signers: &[&Pubkey],
+    mint: &Pubkey,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*token_update_authority, signers.is_empty()),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new_readonly(get_registry_meta_address(program_id, registry_id), false),
+        AccountMeta::new(
+            get_registry_node_address(program_id, registry_id, mint),
+            false,
+        ),
+    ];
+    accounts.extend(multisig_signers(signers));
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        data: RegistryInstruction::CancelTokenAuthorityTransfer.pack(),
+    }
 }
@@ -1,5 +1,12 @@
+#[cfg(not(feature = "no-entrypoint"))]
 mod entrypoint;
-mod error;
-mod instruction;
-mod processor;
-mod state;
+pub mod error;
+pub mod event;
+pub mod instruction;
+pub mod processor;
+pub mod state;
+pub mod validation;
+
+pub use solana_program;
+
+solana_program::declare_id!("FVWvitXme2PDdok2eKkbmxHmWGQSqK9X886nDTHcP4Pc");
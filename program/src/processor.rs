@@ -1,29 +1,43 @@
 use crate::{
     error::RegistryError,
+    event::{self, AuthorityType, RegistryEvent},
     instruction::RegistryInstruction,
-    state::{RegistryMetaAccount, RegistryNodeAccount},
+    state::{
+        self, AccountHeader, CreateUpdateEntryInstructionData, EntryState, RegistryMetaAccount,
+        RegistryNodeAccount, RegistryNodeAccountV0, RegistrySeeds, SymbolClaimAccount,
+        DEFAULT_MAX_EXTENSIONS, DEFAULT_MAX_TAGS, MAX_CURATORS, MAX_FEE_EXEMPT_ACCOUNTS,
+        REGISTRY_ACCOUNT_VERSION, REGISTRY_META_DISCRIMINATOR, REGISTRY_NODE_DISCRIMINATOR,
+        SYMBOL_CLAIM_DISCRIMINATOR,
+    },
+    validation,
 };
-use borsh::{BorshDeserialize, BorshSerialize};
-use byteorder::{BigEndian, ByteOrder};
+use borsh::BorshSerialize;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
+    clock::Clock,
     entrypoint::ProgramResult,
     msg,
-    program_error::ProgramError,
+    program::set_return_data,
+    program_error::{PrintProgramError, ProgramError},
+    program_option::COption,
+    program_pack::Pack,
     pubkey::Pubkey,
     rent::Rent,
     system_instruction, system_program,
+    sysvar::Sysvar,
 };
-use std::{convert::TryInto, io::Write, mem::size_of};
 
 pub struct Processor {}
 impl<'a> Processor {
     pub fn process(program_id: &Pubkey, accounts: &[AccountInfo], input: &[u8]) -> ProgramResult {
         let instruction = RegistryInstruction::unpack(input)?;
         match instruction {
-            RegistryInstruction::InitializeRegistry { fee_amount } => {
+            RegistryInstruction::InitializeRegistry {
+                fee_amount,
+                registry_id,
+            } => {
                 msg!("RegistryInstruction::InitializeRegistry");
-                Self::process_initialize_registry(program_id, accounts, fee_amount)
+                Self::process_initialize_registry(program_id, accounts, fee_amount, registry_id)
             }
             RegistryInstruction::UpdateFees { fee_amount } => {
                 msg!("RegistryInstruction::UpdateFees");
@@ -51,6 +65,22 @@ impl<'a> Processor {
                 msg!("RegistryInstruction::DeleteEntry");
                 Self::process_delete_entry(program_id, accounts)
             }
+            RegistryInstruction::RestoreEntry => {
+                msg!("RegistryInstruction::RestoreEntry");
+                Self::process_restore_entry(program_id, accounts)
+            }
+            RegistryInstruction::ReclaimTokenAuthority => {
+                msg!("RegistryInstruction::ReclaimTokenAuthority");
+                Self::process_reclaim_token_authority(program_id, accounts)
+            }
+            RegistryInstruction::RefreshMintInfo => {
+                msg!("RegistryInstruction::RefreshMintInfo");
+                Self::process_refresh_mint_info(program_id, accounts)
+            }
+            RegistryInstruction::CloseEntry => {
+                msg!("RegistryInstruction::CloseEntry");
+                Self::process_close_entry(program_id, accounts)
+            }
             RegistryInstruction::UpdateEntry {
                 token_symbol,
                 token_name,
@@ -77,6 +107,59 @@ impl<'a> Processor {
                 msg!("RegistryInstruction::TransferTokenAuthority");
                 Self::process_transfer_token_authority(program_id, accounts)
             }
+            RegistryInstruction::UpdateFeeExemptions {
+                fee_exempt_accounts,
+            } => {
+                msg!("RegistryInstruction::UpdateFeeExemptions");
+                Self::process_update_fee_exemptions(program_id, accounts, fee_exempt_accounts)
+            }
+            RegistryInstruction::UpdateEntryLimits {
+                max_tags,
+                max_extensions,
+            } => {
+                msg!("RegistryInstruction::UpdateEntryLimits");
+                Self::process_update_entry_limits(program_id, accounts, max_tags, max_extensions)
+            }
+            RegistryInstruction::MigrateAccount => {
+                msg!("RegistryInstruction::MigrateAccount");
+                Self::process_migrate_account(program_id, accounts)
+            }
+            RegistryInstruction::UpdateSymbolUniqueness { unique_symbols } => {
+                msg!("RegistryInstruction::UpdateSymbolUniqueness");
+                Self::process_update_symbol_uniqueness(program_id, accounts, unique_symbols)
+            }
+            RegistryInstruction::UpdateCurators { curators } => {
+                msg!("RegistryInstruction::UpdateCurators");
+                Self::process_update_curators(program_id, accounts, curators)
+            }
+            RegistryInstruction::VerifyEntry => {
+                msg!("RegistryInstruction::VerifyEntry");
+                Self::process_set_verification(program_id, accounts, true)
+            }
+            RegistryInstruction::UnverifyEntry => {
+                msg!("RegistryInstruction::UnverifyEntry");
+                Self::process_set_verification(program_id, accounts, false)
+            }
+            RegistryInstruction::AcceptFeeAuthority => {
+                msg!("RegistryInstruction::AcceptFeeAuthority");
+                Self::process_accept_fee_authority(program_id, accounts)
+            }
+            RegistryInstruction::AcceptTokenAuthority => {
+                msg!("RegistryInstruction::AcceptTokenAuthority");
+                Self::process_accept_token_authority(program_id, accounts)
+            }
+            RegistryInstruction::CancelFeeAuthorityTransfer => {
+                msg!("RegistryInstruction::CancelFeeAuthorityTransfer");
+                Self::process_cancel_fee_authority_transfer(program_id, accounts)
+            }
+            RegistryInstruction::CancelTokenAuthorityTransfer => {
+                msg!("RegistryInstruction::CancelTokenAuthorityTransfer");
+                Self::process_cancel_token_authority_transfer(program_id, accounts)
+            }
+            RegistryInstruction::CreateEntries { entries } => {
+                msg!("RegistryInstruction::CreateEntries");
+                Self::process_create_entries(program_id, accounts, entries)
+            }
         }
     }
 
@@ -84,7 +167,9 @@ impl<'a> Processor {
         program_id: &Pubkey,
         accounts: &[AccountInfo],
         fee_amount: u64,
+        registry_id: String,
     ) -> ProgramResult {
+        validation::validate_registry_id(&registry_id)?;
         Self::assert_number_of_accounts(accounts, 11)?;
         let accounts_iter = &mut accounts.iter();
         let account_user = next_account_info(accounts_iter)?;
@@ -98,7 +183,7 @@ impl<'a> Processor {
         Self::assert_valid_ata(
             account_fee_destination.key,
             account_fee_mint.key,
-            &account_fee_destination_ata,
+            account_fee_destination_ata,
         )?;
         let account_system_program = next_account_info(accounts_iter)?;
         Self::assert_valid_system_program(account_system_program)?;
@@ -108,15 +193,16 @@ impl<'a> Processor {
         Self::assert_valid_ata_program(account_ata_program)?;
         let account_sysvar_rent = next_account_info(accounts_iter)?;
         Self::assert_valid_sysvar_rent(account_sysvar_rent)?;
+        let registry_seeds = RegistrySeeds::new(&registry_id);
         let account_registry_meta = next_account_info(accounts_iter)?;
         let account_registry_meta_bump_seed =
-            Self::assert_valid_pda(program_id, account_registry_meta, b"meta")?;
+            Self::assert_valid_pda(program_id, account_registry_meta, &registry_seeds.meta())?;
         let account_registry_head = next_account_info(accounts_iter)?;
         let account_registry_head_bump_seed =
-            Self::assert_valid_pda(program_id, account_registry_head, b"head")?;
+            Self::assert_valid_pda(program_id, account_registry_head, &registry_seeds.head())?;
         let account_registry_tail = next_account_info(accounts_iter)?;
         let account_registry_tail_bump_seed =
-            Self::assert_valid_pda(program_id, account_registry_tail, b"tail")?;
+            Self::assert_valid_pda(program_id, account_registry_tail, &registry_seeds.tail())?;
 
         /* Assert that the accounts have not already been created. */
         if account_registry_meta.data_len() != 0
@@ -138,53 +224,85 @@ impl<'a> Processor {
             account_sysvar_rent,
         )?;
 
+        /* Set the fields of account_registry_meta */
+        let registry_meta = RegistryMetaAccount {
+            head_registry_node: account_registry_head.key.to_bytes(),
+            fee_amount,
+            fee_mint: account_fee_mint.key.to_bytes(),
+            fee_destination: account_fee_destination.key.to_bytes(),
+            fee_update_authority: account_user.key.to_bytes(),
+            max_tags: DEFAULT_MAX_TAGS,
+            max_extensions: DEFAULT_MAX_EXTENSIONS,
+            registry_id: registry_id.clone(),
+            ..RegistryMetaAccount::default()
+        };
+
         /* Create the account_registry_meta */
-        let account_registry_meta_space = size_of::<RegistryMetaAccount>();
-        let initialize_instruction_meta = system_instruction::create_account(
-            account_user.key,
-            account_registry_meta.key,
-            Rent::default().minimum_balance(account_registry_meta_space),
-            account_registry_meta_space as u64,
+        let registry_meta_data =
+            registry_meta.pack_account_data(account_registry_meta_bump_seed)?;
+        let rent = Rent::from_account_info(account_sysvar_rent)?;
+        Self::create_program_account(
             program_id,
-        );
-        solana_program::program::invoke_signed(
-            &initialize_instruction_meta,
-            &[account_user.clone(), account_registry_meta.clone()],
-            &[&[b"meta", &[account_registry_meta_bump_seed]]],
+            account_user,
+            account_registry_meta,
+            account_system_program,
+            registry_meta_data.len(),
+            &[
+                &registry_seeds.meta()[..],
+                &[&[account_registry_meta_bump_seed]],
+            ]
+            .concat(),
+            &rent,
         )?;
 
-        /* Set the fields of account_registry_meta */
-        let mut registry_meta = RegistryMetaAccount::default();
-        registry_meta.head_registry_node = account_registry_head.key.to_bytes();
-        registry_meta.fee_amount = fee_amount;
-        registry_meta.fee_mint = account_fee_mint.key.to_bytes();
-        registry_meta.fee_destination = account_fee_destination.key.to_bytes();
-        registry_meta.fee_update_authority = account_user.key.to_bytes();
-        registry_meta.serialize(&mut &mut account_registry_meta.data.borrow_mut()[..])?;
+        Self::write_account_data(account_registry_meta, &registry_meta_data)?;
 
         /* Create the account_registry_head and account_registry_tail */
+        let registry_head = RegistryNodeAccount {
+            next_registry_node: account_registry_tail.key.to_bytes(),
+            ..RegistryNodeAccount::default()
+        };
         Self::initialize_new_registry_account(
             program_id,
             account_user,
             account_registry_head,
-            b"head",
-            account_registry_head_bump_seed,
+            account_system_program,
+            &[
+                &registry_seeds.head()[..],
+                &[&[account_registry_head_bump_seed]],
+            ]
+            .concat(),
+            &registry_head.pack_account_data(account_registry_head_bump_seed)?,
+            &rent,
         )?;
+        let registry_tail = RegistryNodeAccount {
+            prev_registry_node: account_registry_head.key.to_bytes(),
+            ..RegistryNodeAccount::default()
+        };
         Self::initialize_new_registry_account(
             program_id,
             account_user,
             account_registry_tail,
-            b"tail",
-            account_registry_tail_bump_seed,
+            account_system_program,
+            &[
+                &registry_seeds.tail()[..],
+                &[&[account_registry_tail_bump_seed]],
+            ]
+            .concat(),
+            &registry_tail.pack_account_data(account_registry_tail_bump_seed)?,
+            &rent,
         )?;
 
-        /* Set the fields of account_registry_head and account_registry_tail */
-        let mut registry_head = RegistryNodeAccount::default();
-        registry_head.next_registry_node = account_registry_tail.key.to_bytes();
-        Self::serialize_registry_account(registry_head, account_registry_head)?;
-        let mut registry_tail = RegistryNodeAccount::default();
-        registry_tail.prev_registry_node = account_registry_head.key.to_bytes();
-        Self::serialize_registry_account(registry_tail, account_registry_tail)?;
+        event::emit(
+            account_registry_meta.key,
+            RegistryEvent::RegistryInitialized {
+                registry_id,
+                fee_update_authority: registry_meta.fee_update_authority,
+                fee_mint: registry_meta.fee_mint,
+                fee_destination: registry_meta.fee_destination,
+                fee_amount,
+            },
+        )?;
 
         Ok(())
     }
@@ -194,10 +312,9 @@ impl<'a> Processor {
         accounts: &[AccountInfo],
         fee_amount: u64,
     ) -> ProgramResult {
-        Self::assert_number_of_accounts(accounts, 9)?;
+        Self::assert_number_of_accounts_with_signers(accounts, 10)?;
         let accounts_iter = &mut accounts.iter();
-        let account_user = next_account_info(accounts_iter)?;
-        Self::assert_valid_account_user(account_user)?;
+        let account_fee_authority = next_account_info(accounts_iter)?;
         let account_fee_mint = next_account_info(accounts_iter)?;
         Self::assert_valid_account_mint(account_fee_mint)?;
         let account_fee_destination = next_account_info(accounts_iter)?;
@@ -206,7 +323,7 @@ impl<'a> Processor {
         Self::assert_valid_ata(
             account_fee_destination.key,
             account_fee_mint.key,
-            &account_fee_destination_ata,
+            account_fee_destination_ata,
         )?;
         let account_system_program = next_account_info(accounts_iter)?;
         Self::assert_valid_system_program(account_system_program)?;
@@ -218,13 +335,20 @@ impl<'a> Processor {
         Self::assert_valid_sysvar_rent(account_sysvar_rent)?;
 
         let account_registry_meta = next_account_info(accounts_iter)?;
-        Self::assert_valid_pda(program_id, account_registry_meta, b"meta")?;
-        Self::assert_initialized(account_registry_meta)?;
+        let mut registry_meta =
+            Self::assert_valid_registry_meta(program_id, account_registry_meta)?;
+        let account_payer = next_account_info(accounts_iter)?;
+        Self::assert_valid_account_user(account_payer)?;
+
+        if account_fee_authority.key.to_bytes() != registry_meta.fee_update_authority {
+            return Err(ProgramError::from(RegistryError::InvalidFeeUpdateAuthority));
+        }
+        Self::assert_signed_by_authority(account_fee_authority, accounts_iter.as_slice())?;
 
         // If the fee destination ATA has not yet been initialized, do so.
         Self::initialize_ata_if_not_exists(
             account_ata_program,
-            account_user,
+            account_payer,
             account_fee_destination_ata,
             account_fee_destination,
             account_fee_mint,
@@ -233,15 +357,19 @@ impl<'a> Processor {
             account_sysvar_rent,
         )?;
 
-        let mut registry_meta =
-            RegistryMetaAccount::try_from_slice(&account_registry_meta.data.borrow())?;
-        if account_user.key.to_bytes() != registry_meta.fee_update_authority {
-            return Err(ProgramError::from(RegistryError::InvalidFeeUpdateAuthority));
-        }
         registry_meta.fee_amount = fee_amount;
         registry_meta.fee_mint = account_fee_mint.key.to_bytes();
         registry_meta.fee_destination = account_fee_destination.key.to_bytes();
-        registry_meta.serialize(&mut &mut account_registry_meta.data.borrow_mut()[..])?;
+        Self::serialize_registry_meta(&registry_meta, account_registry_meta)?;
+
+        event::emit(
+            account_registry_meta.key,
+            RegistryEvent::FeesUpdated {
+                fee_mint: registry_meta.fee_mint,
+                fee_destination: registry_meta.fee_destination,
+                fee_amount,
+            },
+        )?;
 
         Ok(())
     }
@@ -255,7 +383,7 @@ impl<'a> Processor {
         token_tags: Vec<String>,
         token_extensions: Vec<Vec<String>>,
     ) -> ProgramResult {
-        Self::assert_number_of_accounts(accounts, 10)?;
+        Self::assert_number_of_accounts_with_signers(accounts, 12)?;
         let accounts_iter = &mut accounts.iter();
         let account_user = next_account_info(accounts_iter)?;
         Self::assert_valid_account_user(account_user)?;
@@ -269,57 +397,74 @@ impl<'a> Processor {
         let account_token_program = next_account_info(accounts_iter)?;
         Self::assert_valid_token_program(account_token_program)?;
         let account_registry_meta = next_account_info(accounts_iter)?;
-        Self::assert_valid_pda(program_id, account_registry_meta, b"meta")?;
-        Self::assert_initialized(account_registry_meta)?;
+        let registry_meta = Self::assert_valid_registry_meta(program_id, account_registry_meta)?;
+        let registry_seeds = RegistrySeeds::new(&registry_meta.registry_id);
         let account_registry_head = next_account_info(accounts_iter)?;
-        Self::assert_valid_pda(program_id, account_registry_head, b"head")?;
+        Self::assert_valid_pda(program_id, account_registry_head, &registry_seeds.head())?;
         let account_registry_first = next_account_info(accounts_iter)?;
         Self::assert_valid_registry_first(account_registry_head, account_registry_first)?;
         let account_registry_new = next_account_info(accounts_iter)?;
         let account_registry_new_bump_seed = Self::assert_valid_pda(
             program_id,
             account_registry_new,
-            &account_mint.key.to_bytes(),
+            &registry_seeds.node(account_mint.key.as_ref()),
+        )?;
+        let account_mint_authority = next_account_info(accounts_iter)?;
+        let account_symbol_claim = next_account_info(accounts_iter)?;
+
+        Self::assert_valid_mint_authority(
+            account_mint,
+            account_mint_authority,
+            &registry_meta,
+            accounts_iter.as_slice(),
+        )?;
+        validation::validate_entry(
+            &registry_meta,
+            &token_symbol,
+            &token_name,
+            &token_logo_url,
+            &token_tags,
+            &token_extensions,
+        )?;
+        Self::claim_symbol(
+            program_id,
+            &registry_meta,
+            account_user,
+            account_symbol_claim,
+            account_system_program,
+            &token_symbol,
+            account_mint.key,
         )?;
 
-        /* Transfer fee_amount to the ATA of fee_destination. */
-        let registry_meta =
-            RegistryMetaAccount::try_from_slice(&account_registry_meta.data.borrow())?;
+        /* Transfer fee_amount to the ATA of fee_destination, unless the user is exempt. */
         Self::assert_valid_ata(
             account_user.key,
             &Pubkey::new(&registry_meta.fee_mint),
-            &account_fee_source_ata,
+            account_fee_source_ata,
         )?;
         Self::assert_valid_ata(
             &Pubkey::new(&registry_meta.fee_destination),
             &Pubkey::new(&registry_meta.fee_mint),
-            &account_fee_destination_ata,
-        )?;
-        Self::assert_initialized_ata(&account_fee_destination_ata)?;
-
-        // if account_user.key.to_bytes() != registry_meta.fee_update_authority {
-        //     Self::assert_initialized_ata(&account_fee_source_ata)?;
-        //     let transfer_instruction = spl_token::instruction::transfer(
-        //         account_token_program.key,
-        //         account_fee_source_ata.key,
-        //         account_fee_destination_ata.key,
-        //         account_user.key,
-        //         &[account_user.key],
-        //         registry_meta.fee_amount,
-        //     )?;
-        //     solana_program::program::invoke(
-        //         &transfer_instruction,
-        //         &[
-        //             account_token_program.clone(),
-        //             account_fee_source_ata.clone(),
-        //             account_fee_destination_ata.clone(),
-        //             account_user.clone(),
-        //         ],
-        //     )?;
-        // }
+            account_fee_destination_ata,
+        )?;
+        Self::assert_initialized_ata(account_fee_destination_ata)?;
+
+        if registry_meta.fee_amount != 0
+            && !registry_meta.is_fee_exempt(&account_user.key.to_bytes())
+        {
+            Self::charge_fee(
+                account_user,
+                account_fee_source_ata,
+                account_fee_destination_ata,
+                account_token_program,
+                &registry_meta,
+                registry_meta.fee_amount,
+            )?;
+        }
 
+        let is_new_account = account_registry_new.data_len() == 0;
         let mut registry_node_new;
-        if account_registry_new.data_len() == 0 {
+        if is_new_account {
             /* Update linked list pointers of head and former first entry. */
             let mut registry_head = Self::deserialize_registry_account(account_registry_head)?;
             registry_head.next_registry_node = account_registry_new.key.to_bytes();
@@ -328,69 +473,397 @@ impl<'a> Processor {
             registry_first.prev_registry_node = account_registry_new.key.to_bytes();
             Self::serialize_registry_account(registry_first, account_registry_first)?;
 
-            /* Create an account for registry_node_new */
+            registry_node_new = RegistryNodeAccount {
+                next_registry_node: account_registry_first.key.to_bytes(),
+                prev_registry_node: account_registry_head.key.to_bytes(),
+                token_mint: account_mint.key.to_bytes(),
+                ..RegistryNodeAccount::default()
+            };
+        } else {
+            registry_node_new = Self::deserialize_registry_account(account_registry_new)?;
+            Self::assert_entry_state(registry_node_new.state, &[EntryState::Deleted])?;
+        }
+
+        let event = RegistryEvent::EntryCreated {
+            mint: account_mint.key.to_bytes(),
+            authority: account_user.key.to_bytes(),
+            symbol: token_symbol.clone(),
+        };
+
+        Self::set_created_entry(
+            &mut registry_node_new,
+            account_user,
+            account_mint,
+            CreateUpdateEntryInstructionData {
+                token_symbol,
+                token_name,
+                token_logo_url,
+                token_tags,
+                token_extensions,
+            },
+        )?;
+
+        /* Create an account for registry_node_new, or resize the deleted one being replaced. */
+        if is_new_account {
             Self::initialize_new_registry_account(
                 program_id,
                 account_user,
                 account_registry_new,
-                &account_mint.key.to_bytes(),
-                account_registry_new_bump_seed,
+                account_system_program,
+                &[
+                    &registry_seeds.node(account_mint.key.as_ref())[..],
+                    &[&[account_registry_new_bump_seed]],
+                ]
+                .concat(),
+                &registry_node_new.pack_account_data(account_registry_new_bump_seed)?,
+                &Rent::get()?,
             )?;
-            registry_node_new = RegistryNodeAccount::default();
-            registry_node_new.next_registry_node = account_registry_first.key.to_bytes();
-            registry_node_new.prev_registry_node = account_registry_head.key.to_bytes();
-            registry_node_new.token_mint = account_mint.key.to_bytes();
         } else {
-            registry_node_new = Self::deserialize_registry_account(account_registry_new)?;
-            if !registry_node_new.deleted {
-                return Err(ProgramError::from(RegistryError::PreviouslyRegisteredMint));
-            }
+            Self::resize_registry_account(
+                account_user,
+                account_registry_new,
+                account_system_program,
+                &registry_node_new,
+            )?;
+            Self::serialize_registry_account(registry_node_new, account_registry_new)?;
         }
 
-        /* Set the fields of registry_node_new */
-        registry_node_new.token_symbol = token_symbol;
-        registry_node_new.token_name = token_name;
-        registry_node_new.token_logo_url = token_logo_url;
-        registry_node_new.token_tags = token_tags;
-        registry_node_new.token_extensions = token_extensions;
-        registry_node_new.token_update_authority = account_user.key.to_bytes();
-        registry_node_new.deleted = false;
-        Self::serialize_registry_account(registry_node_new, account_registry_new)?;
+        event::emit(account_registry_meta.key, event)?;
 
         Ok(())
     }
 
-    fn process_delete_entry(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
-        Self::assert_number_of_accounts(accounts, 4)?;
+    fn process_create_entries(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        entries: Vec<CreateUpdateEntryInstructionData>,
+    ) -> ProgramResult {
+        let entries_count = entries.len();
+        Self::assert_number_of_accounts_with_signers(accounts, 8 + 4 * entries_count)?;
+        /* The signers of every multisig mint authority follow the accounts of the entries. */
+        let signer_accounts = &accounts[8 + 4 * entries_count..];
         let accounts_iter = &mut accounts.iter();
         let account_user = next_account_info(accounts_iter)?;
         Self::assert_valid_account_user(account_user)?;
+        let account_fee_source_ata = next_account_info(accounts_iter)?;
+        let account_fee_destination_ata = next_account_info(accounts_iter)?;
+        let account_system_program = next_account_info(accounts_iter)?;
+        Self::assert_valid_system_program(account_system_program)?;
+        let account_token_program = next_account_info(accounts_iter)?;
+        Self::assert_valid_token_program(account_token_program)?;
+        let account_registry_meta = next_account_info(accounts_iter)?;
+        let registry_meta = Self::assert_valid_registry_meta(program_id, account_registry_meta)?;
+        let registry_seeds = RegistrySeeds::new(&registry_meta.registry_id);
+        let account_registry_head = next_account_info(accounts_iter)?;
+        Self::assert_valid_pda(program_id, account_registry_head, &registry_seeds.head())?;
+        let account_registry_first = next_account_info(accounts_iter)?;
+        Self::assert_valid_registry_first(account_registry_head, account_registry_first)?;
+
+        Self::assert_valid_ata(
+            account_user.key,
+            &Pubkey::new(&registry_meta.fee_mint),
+            account_fee_source_ata,
+        )?;
+        Self::assert_valid_ata(
+            &Pubkey::new(&registry_meta.fee_destination),
+            &Pubkey::new(&registry_meta.fee_mint),
+            account_fee_destination_ata,
+        )?;
+        Self::assert_initialized_ata(account_fee_destination_ata)?;
+
+        /* New nodes are linked after account_registry_last, which starts out as the head. */
+        let rent = Rent::get()?;
+        let mut account_registry_last = account_registry_head;
+        let mut created: u32 = 0;
+        for (index, entry) in entries.into_iter().enumerate() {
+            let account_mint = next_account_info(accounts_iter)?;
+            let account_registry_new = next_account_info(accounts_iter)?;
+            let account_mint_authority = next_account_info(accounts_iter)?;
+            let account_symbol_claim = next_account_info(accounts_iter)?;
+
+            let checked = Self::check_batch_entry(
+                program_id,
+                &registry_meta,
+                account_user,
+                account_mint,
+                account_registry_new,
+                account_mint_authority,
+                signer_accounts,
+                account_symbol_claim,
+                account_system_program,
+                &entry,
+            );
+            let (account_registry_new_bump_seed, registry_node_deleted) = match checked {
+                Ok(checked) => checked,
+                Err(error) => {
+                    msg!("Skipping entry {}", index);
+                    error.print::<RegistryError>();
+                    continue;
+                }
+            };
+
+            let event = RegistryEvent::EntryCreated {
+                mint: account_mint.key.to_bytes(),
+                authority: account_user.key.to_bytes(),
+                symbol: entry.token_symbol.clone(),
+            };
+
+            if let Some(mut registry_node_new) = registry_node_deleted {
+                /* Replace the deleted node in place, as in CreateEntry. */
+                Self::set_created_entry(&mut registry_node_new, account_user, account_mint, entry)?;
+                Self::resize_registry_account(
+                    account_user,
+                    account_registry_new,
+                    account_system_program,
+                    &registry_node_new,
+                )?;
+                Self::serialize_registry_account(registry_node_new, account_registry_new)?;
+            } else {
+                let mut registry_node_new = RegistryNodeAccount {
+                    next_registry_node: account_registry_first.key.to_bytes(),
+                    prev_registry_node: account_registry_last.key.to_bytes(),
+                    token_mint: account_mint.key.to_bytes(),
+                    ..RegistryNodeAccount::default()
+                };
+                Self::set_created_entry(&mut registry_node_new, account_user, account_mint, entry)?;
+                Self::initialize_new_registry_account(
+                    program_id,
+                    account_user,
+                    account_registry_new,
+                    account_system_program,
+                    &[
+                        &registry_seeds.node(account_mint.key.as_ref())[..],
+                        &[&[account_registry_new_bump_seed]],
+                    ]
+                    .concat(),
+                    &registry_node_new.pack_account_data(account_registry_new_bump_seed)?,
+                    &rent,
+                )?;
+
+                let mut registry_last = Self::deserialize_registry_account(account_registry_last)?;
+                registry_last.next_registry_node = account_registry_new.key.to_bytes();
+                Self::serialize_registry_account(registry_last, account_registry_last)?;
+                account_registry_last = account_registry_new;
+            }
+
+            event::emit(account_registry_meta.key, event)?;
+            created += 1;
+        }
+
+        /* Close the list behind the last new node. */
+        if account_registry_last.key != account_registry_head.key {
+            let mut registry_first = Self::deserialize_registry_account(account_registry_first)?;
+            registry_first.prev_registry_node = account_registry_last.key.to_bytes();
+            Self::serialize_registry_account(registry_first, account_registry_first)?;
+        }
+
+        /* Transfer fee_amount for every created entry at once, unless the user is exempt. The
+         * nodes are already created by now, so a fee that cannot be paid fails the whole batch. */
+        let fee_amount = registry_meta
+            .fee_amount
+            .checked_mul(u64::from(created))
+            .ok_or(RegistryError::FeeOverflow)?;
+        if fee_amount != 0 && !registry_meta.is_fee_exempt(&account_user.key.to_bytes()) {
+            Self::charge_fee(
+                account_user,
+                account_fee_source_ata,
+                account_fee_destination_ata,
+                account_token_program,
+                &registry_meta,
+                fee_amount,
+            )?;
+        }
+
+        msg!("Created {} of {} entries", created, entries_count);
+        set_return_data(&created.to_le_bytes());
+
+        Ok(())
+    }
+
+    fn process_delete_entry(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        Self::assert_number_of_accounts_with_signers(accounts, 6)?;
+        let accounts_iter = &mut accounts.iter();
+        let account_token_authority = next_account_info(accounts_iter)?;
         let account_mint = next_account_info(accounts_iter)?;
         Self::assert_valid_account_mint(account_mint)?;
         Self::assert_initialized_account_mint(account_mint)?;
         let account_registry_meta = next_account_info(accounts_iter)?;
-        Self::assert_valid_pda(program_id, account_registry_meta, b"meta")?;
-        Self::assert_initialized(account_registry_meta)?;
+        let registry_meta = Self::assert_valid_registry_meta(program_id, account_registry_meta)?;
         let account_registry_to_delete = next_account_info(accounts_iter)?;
         Self::assert_valid_pda(
             program_id,
             account_registry_to_delete,
-            &account_mint.key.to_bytes(),
+            &RegistrySeeds::new(&registry_meta.registry_id).node(account_mint.key.as_ref()),
+        )?;
+
+        let account_symbol_claim = next_account_info(accounts_iter)?;
+        let account_destination = next_account_info(accounts_iter)?;
+
+        Self::assert_token_update_authority(account_token_authority, account_registry_to_delete)?;
+        Self::assert_signed_by_authority(account_token_authority, accounts_iter.as_slice())?;
+        Self::assert_entry_state(
+            RegistryNodeAccount::unpack_state(&account_registry_to_delete.data.borrow())?,
+            &[EntryState::Active],
+        )?;
+
+        /* Only decode the whole node if its symbol is needed. */
+        if registry_meta.unique_symbols {
+            let registry_node_to_delete =
+                Self::deserialize_registry_account(account_registry_to_delete)?;
+            Self::release_symbol(
+                program_id,
+                &registry_meta,
+                account_symbol_claim,
+                account_destination,
+                &registry_node_to_delete.token_symbol,
+                account_mint.key,
+            )?;
+        }
+
+        RegistryNodeAccount::pack_state(
+            &mut account_registry_to_delete.data.borrow_mut(),
+            EntryState::Deleted,
+        )?;
+
+        event::emit(
+            account_registry_meta.key,
+            RegistryEvent::EntryDeleted {
+                mint: account_mint.key.to_bytes(),
+            },
+        )?;
+
+        Ok(())
+    }
+
+    fn process_restore_entry(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        Self::assert_number_of_accounts_with_signers(accounts, 7)?;
+        let accounts_iter = &mut accounts.iter();
+        let account_token_authority = next_account_info(accounts_iter)?;
+        let account_mint = next_account_info(accounts_iter)?;
+        Self::assert_valid_account_mint(account_mint)?;
+        Self::assert_initialized_account_mint(account_mint)?;
+        let account_registry_meta = next_account_info(accounts_iter)?;
+        let registry_meta = Self::assert_valid_registry_meta(program_id, account_registry_meta)?;
+        let account_registry_to_restore = next_account_info(accounts_iter)?;
+        Self::assert_valid_pda(
+            program_id,
+            account_registry_to_restore,
+            &RegistrySeeds::new(&registry_meta.registry_id).node(account_mint.key.as_ref()),
         )?;
 
-        let mut registry_node_to_delete =
-            Self::deserialize_registry_account(account_registry_to_delete)?;
-        if account_user.key.to_bytes() != registry_node_to_delete.token_update_authority {
+        let account_symbol_claim = next_account_info(accounts_iter)?;
+        let account_system_program = next_account_info(accounts_iter)?;
+        Self::assert_valid_system_program(account_system_program)?;
+        let account_payer = next_account_info(accounts_iter)?;
+        Self::assert_valid_account_user(account_payer)?;
+
+        Self::assert_token_update_authority(account_token_authority, account_registry_to_restore)?;
+        Self::assert_signed_by_authority(account_token_authority, accounts_iter.as_slice())?;
+        Self::assert_entry_state(
+            RegistryNodeAccount::unpack_state(&account_registry_to_restore.data.borrow())?,
+            &[EntryState::Deleted],
+        )?;
+
+        /* Only decode the whole node if its symbol is needed. */
+        if registry_meta.unique_symbols {
+            let registry_node_to_restore =
+                Self::deserialize_registry_account(account_registry_to_restore)?;
+            Self::claim_symbol(
+                program_id,
+                &registry_meta,
+                account_payer,
+                account_symbol_claim,
+                account_system_program,
+                &registry_node_to_restore.token_symbol,
+                account_mint.key,
+            )?;
+        }
+
+        RegistryNodeAccount::pack_state(
+            &mut account_registry_to_restore.data.borrow_mut(),
+            EntryState::Active,
+        )?;
+
+        event::emit(
+            account_registry_meta.key,
+            RegistryEvent::EntryRestored {
+                mint: account_mint.key.to_bytes(),
+            },
+        )?;
+
+        Ok(())
+    }
+
+    fn process_close_entry(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        Self::assert_number_of_accounts_with_signers(accounts, 8)?;
+        let accounts_iter = &mut accounts.iter();
+        let account_token_authority = next_account_info(accounts_iter)?;
+        let account_mint = next_account_info(accounts_iter)?;
+        Self::assert_valid_account_mint(account_mint)?;
+        Self::assert_initialized_account_mint(account_mint)?;
+        let account_registry_meta = next_account_info(accounts_iter)?;
+        let registry_meta = Self::assert_valid_registry_meta(program_id, account_registry_meta)?;
+        let account_registry_to_close = next_account_info(accounts_iter)?;
+        Self::assert_valid_pda(
+            program_id,
+            account_registry_to_close,
+            &RegistrySeeds::new(&registry_meta.registry_id).node(account_mint.key.as_ref()),
+        )?;
+        let account_registry_prev = next_account_info(accounts_iter)?;
+        let account_registry_next = next_account_info(accounts_iter)?;
+        let account_destination = next_account_info(accounts_iter)?;
+        let account_symbol_claim = next_account_info(accounts_iter)?;
+
+        Self::assert_token_update_authority(account_token_authority, account_registry_to_close)?;
+        Self::assert_signed_by_authority(account_token_authority, accounts_iter.as_slice())?;
+        let registry_node_to_close = Self::deserialize_registry_account(account_registry_to_close)?;
+        Self::assert_entry_state(
+            registry_node_to_close.state,
+            &[EntryState::Active, EntryState::Deleted],
+        )?;
+        if account_registry_prev.key.to_bytes() != registry_node_to_close.prev_registry_node
+            || account_registry_next.key.to_bytes() != registry_node_to_close.next_registry_node
+        {
             return Err(ProgramError::from(
-                RegistryError::InvalidTokenUpdateAuthority,
+                RegistryError::InvalidRegistryNodeNeighbor,
             ));
         }
-        if registry_node_to_delete.deleted {
-            return Err(ProgramError::from(RegistryError::PreviouslyDeletedMint));
+        if account_destination.key == account_registry_to_close.key {
+            return Err(ProgramError::InvalidArgument);
         }
 
-        registry_node_to_delete.deleted = true;
-        Self::serialize_registry_account(registry_node_to_delete, account_registry_to_delete)?;
+        /* Splice the node out of the linked list. */
+        let mut registry_prev = Self::deserialize_registry_account(account_registry_prev)?;
+        registry_prev.next_registry_node = registry_node_to_close.next_registry_node;
+        Self::serialize_registry_account(registry_prev, account_registry_prev)?;
+        let mut registry_next = Self::deserialize_registry_account(account_registry_next)?;
+        registry_next.prev_registry_node = registry_node_to_close.prev_registry_node;
+        Self::serialize_registry_account(registry_next, account_registry_next)?;
+
+        Self::release_symbol(
+            program_id,
+            &registry_meta,
+            account_symbol_claim,
+            account_destination,
+            &registry_node_to_close.token_symbol,
+            account_mint.key,
+        )?;
+
+        /* Close the account, refunding its lamports to the destination. It is wiped and given back
+         * to the system program first, as the runtime only does so at the end of the transaction,
+         * and not at all if the account is funded again before then. */
+        account_registry_to_close.realloc(0, false)?;
+        account_registry_to_close.assign(&system_program::id());
+        let refund = account_registry_to_close.lamports();
+        **account_registry_to_close.try_borrow_mut_lamports()? = 0;
+        **account_destination.try_borrow_mut_lamports()? += refund;
+
+        event::emit(
+            account_registry_meta.key,
+            RegistryEvent::EntryClosed {
+                mint: account_mint.key.to_bytes(),
+            },
+        )?;
 
         Ok(())
     }
@@ -404,38 +877,96 @@ impl<'a> Processor {
         token_tags: Vec<String>,
         token_extensions: Vec<Vec<String>>,
     ) -> ProgramResult {
-        Self::assert_number_of_accounts(accounts, 4)?;
+        Self::assert_number_of_accounts_with_signers(accounts, 8)?;
         let accounts_iter = &mut accounts.iter();
-        let account_user = next_account_info(accounts_iter)?;
-        Self::assert_valid_account_user(account_user)?;
+        let account_token_authority = next_account_info(accounts_iter)?;
         let account_mint = next_account_info(accounts_iter)?;
         Self::assert_valid_account_mint(account_mint)?;
         Self::assert_initialized_account_mint(account_mint)?;
         let account_registry_meta = next_account_info(accounts_iter)?;
-        Self::assert_valid_pda(program_id, account_registry_meta, b"meta")?;
-        Self::assert_initialized(account_registry_meta)?;
+        let registry_meta = Self::assert_valid_registry_meta(program_id, account_registry_meta)?;
         let account_registry_to_update = next_account_info(accounts_iter)?;
         Self::assert_valid_pda(
             program_id,
             account_registry_to_update,
-            &account_mint.key.to_bytes(),
+            &RegistrySeeds::new(&registry_meta.registry_id).node(account_mint.key.as_ref()),
+        )?;
+        let account_system_program = next_account_info(accounts_iter)?;
+        Self::assert_valid_system_program(account_system_program)?;
+        let account_current_symbol_claim = next_account_info(accounts_iter)?;
+        let account_new_symbol_claim = next_account_info(accounts_iter)?;
+        let account_user = next_account_info(accounts_iter)?;
+        Self::assert_valid_account_user(account_user)?;
+
+        validation::validate_entry(
+            &registry_meta,
+            &token_symbol,
+            &token_name,
+            &token_logo_url,
+            &token_tags,
+            &token_extensions,
         )?;
 
+        Self::assert_token_update_authority(account_token_authority, account_registry_to_update)?;
+        Self::assert_signed_by_authority(account_token_authority, accounts_iter.as_slice())?;
         let mut registry_node_to_update =
             Self::deserialize_registry_account(account_registry_to_update)?;
-        if account_user.key.to_bytes() != registry_node_to_update.token_update_authority {
-            return Err(ProgramError::from(
-                RegistryError::InvalidTokenUpdateAuthority,
-            ));
+        Self::assert_entry_state(registry_node_to_update.state, &[EntryState::Active])?;
+
+        /* Move the node's claim to the new symbol, unless both normalize to the same claim. */
+        if registry_meta.unique_symbols
+            && validation::normalize_symbol(&registry_node_to_update.token_symbol)
+                != validation::normalize_symbol(&token_symbol)
+        {
+            Self::release_symbol(
+                program_id,
+                &registry_meta,
+                account_current_symbol_claim,
+                account_user,
+                &registry_node_to_update.token_symbol,
+                account_mint.key,
+            )?;
         }
+        Self::claim_symbol(
+            program_id,
+            &registry_meta,
+            account_user,
+            account_new_symbol_claim,
+            account_system_program,
+            &token_symbol,
+            account_mint.key,
+        )?;
+
+        let event = RegistryEvent::EntryUpdated {
+            mint: account_mint.key.to_bytes(),
+            token_symbol: Self::changed(&registry_node_to_update.token_symbol, &token_symbol),
+            token_name: Self::changed(&registry_node_to_update.token_name, &token_name),
+            token_logo_url: Self::changed(&registry_node_to_update.token_logo_url, &token_logo_url),
+            token_tags: Self::changed(&registry_node_to_update.token_tags, &token_tags),
+            token_extensions: Self::changed(
+                &registry_node_to_update.token_extensions,
+                &token_extensions,
+            ),
+            unverified: registry_node_to_update.verified,
+        };
 
         registry_node_to_update.token_symbol = token_symbol;
         registry_node_to_update.token_name = token_name;
         registry_node_to_update.token_logo_url = token_logo_url;
         registry_node_to_update.token_tags = token_tags;
         registry_node_to_update.token_extensions = token_extensions;
+        registry_node_to_update.clear_verification();
+        Self::snapshot_mint_info(account_mint, &mut registry_node_to_update)?;
+        Self::resize_registry_account(
+            account_user,
+            account_registry_to_update,
+            account_system_program,
+            &registry_node_to_update,
+        )?;
         Self::serialize_registry_account(registry_node_to_update, account_registry_to_update)?;
 
+        event::emit(account_registry_meta.key, event)?;
+
         Ok(())
     }
 
@@ -443,61 +974,607 @@ impl<'a> Processor {
         program_id: &Pubkey,
         accounts: &[AccountInfo],
     ) -> ProgramResult {
-        Self::assert_number_of_accounts(accounts, 3)?;
+        Self::assert_number_of_accounts_with_signers(accounts, 3)?;
         let accounts_iter = &mut accounts.iter();
-        let account_user = next_account_info(accounts_iter)?;
-        Self::assert_valid_account_user(account_user)?;
+        let account_fee_authority = next_account_info(accounts_iter)?;
         let account_new_fee_authority = next_account_info(accounts_iter)?;
-        Self::assert_valid_system_account(account_new_fee_authority)?;
         let account_registry_meta = next_account_info(accounts_iter)?;
-        Self::assert_valid_pda(program_id, account_registry_meta, b"meta")?;
-        Self::assert_initialized(account_registry_meta)?;
-
         let mut registry_meta =
-            RegistryMetaAccount::try_from_slice(&account_registry_meta.data.borrow())?;
-        if account_user.key.to_bytes() != registry_meta.fee_update_authority {
+            Self::assert_valid_registry_meta(program_id, account_registry_meta)?;
+
+        if account_fee_authority.key.to_bytes() != registry_meta.fee_update_authority {
             return Err(ProgramError::from(RegistryError::InvalidFeeUpdateAuthority));
         }
-        registry_meta.fee_update_authority = account_new_fee_authority.key.to_bytes();
-        registry_meta.serialize(&mut &mut account_registry_meta.data.borrow_mut()[..])?;
+        Self::assert_signed_by_authority(account_fee_authority, accounts_iter.as_slice())?;
+        registry_meta.pending_fee_update_authority = account_new_fee_authority.key.to_bytes();
+        Self::serialize_registry_meta(&registry_meta, account_registry_meta)?;
+
+        event::emit(
+            account_registry_meta.key,
+            RegistryEvent::AuthorityTransferProposed {
+                authority: AuthorityType::Fee,
+                current_authority: registry_meta.fee_update_authority,
+                pending_authority: registry_meta.pending_fee_update_authority,
+            },
+        )?;
 
         Ok(())
     }
 
-    fn process_transfer_token_authority(
+    fn process_accept_fee_authority(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
     ) -> ProgramResult {
-        Self::assert_number_of_accounts(accounts, 5)?;
+        Self::assert_number_of_accounts_with_signers(accounts, 2)?;
         let accounts_iter = &mut accounts.iter();
-        let account_user = next_account_info(accounts_iter)?;
-        Self::assert_valid_account_user(account_user)?;
-        let account_mint = next_account_info(accounts_iter)?;
-        Self::assert_valid_account_mint(account_mint)?;
-        Self::assert_initialized_account_mint(account_mint)?;
+        let account_new_fee_authority = next_account_info(accounts_iter)?;
         let account_registry_meta = next_account_info(accounts_iter)?;
-        Self::assert_valid_pda(program_id, account_registry_meta, b"meta")?;
-        Self::assert_initialized(account_registry_meta)?;
-        let account_registry_to_update = next_account_info(accounts_iter)?;
-        Self::assert_valid_pda(
-            program_id,
-            account_registry_to_update,
-            &account_mint.key.to_bytes(),
+        let mut registry_meta =
+            Self::assert_valid_registry_meta(program_id, account_registry_meta)?;
+
+        Self::assert_pending_authority(
+            account_new_fee_authority,
+            &registry_meta.pending_fee_update_authority,
+            accounts_iter.as_slice(),
         )?;
-        let account_new_token_authority = next_account_info(accounts_iter)?;
-        Self::assert_valid_system_account(account_new_token_authority)?;
+        let previous_authority = registry_meta.fee_update_authority;
+        registry_meta.fee_update_authority = registry_meta.pending_fee_update_authority;
+        registry_meta.pending_fee_update_authority = [0; 32];
+        Self::serialize_registry_meta(&registry_meta, account_registry_meta)?;
 
-        let mut registry_node_to_update =
-            Self::deserialize_registry_account(account_registry_to_update)?;
-        if account_user.key.to_bytes() != registry_node_to_update.token_update_authority {
-            return Err(ProgramError::from(
-                RegistryError::InvalidTokenUpdateAuthority,
-            ));
-        }
+        event::emit(
+            account_registry_meta.key,
+            RegistryEvent::AuthorityTransferred {
+                authority: AuthorityType::Fee,
+                previous_authority,
+                new_authority: registry_meta.fee_update_authority,
+            },
+        )?;
 
-        registry_node_to_update.token_update_authority = account_new_token_authority.key.to_bytes();
+        Ok(())
+    }
+
+    fn process_cancel_fee_authority_transfer(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        Self::assert_number_of_accounts_with_signers(accounts, 2)?;
+        let accounts_iter = &mut accounts.iter();
+        let account_fee_authority = next_account_info(accounts_iter)?;
+        let account_registry_meta = next_account_info(accounts_iter)?;
+        let mut registry_meta =
+            Self::assert_valid_registry_meta(program_id, account_registry_meta)?;
+
+        if account_fee_authority.key.to_bytes() != registry_meta.fee_update_authority {
+            return Err(ProgramError::from(RegistryError::InvalidFeeUpdateAuthority));
+        }
+        Self::assert_signed_by_authority(account_fee_authority, accounts_iter.as_slice())?;
+        if registry_meta.pending_fee_update_authority == [0; 32] {
+            return Err(ProgramError::from(RegistryError::NoPendingAuthority));
+        }
+        let pending_authority = registry_meta.pending_fee_update_authority;
+        registry_meta.pending_fee_update_authority = [0; 32];
+        Self::serialize_registry_meta(&registry_meta, account_registry_meta)?;
+
+        event::emit(
+            account_registry_meta.key,
+            RegistryEvent::AuthorityTransferCancelled {
+                authority: AuthorityType::Fee,
+                pending_authority,
+            },
+        )?;
+
+        Ok(())
+    }
+
+    fn process_transfer_token_authority(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        Self::assert_number_of_accounts_with_signers(accounts, 5)?;
+        let accounts_iter = &mut accounts.iter();
+        let account_token_authority = next_account_info(accounts_iter)?;
+        let account_mint = next_account_info(accounts_iter)?;
+        Self::assert_valid_account_mint(account_mint)?;
+        Self::assert_initialized_account_mint(account_mint)?;
+        let account_registry_meta = next_account_info(accounts_iter)?;
+        let registry_meta = Self::assert_valid_registry_meta(program_id, account_registry_meta)?;
+        let account_registry_to_update = next_account_info(accounts_iter)?;
+        Self::assert_valid_pda(
+            program_id,
+            account_registry_to_update,
+            &RegistrySeeds::new(&registry_meta.registry_id).node(account_mint.key.as_ref()),
+        )?;
+        let account_new_token_authority = next_account_info(accounts_iter)?;
+
+        Self::assert_token_update_authority(account_token_authority, account_registry_to_update)?;
+        Self::assert_signed_by_authority(account_token_authority, accounts_iter.as_slice())?;
+        let mut registry_node_to_update =
+            Self::deserialize_registry_account(account_registry_to_update)?;
+        Self::assert_entry_state(registry_node_to_update.state, &[EntryState::Active])?;
+
+        registry_node_to_update.pending_token_update_authority =
+            account_new_token_authority.key.to_bytes();
+        let event = RegistryEvent::AuthorityTransferProposed {
+            authority: AuthorityType::Token {
+                mint: account_mint.key.to_bytes(),
+            },
+            current_authority: registry_node_to_update.token_update_authority,
+            pending_authority: registry_node_to_update.pending_token_update_authority,
+        };
+        Self::serialize_registry_account(registry_node_to_update, account_registry_to_update)?;
+
+        event::emit(account_registry_meta.key, event)?;
+
+        Ok(())
+    }
+
+    fn process_accept_token_authority(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        Self::assert_number_of_accounts_with_signers(accounts, 4)?;
+        let accounts_iter = &mut accounts.iter();
+        let account_new_token_authority = next_account_info(accounts_iter)?;
+        let account_mint = next_account_info(accounts_iter)?;
+        Self::assert_valid_account_mint(account_mint)?;
+        Self::assert_initialized_account_mint(account_mint)?;
+        let account_registry_meta = next_account_info(accounts_iter)?;
+        let registry_meta = Self::assert_valid_registry_meta(program_id, account_registry_meta)?;
+        let account_registry_to_update = next_account_info(accounts_iter)?;
+        Self::assert_valid_pda(
+            program_id,
+            account_registry_to_update,
+            &RegistrySeeds::new(&registry_meta.registry_id).node(account_mint.key.as_ref()),
+        )?;
+
+        let mut registry_node_to_update =
+            Self::deserialize_registry_account(account_registry_to_update)?;
+        Self::assert_entry_state(registry_node_to_update.state, &[EntryState::Active])?;
+        Self::assert_pending_authority(
+            account_new_token_authority,
+            &registry_node_to_update.pending_token_update_authority,
+            accounts_iter.as_slice(),
+        )?;
+
+        let event = RegistryEvent::AuthorityTransferred {
+            authority: AuthorityType::Token {
+                mint: account_mint.key.to_bytes(),
+            },
+            previous_authority: registry_node_to_update.token_update_authority,
+            new_authority: registry_node_to_update.pending_token_update_authority,
+        };
+        registry_node_to_update.token_update_authority =
+            registry_node_to_update.pending_token_update_authority;
+        registry_node_to_update.pending_token_update_authority = [0; 32];
+        Self::serialize_registry_account(registry_node_to_update, account_registry_to_update)?;
+
+        event::emit(account_registry_meta.key, event)?;
+
+        Ok(())
+    }
+
+    fn process_cancel_token_authority_transfer(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        Self::assert_number_of_accounts_with_signers(accounts, 4)?;
+        let accounts_iter = &mut accounts.iter();
+        let account_token_authority = next_account_info(accounts_iter)?;
+        let account_mint = next_account_info(accounts_iter)?;
+        Self::assert_valid_account_mint(account_mint)?;
+        Self::assert_initialized_account_mint(account_mint)?;
+        let account_registry_meta = next_account_info(accounts_iter)?;
+        let registry_meta = Self::assert_valid_registry_meta(program_id, account_registry_meta)?;
+        let account_registry_to_update = next_account_info(accounts_iter)?;
+        Self::assert_valid_pda(
+            program_id,
+            account_registry_to_update,
+            &RegistrySeeds::new(&registry_meta.registry_id).node(account_mint.key.as_ref()),
+        )?;
+
+        Self::assert_token_update_authority(account_token_authority, account_registry_to_update)?;
+        Self::assert_signed_by_authority(account_token_authority, accounts_iter.as_slice())?;
+        let mut registry_node_to_update =
+            Self::deserialize_registry_account(account_registry_to_update)?;
+        Self::assert_entry_state(
+            registry_node_to_update.state,
+            &[EntryState::Active, EntryState::Deleted],
+        )?;
+        if registry_node_to_update.pending_token_update_authority == [0; 32] {
+            return Err(ProgramError::from(RegistryError::NoPendingAuthority));
+        }
+
+        let event = RegistryEvent::AuthorityTransferCancelled {
+            authority: AuthorityType::Token {
+                mint: account_mint.key.to_bytes(),
+            },
+            pending_authority: registry_node_to_update.pending_token_update_authority,
+        };
+        registry_node_to_update.pending_token_update_authority = [0; 32];
+        Self::serialize_registry_account(registry_node_to_update, account_registry_to_update)?;
+
+        event::emit(account_registry_meta.key, event)?;
+
+        Ok(())
+    }
+
+    fn process_reclaim_token_authority(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        Self::assert_number_of_accounts_with_signers(accounts, 5)?;
+        let accounts_iter = &mut accounts.iter();
+        let account_mint_authority = next_account_info(accounts_iter)?;
+        let account_mint = next_account_info(accounts_iter)?;
+        Self::assert_valid_account_mint(account_mint)?;
+        Self::assert_initialized_account_mint(account_mint)?;
+        let account_registry_meta = next_account_info(accounts_iter)?;
+        let registry_meta = Self::assert_valid_registry_meta(program_id, account_registry_meta)?;
+        let account_registry_to_update = next_account_info(accounts_iter)?;
+        Self::assert_valid_pda(
+            program_id,
+            account_registry_to_update,
+            &RegistrySeeds::new(&registry_meta.registry_id).node(account_mint.key.as_ref()),
+        )?;
+        let account_new_token_authority = next_account_info(accounts_iter)?;
+
+        Self::assert_valid_mint_authority(
+            account_mint,
+            account_mint_authority,
+            &registry_meta,
+            accounts_iter.as_slice(),
+        )?;
+
+        let mut registry_node_to_update =
+            Self::deserialize_registry_account(account_registry_to_update)?;
+        Self::assert_entry_state(
+            registry_node_to_update.state,
+            &[EntryState::Active, EntryState::Deleted],
+        )?;
+
+        let event = RegistryEvent::AuthorityTransferred {
+            authority: AuthorityType::Token {
+                mint: account_mint.key.to_bytes(),
+            },
+            previous_authority: registry_node_to_update.token_update_authority,
+            new_authority: account_new_token_authority.key.to_bytes(),
+        };
+        registry_node_to_update.token_update_authority = account_new_token_authority.key.to_bytes();
+        registry_node_to_update.pending_token_update_authority = [0; 32];
+        Self::serialize_registry_account(registry_node_to_update, account_registry_to_update)?;
+
+        event::emit(account_registry_meta.key, event)?;
+
+        Ok(())
+    }
+
+    fn process_refresh_mint_info(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        Self::assert_number_of_accounts(accounts, 3)?;
+        let accounts_iter = &mut accounts.iter();
+        let account_mint = next_account_info(accounts_iter)?;
+        Self::assert_valid_account_mint(account_mint)?;
+        Self::assert_initialized_account_mint(account_mint)?;
+        let account_registry_meta = next_account_info(accounts_iter)?;
+        let registry_meta = Self::assert_valid_registry_meta(program_id, account_registry_meta)?;
+        let account_registry_to_update = next_account_info(accounts_iter)?;
+        Self::assert_valid_pda(
+            program_id,
+            account_registry_to_update,
+            &RegistrySeeds::new(&registry_meta.registry_id).node(account_mint.key.as_ref()),
+        )?;
+
+        let mut registry_node_to_update =
+            Self::deserialize_registry_account(account_registry_to_update)?;
+        Self::assert_entry_state(
+            registry_node_to_update.state,
+            &[EntryState::Active, EntryState::Deleted],
+        )?;
+
+        Self::snapshot_mint_info(account_mint, &mut registry_node_to_update)?;
+        let event = RegistryEvent::MintInfoRefreshed {
+            mint: account_mint.key.to_bytes(),
+            decimals: registry_node_to_update.mint_decimals,
+            supply: registry_node_to_update.mint_supply,
+        };
+        Self::serialize_registry_account(registry_node_to_update, account_registry_to_update)?;
+
+        event::emit(account_registry_meta.key, event)?;
+
+        Ok(())
+    }
+
+    fn process_update_fee_exemptions(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        fee_exempt_accounts: Vec<[u8; 32]>,
+    ) -> ProgramResult {
+        Self::assert_number_of_accounts_with_signers(accounts, 2)?;
+        let accounts_iter = &mut accounts.iter();
+        let account_fee_authority = next_account_info(accounts_iter)?;
+        let account_registry_meta = next_account_info(accounts_iter)?;
+        let mut registry_meta =
+            Self::assert_valid_registry_meta(program_id, account_registry_meta)?;
+
+        if fee_exempt_accounts.len() > MAX_FEE_EXEMPT_ACCOUNTS {
+            return Err(ProgramError::from(RegistryError::TooManyFeeExemptAccounts));
+        }
+
+        if account_fee_authority.key.to_bytes() != registry_meta.fee_update_authority {
+            return Err(ProgramError::from(RegistryError::InvalidFeeUpdateAuthority));
+        }
+        Self::assert_signed_by_authority(account_fee_authority, accounts_iter.as_slice())?;
+        registry_meta.fee_exempt_accounts = [[0; 32]; MAX_FEE_EXEMPT_ACCOUNTS];
+        registry_meta.fee_exempt_accounts[..fee_exempt_accounts.len()]
+            .copy_from_slice(&fee_exempt_accounts);
+        Self::serialize_registry_meta(&registry_meta, account_registry_meta)?;
+
+        event::emit(
+            account_registry_meta.key,
+            RegistryEvent::FeeExemptionsUpdated {
+                fee_exempt_accounts,
+            },
+        )?;
+
+        Ok(())
+    }
+
+    fn process_update_entry_limits(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        max_tags: u8,
+        max_extensions: u8,
+    ) -> ProgramResult {
+        Self::assert_number_of_accounts_with_signers(accounts, 2)?;
+        let accounts_iter = &mut accounts.iter();
+        let account_fee_authority = next_account_info(accounts_iter)?;
+        let account_registry_meta = next_account_info(accounts_iter)?;
+        let mut registry_meta =
+            Self::assert_valid_registry_meta(program_id, account_registry_meta)?;
+
+        if account_fee_authority.key.to_bytes() != registry_meta.fee_update_authority {
+            return Err(ProgramError::from(RegistryError::InvalidFeeUpdateAuthority));
+        }
+        Self::assert_signed_by_authority(account_fee_authority, accounts_iter.as_slice())?;
+        registry_meta.max_tags = max_tags;
+        registry_meta.max_extensions = max_extensions;
+        Self::serialize_registry_meta(&registry_meta, account_registry_meta)?;
+
+        event::emit(
+            account_registry_meta.key,
+            RegistryEvent::EntryLimitsUpdated {
+                max_tags,
+                max_extensions,
+            },
+        )?;
+
+        Ok(())
+    }
+
+    fn process_update_symbol_uniqueness(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        unique_symbols: bool,
+    ) -> ProgramResult {
+        Self::assert_number_of_accounts_with_signers(accounts, 2)?;
+        let accounts_iter = &mut accounts.iter();
+        let account_fee_authority = next_account_info(accounts_iter)?;
+        let account_registry_meta = next_account_info(accounts_iter)?;
+        let mut registry_meta =
+            Self::assert_valid_registry_meta(program_id, account_registry_meta)?;
+
+        if account_fee_authority.key.to_bytes() != registry_meta.fee_update_authority {
+            return Err(ProgramError::from(RegistryError::InvalidFeeUpdateAuthority));
+        }
+        Self::assert_signed_by_authority(account_fee_authority, accounts_iter.as_slice())?;
+        registry_meta.unique_symbols = unique_symbols;
+        Self::serialize_registry_meta(&registry_meta, account_registry_meta)?;
+
+        event::emit(
+            account_registry_meta.key,
+            RegistryEvent::SymbolUniquenessUpdated { unique_symbols },
+        )?;
+
+        Ok(())
+    }
+
+    fn process_update_curators(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        curators: Vec<[u8; 32]>,
+    ) -> ProgramResult {
+        Self::assert_number_of_accounts_with_signers(accounts, 2)?;
+        let accounts_iter = &mut accounts.iter();
+        let account_fee_authority = next_account_info(accounts_iter)?;
+        let account_registry_meta = next_account_info(accounts_iter)?;
+        let mut registry_meta =
+            Self::assert_valid_registry_meta(program_id, account_registry_meta)?;
+
+        if curators.len() > MAX_CURATORS {
+            return Err(ProgramError::from(RegistryError::TooManyCurators));
+        }
+
+        if account_fee_authority.key.to_bytes() != registry_meta.fee_update_authority {
+            return Err(ProgramError::from(RegistryError::InvalidFeeUpdateAuthority));
+        }
+        Self::assert_signed_by_authority(account_fee_authority, accounts_iter.as_slice())?;
+        registry_meta.curators = [[0; 32]; MAX_CURATORS];
+        registry_meta.curators[..curators.len()].copy_from_slice(&curators);
+        Self::serialize_registry_meta(&registry_meta, account_registry_meta)?;
+
+        event::emit(
+            account_registry_meta.key,
+            RegistryEvent::CuratorsUpdated { curators },
+        )?;
+
+        Ok(())
+    }
+
+    /// Process a VerifyEntry (`verified` is true) or an UnverifyEntry (`verified` is false).
+    fn process_set_verification(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        verified: bool,
+    ) -> ProgramResult {
+        Self::assert_number_of_accounts_with_signers(accounts, 4)?;
+        let accounts_iter = &mut accounts.iter();
+        let account_curator = next_account_info(accounts_iter)?;
+        let account_mint = next_account_info(accounts_iter)?;
+        Self::assert_valid_account_mint(account_mint)?;
+        Self::assert_initialized_account_mint(account_mint)?;
+        let account_registry_meta = next_account_info(accounts_iter)?;
+        let registry_meta = Self::assert_valid_registry_meta(program_id, account_registry_meta)?;
+        let account_registry_to_update = next_account_info(accounts_iter)?;
+        Self::assert_valid_pda(
+            program_id,
+            account_registry_to_update,
+            &RegistrySeeds::new(&registry_meta.registry_id).node(account_mint.key.as_ref()),
+        )?;
+
+        if !registry_meta.is_curator(&account_curator.key.to_bytes()) {
+            return Err(ProgramError::from(RegistryError::InvalidCurator));
+        }
+        Self::assert_signed_by_authority(account_curator, accounts_iter.as_slice())?;
+
+        let mut registry_node_to_update =
+            Self::deserialize_registry_account(account_registry_to_update)?;
+        let event = if verified {
+            Self::assert_entry_state(registry_node_to_update.state, &[EntryState::Active])?;
+            let verified_at = Clock::get()?.unix_timestamp;
+            registry_node_to_update.verify(account_curator.key.to_bytes(), verified_at);
+            RegistryEvent::EntryVerified {
+                mint: account_mint.key.to_bytes(),
+                verifier: account_curator.key.to_bytes(),
+                verified_at,
+            }
+        } else {
+            Self::assert_entry_state(
+                registry_node_to_update.state,
+                &[EntryState::Active, EntryState::Deleted],
+            )?;
+            registry_node_to_update.clear_verification();
+            RegistryEvent::EntryUnverified {
+                mint: account_mint.key.to_bytes(),
+                curator: account_curator.key.to_bytes(),
+            }
+        };
         Self::serialize_registry_account(registry_node_to_update, account_registry_to_update)?;
 
+        event::emit(account_registry_meta.key, event)?;
+
+        Ok(())
+    }
+
+    fn process_migrate_account(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        if accounts.len() != 3 && accounts.len() != 4 {
+            return Err(RegistryError::InvalidNumberOfAccounts.into());
+        }
+        let accounts_iter = &mut accounts.iter();
+        let account_user = next_account_info(accounts_iter)?;
+        Self::assert_valid_account_user(account_user)?;
+        let account_to_migrate = next_account_info(accounts_iter)?;
+        if account_to_migrate.owner != program_id {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let account_system_program = next_account_info(accounts_iter)?;
+        Self::assert_valid_system_program(account_system_program)?;
+
+        /* Accounts since version 1 start with an AccountHeader, which holds their type and bump
+         * seed. Version 0 accounts all belong to the default registry, whose id is empty, and only
+         * its meta lives at the "meta" PDA: every other version 0 account is a node. */
+        let (discriminator, bump_seed, migrated_data) = {
+            let data = account_to_migrate.data.borrow();
+            let header = [REGISTRY_META_DISCRIMINATOR, REGISTRY_NODE_DISCRIMINATOR]
+                .iter()
+                .find_map(|discriminator| {
+                    AccountHeader::unpack_any_version(&data, discriminator).ok()
+                });
+            let (discriminator, version, bump_seed) = match header {
+                Some(header) => (header.discriminator, header.version, header.bump_seed),
+                None => match Self::assert_valid_pda(program_id, account_to_migrate, &[b"meta"]) {
+                    Ok(bump_seed) => (REGISTRY_META_DISCRIMINATOR, 0, bump_seed),
+                    Err(_) => {
+                        let registry_node = RegistryNodeAccountV0::unpack_account_data(&data)?;
+                        let bump_seed =
+                            Self::assert_valid_pda(program_id, account_to_migrate, &[b"head"])
+                                .or_else(|_| {
+                                    Self::assert_valid_pda(
+                                        program_id,
+                                        account_to_migrate,
+                                        &[b"tail"],
+                                    )
+                                })
+                                .or_else(|_| {
+                                    Self::assert_valid_pda(
+                                        program_id,
+                                        account_to_migrate,
+                                        &[&registry_node.token_mint],
+                                    )
+                                })?;
+                        (REGISTRY_NODE_DISCRIMINATOR, 0, bump_seed)
+                    }
+                },
+            };
+            if version == REGISTRY_ACCOUNT_VERSION {
+                return Err(RegistryError::AccountAlreadyMigrated.into());
+            }
+            let migrated_data =
+                state::migrate_account_data(&data, &discriminator, version, bump_seed)?;
+            (discriminator, bump_seed, migrated_data)
+        };
+
+        /* A meta is its own registry. A node belongs to the registry of the RegistryMetaAccount
+         * that follows, or to the default registry without one, and must live at one of its PDAs. */
+        let registry_meta_address = if discriminator == REGISTRY_META_DISCRIMINATOR {
+            *account_to_migrate.key
+        } else {
+            let (registry_meta_address, registry_id) = match accounts_iter.next() {
+                Some(account_registry_meta) => {
+                    let registry_meta =
+                        Self::assert_valid_registry_meta(program_id, account_registry_meta)?;
+                    (*account_registry_meta.key, registry_meta.registry_id)
+                }
+                None => {
+                    let registry_seeds = RegistrySeeds::new("");
+                    let (address, _) =
+                        Pubkey::find_program_address(&registry_seeds.meta(), program_id);
+                    (address, String::new())
+                }
+            };
+            let registry_seeds = RegistrySeeds::new(&registry_id);
+            let token_mint = RegistryNodeAccount::unpack_token_mint(&migrated_data)?;
+            let is_registry_node = [
+                registry_seeds.head(),
+                registry_seeds.tail(),
+                registry_seeds.node(token_mint),
+            ]
+            .iter()
+            .any(|seeds| {
+                Pubkey::create_program_address(&[&seeds[..], &[&[bump_seed]]].concat(), program_id)
+                    == Ok(*account_to_migrate.key)
+            });
+            if !is_registry_node {
+                return Err(RegistryError::InvalidProgramDerivedAccount.into());
+            }
+            registry_meta_address
+        };
+
+        Self::fund_rent_exemption(
+            account_user,
+            account_to_migrate,
+            account_system_program,
+            migrated_data.len(),
+        )?;
+        account_to_migrate.realloc(migrated_data.len(), false)?;
+        Self::write_account_data(account_to_migrate, &migrated_data)?;
+
+        event::emit(
+            &registry_meta_address,
+            RegistryEvent::AccountMigrated {
+                account: account_to_migrate.key.to_bytes(),
+            },
+        )?;
+
         Ok(())
     }
 
@@ -505,70 +1582,434 @@ impl<'a> Processor {
         program_id: &Pubkey,
         account_user: &AccountInfo<'a>,
         account_registry_new: &AccountInfo<'a>,
-        seed: &[u8],
-        bump_seed: u8,
+        account_system_program: &AccountInfo<'a>,
+        signer_seeds: &[&[u8]],
+        registry_node_data: &[u8],
+        rent: &Rent,
     ) -> Result<(), ProgramError> {
         if account_registry_new.data_len() != 0 {
             return Err(ProgramError::from(RegistryError::PreviouslyRegisteredMint));
         }
-        let default_registry_node = RegistryNodeAccount::max_filled();
-        let mut default_registry_node_buffer: Vec<u8> = Vec::new();
-        default_registry_node.serialize(&mut default_registry_node_buffer)?;
-        let account_registry_node_space = default_registry_node_buffer.len() + 4;
-        let initialize_instruction = system_instruction::create_account(
-            account_user.key,
-            account_registry_new.key,
-            Rent::default().minimum_balance(account_registry_node_space),
-            account_registry_node_space as u64,
+        Self::create_program_account(
             program_id,
-        );
+            account_user,
+            account_registry_new,
+            account_system_program,
+            registry_node_data.len(),
+            signer_seeds,
+            rent,
+        )?;
+        Self::write_account_data(account_registry_new, registry_node_data)?;
+        Ok(())
+    }
+
+    /// Create a rent-exempt account owned by this program at the PDA derived from `signer_seeds`.
+    /// Lamports that were already sent to the address count towards the rent, so the user is only
+    /// charged the shortfall.
+    fn create_program_account(
+        program_id: &Pubkey,
+        account_user: &AccountInfo<'a>,
+        account_new: &AccountInfo<'a>,
+        account_system_program: &AccountInfo<'a>,
+        space: usize,
+        signer_seeds: &[&[u8]],
+        rent: &Rent,
+    ) -> Result<(), ProgramError> {
+        let minimum_balance = rent.minimum_balance(space);
+        let current_balance = account_new.lamports();
+        if current_balance == 0 {
+            solana_program::program::invoke_signed(
+                &system_instruction::create_account(
+                    account_user.key,
+                    account_new.key,
+                    minimum_balance,
+                    space as u64,
+                    program_id,
+                ),
+                &[
+                    account_user.clone(),
+                    account_new.clone(),
+                    account_system_program.clone(),
+                ],
+                &[signer_seeds],
+            )?;
+            return Ok(());
+        }
+
+        if minimum_balance > current_balance {
+            solana_program::program::invoke(
+                &system_instruction::transfer(
+                    account_user.key,
+                    account_new.key,
+                    minimum_balance - current_balance,
+                ),
+                &[
+                    account_user.clone(),
+                    account_new.clone(),
+                    account_system_program.clone(),
+                ],
+            )?;
+        }
+        solana_program::program::invoke_signed(
+            &system_instruction::allocate(account_new.key, space as u64),
+            &[account_new.clone(), account_system_program.clone()],
+            &[signer_seeds],
+        )?;
         solana_program::program::invoke_signed(
-            &initialize_instruction,
-            &[account_user.clone(), account_registry_new.clone()],
-            &[&[seed, &[bump_seed]]],
+            &system_instruction::assign(account_new.key, program_id),
+            &[account_new.clone(), account_system_program.clone()],
+            &[signer_seeds],
+        )?;
+        Ok(())
+    }
+
+    /// Claim `token_symbol` for `token_mint`, creating its SymbolClaimAccount if needed. Does
+    /// nothing unless the registry has `unique_symbols` set.
+    fn claim_symbol(
+        program_id: &Pubkey,
+        registry_meta: &RegistryMetaAccount,
+        account_user: &AccountInfo<'a>,
+        account_symbol_claim: &AccountInfo<'a>,
+        account_system_program: &AccountInfo<'a>,
+        token_symbol: &str,
+        token_mint: &Pubkey,
+    ) -> Result<(), ProgramError> {
+        if !registry_meta.unique_symbols {
+            return Ok(());
+        }
+        let normalized_symbol = validation::normalize_symbol(token_symbol)?;
+        let registry_seeds = RegistrySeeds::new(&registry_meta.registry_id);
+        let seeds = registry_seeds.symbol_claim(&normalized_symbol);
+        let bump_seed = Self::assert_valid_pda(program_id, account_symbol_claim, &seeds)?;
+
+        if account_symbol_claim.data_len() != 0 {
+            let symbol_claim =
+                SymbolClaimAccount::unpack_account_data(&account_symbol_claim.data.borrow())?;
+            if symbol_claim.token_mint != token_mint.to_bytes() {
+                return Err(ProgramError::from(RegistryError::SymbolAlreadyClaimed));
+            }
+            return Ok(());
+        }
+
+        let symbol_claim_data = SymbolClaimAccount {
+            token_mint: token_mint.to_bytes(),
+        }
+        .pack_account_data(bump_seed)?;
+        Self::create_program_account(
+            program_id,
+            account_user,
+            account_symbol_claim,
+            account_system_program,
+            symbol_claim_data.len(),
+            &[&seeds[..], &[&[bump_seed]]].concat(),
+            &Rent::get()?,
+        )?;
+        Self::write_account_data(account_symbol_claim, &symbol_claim_data)?;
+        Ok(())
+    }
+
+    /// Release the claim of `token_mint` on `token_symbol`, closing its SymbolClaimAccount and
+    /// sending its lamports to `account_destination`. Does nothing unless the registry has
+    /// `unique_symbols` set, or if `token_mint` does not hold the claim (e.g, because its node
+    /// predates `unique_symbols`).
+    fn release_symbol(
+        program_id: &Pubkey,
+        registry_meta: &RegistryMetaAccount,
+        account_symbol_claim: &AccountInfo,
+        account_destination: &AccountInfo,
+        token_symbol: &str,
+        token_mint: &Pubkey,
+    ) -> Result<(), ProgramError> {
+        if !registry_meta.unique_symbols {
+            return Ok(());
+        }
+        /* A symbol that doesn't normalize can never have been claimed. */
+        let normalized_symbol = match validation::normalize_symbol(token_symbol) {
+            Ok(normalized_symbol) => normalized_symbol,
+            Err(_) => return Ok(()),
+        };
+        Self::assert_valid_pda(
+            program_id,
+            account_symbol_claim,
+            &RegistrySeeds::new(&registry_meta.registry_id).symbol_claim(&normalized_symbol),
         )?;
+        if account_symbol_claim.data_len() == 0
+            || SymbolClaimAccount::unpack_account_data(&account_symbol_claim.data.borrow())?
+                .token_mint
+                != token_mint.to_bytes()
+        {
+            return Ok(());
+        }
+
+        let refund = account_symbol_claim.lamports();
+        **account_symbol_claim.try_borrow_mut_lamports()? = 0;
+        **account_destination.try_borrow_mut_lamports()? += refund;
+        account_symbol_claim.realloc(0, false)?;
+        account_symbol_claim.assign(&system_program::id());
         Ok(())
     }
 
+    /// Reallocate `registry_node_account` to fit `registry_node`. The user pays for any additional
+    /// rent, and is refunded any rent that is no longer needed.
+    fn resize_registry_account(
+        account_user: &AccountInfo<'a>,
+        registry_node_account: &AccountInfo<'a>,
+        account_system_program: &AccountInfo<'a>,
+        registry_node: &RegistryNodeAccount,
+    ) -> Result<(), ProgramError> {
+        let account_registry_node_space = Self::registry_account_space(registry_node)?;
+        Self::fund_rent_exemption(
+            account_user,
+            registry_node_account,
+            account_system_program,
+            account_registry_node_space,
+        )?;
+        let minimum_balance = Rent::get()?.minimum_balance(account_registry_node_space);
+        let current_balance = registry_node_account.lamports();
+        if current_balance > minimum_balance {
+            **registry_node_account.try_borrow_mut_lamports()? -= current_balance - minimum_balance;
+            **account_user.try_borrow_mut_lamports()? += current_balance - minimum_balance;
+        }
+        registry_node_account.realloc(account_registry_node_space, false)?;
+        Ok(())
+    }
+
+    /// Transfer lamports from the user to `account` until it is rent-exempt at `space` bytes.
+    fn fund_rent_exemption(
+        account_user: &AccountInfo<'a>,
+        account: &AccountInfo<'a>,
+        account_system_program: &AccountInfo<'a>,
+        space: usize,
+    ) -> Result<(), ProgramError> {
+        let minimum_balance = Rent::get()?.minimum_balance(space);
+        let current_balance = account.lamports();
+        if minimum_balance > current_balance {
+            let transfer_instruction = system_instruction::transfer(
+                account_user.key,
+                account.key,
+                minimum_balance - current_balance,
+            );
+            solana_program::program::invoke(
+                &transfer_instruction,
+                &[
+                    account_user.clone(),
+                    account.clone(),
+                    account_system_program.clone(),
+                ],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Run the checks of CreateEntry that concern a single entry of a CreateEntries, and claim its
+    /// symbol. Nothing is written unless every check passes, so that a failing entry can be
+    /// skipped. Returns the bump seed of the new node, and the deleted node it replaces, if any.
+    #[allow(clippy::too_many_arguments)]
+    fn check_batch_entry(
+        program_id: &Pubkey,
+        registry_meta: &RegistryMetaAccount,
+        account_user: &AccountInfo<'a>,
+        account_mint: &AccountInfo,
+        account_registry_new: &AccountInfo,
+        account_mint_authority: &AccountInfo,
+        signer_accounts: &[AccountInfo],
+        account_symbol_claim: &AccountInfo<'a>,
+        account_system_program: &AccountInfo<'a>,
+        entry: &CreateUpdateEntryInstructionData,
+    ) -> Result<(u8, Option<RegistryNodeAccount>), ProgramError> {
+        Self::assert_valid_account_mint(account_mint)?;
+        Self::assert_initialized_account_mint(account_mint)?;
+        let bump_seed = Self::assert_valid_pda(
+            program_id,
+            account_registry_new,
+            &RegistrySeeds::new(&registry_meta.registry_id).node(account_mint.key.as_ref()),
+        )?;
+        let registry_node_deleted = if account_registry_new.data_len() == 0 {
+            None
+        } else {
+            let registry_node = Self::deserialize_registry_account(account_registry_new)?;
+            Self::assert_entry_state(registry_node.state, &[EntryState::Deleted])?;
+            Some(registry_node)
+        };
+        Self::assert_valid_mint_authority(
+            account_mint,
+            account_mint_authority,
+            registry_meta,
+            signer_accounts,
+        )?;
+        validation::validate_entry(
+            registry_meta,
+            &entry.token_symbol,
+            &entry.token_name,
+            &entry.token_logo_url,
+            &entry.token_tags,
+            &entry.token_extensions,
+        )?;
+        Self::claim_symbol(
+            program_id,
+            registry_meta,
+            account_user,
+            account_symbol_claim,
+            account_system_program,
+            &entry.token_symbol,
+            account_mint.key,
+        )?;
+        Ok((bump_seed, registry_node_deleted))
+    }
+
+    /// Set the fields of a node created (or replaced) by CreateEntry or CreateEntries.
+    fn set_created_entry(
+        registry_node: &mut RegistryNodeAccount,
+        account_user: &AccountInfo,
+        account_mint: &AccountInfo,
+        entry: CreateUpdateEntryInstructionData,
+    ) -> Result<(), ProgramError> {
+        registry_node.token_symbol = entry.token_symbol;
+        registry_node.token_name = entry.token_name;
+        registry_node.token_logo_url = entry.token_logo_url;
+        registry_node.token_tags = entry.token_tags;
+        registry_node.token_extensions = entry.token_extensions;
+        registry_node.token_update_authority = account_user.key.to_bytes();
+        registry_node.pending_token_update_authority = [0; 32];
+        registry_node.state = EntryState::Active;
+        registry_node.clear_verification();
+        Self::snapshot_mint_info(account_mint, registry_node)
+    }
+
+    /// Transfer `fee_amount` of the fee mint from the user's ATA to the ATA of `fee_destination`.
+    fn charge_fee(
+        account_user: &AccountInfo<'a>,
+        account_fee_source_ata: &AccountInfo<'a>,
+        account_fee_destination_ata: &AccountInfo<'a>,
+        account_token_program: &AccountInfo<'a>,
+        registry_meta: &RegistryMetaAccount,
+        fee_amount: u64,
+    ) -> ProgramResult {
+        Self::assert_valid_fee_source(account_fee_source_ata, registry_meta, fee_amount)?;
+        let transfer_instruction = spl_token::instruction::transfer(
+            account_token_program.key,
+            account_fee_source_ata.key,
+            account_fee_destination_ata.key,
+            account_user.key,
+            &[account_user.key],
+            fee_amount,
+        )?;
+        solana_program::program::invoke(
+            &transfer_instruction,
+            &[
+                account_token_program.clone(),
+                account_fee_source_ata.clone(),
+                account_fee_destination_ata.clone(),
+                account_user.clone(),
+            ],
+        )
+    }
+
+    /// Copy the decimals, supply and authorities of `account_mint` into `registry_node`.
+    fn snapshot_mint_info(
+        account_mint: &AccountInfo,
+        registry_node: &mut RegistryNodeAccount,
+    ) -> Result<(), ProgramError> {
+        let mint = spl_token::state::Mint::unpack(&account_mint.data.borrow())
+            .or(Err(RegistryError::UninitializedMint))?;
+        registry_node.mint_decimals = mint.decimals;
+        registry_node.mint_supply = mint.supply;
+        registry_node.mint_authority = mint.mint_authority.unwrap_or_default().to_bytes();
+        registry_node.mint_freeze_authority = mint.freeze_authority.unwrap_or_default().to_bytes();
+        registry_node.mint_snapshot_slot = Clock::get()?.slot;
+        Ok(())
+    }
+
+    /// `new` if it differs from `current`, for the diff in an EntryUpdated event.
+    fn changed<T: Clone + PartialEq>(current: &T, new: &T) -> Option<T> {
+        if current == new {
+            return None;
+        }
+        Some(new.clone())
+    }
+
+    fn registry_account_space(registry_node: &RegistryNodeAccount) -> Result<usize, ProgramError> {
+        Ok(AccountHeader::LEN + registry_node.try_to_vec()?.len())
+    }
+
+    /// Overwrite the node in `registry_node_account`, keeping the bump seed of its header.
     fn serialize_registry_account(
         registry_node: RegistryNodeAccount,
         registry_node_account: &AccountInfo,
     ) -> Result<(), ProgramError> {
-        let mut registry_node_data: Vec<u8> = Vec::new();
-        registry_node.serialize(&mut registry_node_data)?;
-
-        let mut length_bytes = [0; 4];
-        BigEndian::write_u32(&mut length_bytes, registry_node_data.len() as u32);
+        let bump_seed = AccountHeader::unpack(
+            &registry_node_account.data.borrow(),
+            &REGISTRY_NODE_DISCRIMINATOR,
+        )?
+        .bump_seed;
+        Self::write_account_data(
+            registry_node_account,
+            &registry_node.pack_account_data(bump_seed)?,
+        )?;
 
-        registry_node_data.insert(0, length_bytes[3]);
-        registry_node_data.insert(0, length_bytes[2]);
-        registry_node_data.insert(0, length_bytes[1]);
-        registry_node_data.insert(0, length_bytes[0]);
+        Ok(())
+    }
 
-        registry_node_account
+    /// Write `data` at the start of the data of `account`. Writing through `std::io::Write` would
+    /// advance the slice held by the AccountInfo, and shrink its `data_len`.
+    fn write_account_data(account: &AccountInfo, data: &[u8]) -> ProgramResult {
+        account
             .data
             .borrow_mut()
-            .write(&registry_node_data[..])?;
-
+            .get_mut(..data.len())
+            .ok_or(ProgramError::AccountDataTooSmall)?
+            .copy_from_slice(data);
         Ok(())
     }
 
     fn deserialize_registry_account(
         registry_node_account: &AccountInfo,
     ) -> Result<RegistryNodeAccount, ProgramError> {
-        if registry_node_account.data_len() == 0 {
-            return Err(ProgramError::from(RegistryError::NotYetRegisteredMint));
-        }
-        let length_bytes = u32::from_be_bytes(
-            (&registry_node_account.data.borrow()[..4])
-                .try_into()
-                .unwrap(),
-        ) as usize;
-        Ok(RegistryNodeAccount::try_from_slice(
-            &registry_node_account.data.borrow()[4..4 + length_bytes],
-        )?)
+        Self::assert_registered(registry_node_account)?;
+        RegistryNodeAccount::unpack_account_data(&registry_node_account.data.borrow())
     }
 
+    /// Overwrite the meta in `registry_meta_account`, keeping the bump seed of its header.
+    fn serialize_registry_meta(
+        registry_meta: &RegistryMetaAccount,
+        registry_meta_account: &AccountInfo,
+    ) -> Result<(), ProgramError> {
+        let bump_seed = AccountHeader::unpack(
+            &registry_meta_account.data.borrow(),
+            &REGISTRY_META_DISCRIMINATOR,
+        )?
+        .bump_seed;
+        Self::write_account_data(
+            registry_meta_account,
+            &registry_meta.pack_account_data(bump_seed)?,
+        )?;
+
+        Ok(())
+    }
+
+    /// Deserialize the RegistryMetaAccount, after asserting that it lives at the PDA derived from
+    /// its own `registry_id`. The registry's other PDAs are then derived from that id.
+    fn assert_valid_registry_meta(
+        program_id: &Pubkey,
+        account_registry_meta: &AccountInfo,
+    ) -> Result<RegistryMetaAccount, ProgramError> {
+        Self::assert_initialized(account_registry_meta)?;
+        let registry_meta = Self::deserialize_registry_meta(account_registry_meta)?;
+        Self::assert_valid_pda(
+            program_id,
+            account_registry_meta,
+            &RegistrySeeds::new(&registry_meta.registry_id).meta(),
+        )?;
+        Ok(registry_meta)
+    }
+
+    fn deserialize_registry_meta(
+        registry_meta_account: &AccountInfo,
+    ) -> Result<RegistryMetaAccount, ProgramError> {
+        RegistryMetaAccount::unpack_account_data(&registry_meta_account.data.borrow())
+    }
+
+    #[allow(clippy::too_many_arguments)]
     fn initialize_ata_if_not_exists(
         account_ata_program: &AccountInfo<'a>,
         account_user: &AccountInfo<'a>,
@@ -581,7 +2022,7 @@ impl<'a> Processor {
     ) -> Result<(), ProgramError> {
         if account_fee_destination_ata.data_len() == 0 {
             let create_account_instr =
-                spl_associated_token_account::create_associated_token_account(
+                spl_associated_token_account::instruction::create_associated_token_account(
                     account_user.key,
                     account_fee_destination.key,
                     account_fee_mint.key,
@@ -613,20 +2054,62 @@ impl<'a> Processor {
         Ok(())
     }
 
+    /// Assert that there are `num` accounts, followed by up to `MAX_SIGNERS` signers of a multisig
+    /// authority. See `assert_signed_by_authority`.
+    fn assert_number_of_accounts_with_signers(
+        accounts: &[AccountInfo],
+        num: usize,
+    ) -> Result<(), RegistryError> {
+        if accounts.len() < num || accounts.len() > num + spl_token::instruction::MAX_SIGNERS {
+            return Err(RegistryError::InvalidNumberOfAccounts);
+        }
+        Ok(())
+    }
+
+    /// Assert that `account` is the PDA derived from `seeds`, and return its bump seed. Accounts that
+    /// were already created by the program store their bump seed in their AccountHeader, so a
+    /// single `create_program_address` is enough. Otherwise the bump seed is searched for with
+    /// `find_program_address`, which may hash up to 255 candidates.
     fn assert_valid_pda(
         program_id: &Pubkey,
         account: &AccountInfo,
-        seed: &[u8],
+        seeds: &[&[u8]],
     ) -> Result<u8, RegistryError> {
-        let subseed = &seed[..std::cmp::min(32, seed.len())];
-        let (derived_pubkey, derived_bump_seed) =
-            Pubkey::find_program_address(&[subseed], program_id);
+        if let Some(stored_bump_seed) = Self::stored_bump_seed(program_id, account) {
+            let derived_pubkey = Pubkey::create_program_address(
+                &[seeds, &[&[stored_bump_seed]]].concat(),
+                program_id,
+            )
+            .or(Err(RegistryError::InvalidProgramDerivedAccount))?;
+            if *account.key != derived_pubkey {
+                return Err(RegistryError::InvalidProgramDerivedAccount);
+            }
+            return Ok(stored_bump_seed);
+        }
+        let (derived_pubkey, derived_bump_seed) = Pubkey::find_program_address(seeds, program_id);
         if *account.key != derived_pubkey {
             return Err(RegistryError::InvalidProgramDerivedAccount);
         }
         Ok(derived_bump_seed)
     }
 
+    /// The bump seed in the AccountHeader of `account`, if it is a current-version account owned
+    /// by the program. The `find-bump-seeds` feature ignores it, for benchmarks.
+    fn stored_bump_seed(program_id: &Pubkey, account: &AccountInfo) -> Option<u8> {
+        if cfg!(feature = "find-bump-seeds") || account.owner != program_id {
+            return None;
+        }
+        let data = account.data.borrow();
+        [
+            REGISTRY_META_DISCRIMINATOR,
+            REGISTRY_NODE_DISCRIMINATOR,
+            SYMBOL_CLAIM_DISCRIMINATOR,
+        ]
+        .iter()
+        .find_map(|discriminator| AccountHeader::unpack(&data, discriminator).ok())
+        .map(|header| header.bump_seed)
+    }
+
     fn assert_valid_ata(
         user_pubkey: &Pubkey,
         mint_pubkey: &Pubkey,
@@ -647,11 +2130,102 @@ impl<'a> Processor {
         Ok(())
     }
 
+    /// Assert that the fee source is a usable account of the fee mint holding at least `fee_amount`.
+    fn assert_valid_fee_source(
+        account_fee_source_ata: &AccountInfo,
+        registry_meta: &RegistryMetaAccount,
+        fee_amount: u64,
+    ) -> Result<(), RegistryError> {
+        Self::assert_initialized_ata(account_fee_source_ata)?;
+        if *account_fee_source_ata.owner != spl_token::ID {
+            return Err(RegistryError::InvalidAssociatedTokenAccount);
+        }
+        let fee_source = spl_token::state::Account::unpack(&account_fee_source_ata.data.borrow())
+            .or(Err(RegistryError::UninitializedAssociatedTokenAccount))?;
+        if fee_source.mint.to_bytes() != registry_meta.fee_mint {
+            return Err(RegistryError::InvalidFeeSourceMint);
+        }
+        if fee_source.is_frozen() {
+            return Err(RegistryError::FrozenFeeSourceAccount);
+        }
+        if fee_source.amount < fee_amount {
+            return Err(RegistryError::InsufficientFeeBalance);
+        }
+        Ok(())
+    }
+
+    /// Assert that the mint authority of `account_mint` has authorized the instruction (see
+    /// `assert_signed_by_authority`). Mints without a mint authority cannot prove ownership, so the
+    /// registry's `fee_update_authority` must authorize it for them instead.
+    fn assert_valid_mint_authority(
+        account_mint: &AccountInfo,
+        account_mint_authority: &AccountInfo,
+        registry_meta: &RegistryMetaAccount,
+        signer_accounts: &[AccountInfo],
+    ) -> Result<(), RegistryError> {
+        let mint = spl_token::state::Mint::unpack(&account_mint.data.borrow())
+            .or(Err(RegistryError::UninitializedMint))?;
+        match mint.mint_authority {
+            COption::Some(mint_authority) => {
+                if *account_mint_authority.key != mint_authority {
+                    return Err(RegistryError::InvalidMintAuthority);
+                }
+            }
+            COption::None => {
+                if account_mint_authority.key.to_bytes() != registry_meta.fee_update_authority {
+                    return Err(RegistryError::RevokedMintAuthority);
+                }
+            }
+        }
+        Self::assert_signed_by_authority(account_mint_authority, signer_accounts)
+    }
+
+    /// Assert that the user has signed and can pay for accounts, i.e. is owned by the system program.
     fn assert_valid_account_user(account_user: &AccountInfo) -> Result<(), RegistryError> {
-        if !account_user.is_signer {
+        Self::assert_signer(account_user)?;
+        Self::assert_valid_system_account(account_user)?;
+        Ok(())
+    }
+
+    /// Assert that `account` has signed. Unlike `assert_valid_account_user`, any owner is allowed, so
+    /// that program derived addresses (e.g, of a DAO) can sign through `invoke_signed`.
+    fn assert_signer(account: &AccountInfo) -> Result<(), RegistryError> {
+        if !account.is_signer {
             return Err(RegistryError::InvalidUserAccount);
         }
-        Self::assert_valid_system_account(account_user)?;
+        Ok(())
+    }
+
+    /// Assert that `account_authority` has authorized the instruction. If it is an SPL Token
+    /// multisig, at least `m` of its signers must be among `signer_accounts` and have signed, as in
+    /// spl-token's `validate_owner`. A key counts once, however many of the multisig's slots hold it
+    /// or however often it is passed. Otherwise the authority must have signed itself.
+    fn assert_signed_by_authority(
+        account_authority: &AccountInfo,
+        signer_accounts: &[AccountInfo],
+    ) -> Result<(), RegistryError> {
+        if *account_authority.owner != spl_token::ID
+            || account_authority.data_len() != spl_token::state::Multisig::LEN
+        {
+            return Self::assert_signer(account_authority);
+        }
+        let multisig = spl_token::state::Multisig::unpack(&account_authority.data.borrow())
+            .or(Err(RegistryError::MissingMultisigSigners))?;
+        let signers = &multisig.signers[..multisig.n as usize];
+        let mut matched = [false; spl_token::instruction::MAX_SIGNERS];
+        let mut num_signers = 0;
+        for account in signer_accounts.iter().filter(|account| account.is_signer) {
+            /* Duplicate keys all map to the slot that holds the key first. */
+            if let Some(position) = signers.iter().position(|signer| signer == account.key) {
+                if !matched[position] {
+                    matched[position] = true;
+                    num_signers += 1;
+                }
+            }
+        }
+        if num_signers < multisig.m as usize {
+            return Err(RegistryError::MissingMultisigSigners);
+        }
         Ok(())
     }
 
@@ -698,6 +2272,62 @@ impl<'a> Processor {
         Ok(())
     }
 
+    fn assert_entry_state(
+        state: EntryState,
+        allowed_states: &[EntryState],
+    ) -> Result<(), RegistryError> {
+        if allowed_states.contains(&state) {
+            return Ok(());
+        }
+        Err(match state {
+            EntryState::Active => RegistryError::PreviouslyRegisteredMint,
+            EntryState::Deleted => RegistryError::PreviouslyDeletedMint,
+            EntryState::Closed => RegistryError::ClosedMint,
+        })
+    }
+
+    /// Assert that the node account has been created, i.e. is not empty.
+    fn assert_registered(registry_node_account: &AccountInfo) -> Result<(), RegistryError> {
+        if registry_node_account.data_len() == 0 {
+            return Err(RegistryError::NotYetRegisteredMint);
+        }
+        Ok(())
+    }
+
+    /// Assert that the user is the `token_update_authority` of the node, without deserializing it.
+    /// Whether the user has signed is checked separately.
+    fn assert_token_update_authority(
+        account_user: &AccountInfo,
+        registry_node_account: &AccountInfo,
+    ) -> Result<(), ProgramError> {
+        Self::assert_registered(registry_node_account)?;
+        let registry_node_data = registry_node_account.data.borrow();
+        let token_update_authority =
+            RegistryNodeAccount::unpack_token_update_authority(&registry_node_data)?;
+        if account_user.key.as_ref() != token_update_authority {
+            return Err(ProgramError::from(
+                RegistryError::InvalidTokenUpdateAuthority,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Assert that `account` is the proposed new authority of a pending transfer, and has authorized
+    /// the instruction (see `assert_signed_by_authority`).
+    fn assert_pending_authority(
+        account: &AccountInfo,
+        pending_authority: &[u8; 32],
+        signer_accounts: &[AccountInfo],
+    ) -> Result<(), RegistryError> {
+        if *pending_authority == [0; 32] {
+            return Err(RegistryError::NoPendingAuthority);
+        }
+        if account.key.to_bytes() != *pending_authority {
+            return Err(RegistryError::InvalidPendingAuthority);
+        }
+        Self::assert_signed_by_authority(account, signer_accounts)
+    }
+
     fn assert_valid_system_program(account: &AccountInfo) -> Result<(), RegistryError> {
         if *account.key != system_program::ID {
             return Err(RegistryError::InvalidSystemProgram);
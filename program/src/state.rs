@@ -1,4 +1,152 @@
+use crate::error::RegistryError;
+use arrayref::array_ref;
 use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{hash::hashv, program_error::ProgramError};
+
+/// The discriminator of a RegistryMetaAccount.
+pub const REGISTRY_META_DISCRIMINATOR: [u8; 8] = *b"regmeta\0";
+/// The discriminator of a RegistryNodeAccount (including the RegistryHeadAccount/RegistryTailAccount).
+pub const REGISTRY_NODE_DISCRIMINATOR: [u8; 8] = *b"regnode\0";
+/// The discriminator of a SymbolClaimAccount.
+pub const SYMBOL_CLAIM_DISCRIMINATOR: [u8; 8] = *b"regsymb\0";
+/// The current layout version of both account types. Accounts in earlier layouts, including the
+/// version 0 accounts created before headers were introduced, must be upgraded with MigrateAccount
+/// before use. Every layout change bumps the version and adds a step to `migrate_account_data`.
+/// Fields added since version 1 go at the end of the account, so their steps only append them:
+///
+/// * 2: `RegistryMetaAccount::registry_id`.
+/// * 3: `RegistryMetaAccount::unique_symbols`.
+/// * 4: `RegistryMetaAccount::curators` and the `verified`, `verifier` and `verified_at` of
+///   RegistryNodeAccounts.
+/// * 5: `RegistryMetaAccount::pending_fee_update_authority` and
+///   `RegistryNodeAccount::pending_token_update_authority`.
+pub const REGISTRY_ACCOUNT_VERSION: u8 = 5;
+
+/// The header at the start of every account owned by the program. It is followed by the borsh
+/// serialization of the account, which is `data_length` bytes long.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct AccountHeader {
+    /// Identifies the type of the account. See `REGISTRY_META_DISCRIMINATOR` and
+    /// `REGISTRY_NODE_DISCRIMINATOR`.
+    pub discriminator: [u8; 8],
+    /// The version of the account layout. See `REGISTRY_ACCOUNT_VERSION`.
+    pub version: u8,
+    /// The bump seed of the program derived address of the account. It is set when the account is
+    /// created, so that later instructions can verify the address with `create_program_address`.
+    pub bump_seed: u8,
+    /// The length, in bytes, of the serialized account following the header.
+    pub data_length: u32,
+}
+
+impl AccountHeader {
+    /// The serialized length of the header.
+    pub const LEN: usize = 14;
+
+    /// Read the header at the start of `data`, checking that it belongs to an account of the type
+    /// identified by `discriminator` in the current layout version.
+    pub fn unpack(data: &[u8], discriminator: &[u8; 8]) -> Result<Self, RegistryError> {
+        let header = Self::unpack_any_version(data, discriminator)?;
+        if header.version != REGISTRY_ACCOUNT_VERSION {
+            return Err(RegistryError::InvalidAccountVersion);
+        }
+        Ok(header)
+    }
+
+    /// Like `unpack`, but accepts any layout version, for MigrateAccount.
+    pub fn unpack_any_version(data: &[u8], discriminator: &[u8; 8]) -> Result<Self, RegistryError> {
+        data.get(..Self::LEN)
+            .and_then(|header_data| Self::try_from_slice(header_data).ok())
+            .filter(|header| header.discriminator == *discriminator)
+            .ok_or(RegistryError::InvalidAccountType)
+    }
+}
+
+/// The maximum length, in bytes, of `RegistryNodeAccount::token_symbol`.
+pub const MAX_SYMBOL_LENGTH: usize = 16;
+/// The maximum length, in bytes, of `RegistryNodeAccount::token_name`.
+pub const MAX_NAME_LENGTH: usize = 32;
+/// The maximum length, in bytes, of `RegistryNodeAccount::token_logo_url`.
+pub const MAX_LOGO_URL_LENGTH: usize = 256;
+/// The initial `RegistryMetaAccount::max_tags` of a new registry.
+pub const DEFAULT_MAX_TAGS: u8 = 8;
+/// The maximum length, in bytes, of a single tag.
+pub const MAX_TAG_LENGTH: usize = 256;
+/// The initial `RegistryMetaAccount::max_extensions` of a new registry.
+pub const DEFAULT_MAX_EXTENSIONS: u8 = 16;
+/// The maximum length, in bytes, of an extension's key or value.
+pub const MAX_EXTENSION_FIELD_LENGTH: usize = 256;
+
+/// The maximum number of addresses (besides `fee_update_authority`) that can be exempt from fees.
+pub const MAX_FEE_EXEMPT_ACCOUNTS: usize = 8;
+
+/// The maximum number of addresses (besides `fee_update_authority`) that can verify registry nodes.
+pub const MAX_CURATORS: usize = 8;
+
+/// The maximum length, in bytes, of `RegistryMetaAccount::registry_id`.
+pub const MAX_REGISTRY_ID_LENGTH: usize = 32;
+
+/// The seeds of the program derived addresses of the accounts of one registry. The seeds of every
+/// kind of account start with their own tag, followed by the sha256 hash of the `registry_id`, so
+/// that no two accounts of any registries share an address. The default registry, whose id is
+/// empty, keeps the addresses used before registries had ids, which have no hash and no tag for
+/// nodes: `[b"meta"]`, `[b"head"]`, `[b"tail"]`, `[mint]` and `[b"symbol", normalized_symbol]`.
+/// Those are all shorter than the seeds of any other registry, so they cannot collide with them.
+pub struct RegistrySeeds {
+    /// The hash of the `registry_id`, or None for the default registry.
+    namespace: Option<[u8; 32]>,
+}
+
+impl RegistrySeeds {
+    pub fn new(registry_id: &str) -> Self {
+        RegistrySeeds {
+            namespace: if registry_id.is_empty() {
+                None
+            } else {
+                Some(hashv(&[registry_id.as_bytes()]).to_bytes())
+            },
+        }
+    }
+
+    /// The seeds of the RegistryMetaAccount.
+    pub fn meta(&self) -> Vec<&[u8]> {
+        self.tagged(b"meta", &[])
+    }
+
+    /// The seeds of the RegistryHeadAccount.
+    pub fn head(&self) -> Vec<&[u8]> {
+        self.tagged(b"head", &[])
+    }
+
+    /// The seeds of the RegistryTailAccount.
+    pub fn tail(&self) -> Vec<&[u8]> {
+        self.tagged(b"tail", &[])
+    }
+
+    /// The seeds of the RegistryNodeAccount of the mint at `mint`.
+    pub fn node<'s>(&'s self, mint: &'s [u8]) -> Vec<&'s [u8]> {
+        match self.namespace {
+            None => vec![mint],
+            Some(_) => self.tagged(b"node", mint),
+        }
+    }
+
+    /// The seeds of the SymbolClaimAccount of `normalized_symbol`, as given by
+    /// `validation::normalize_symbol`.
+    pub fn symbol_claim<'s>(&'s self, normalized_symbol: &'s str) -> Vec<&'s [u8]> {
+        self.tagged(b"symbol", normalized_symbol.as_bytes())
+    }
+
+    fn tagged<'s>(&'s self, tag: &'static [u8], key: &'s [u8]) -> Vec<&'s [u8]> {
+        let mut seeds = vec![tag];
+        if let Some(namespace) = &self.namespace {
+            seeds.push(namespace);
+        }
+        if !key.is_empty() {
+            seeds.push(key);
+        }
+        seeds
+    }
+}
 
 #[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
 pub struct RegistryMetaAccount {
@@ -12,9 +160,55 @@ pub struct RegistryMetaAccount {
     pub fee_destination: [u8; 32],
     /// The address that is allowed to change fee settings.
     pub fee_update_authority: [u8; 32],
+    /// Addresses that may create RegistryNodeAccounts without paying the fee. Unused slots are zeroed.
+    pub fee_exempt_accounts: [[u8; 32]; MAX_FEE_EXEMPT_ACCOUNTS],
+    /// The maximum number of entries in `RegistryNodeAccount::token_tags`.
+    pub max_tags: u8,
+    /// The maximum number of entries in `RegistryNodeAccount::token_extensions`.
+    pub max_extensions: u8,
+    /// The id that all PDAs of the registry are derived from. Empty for the default registry.
+    pub registry_id: String,
+    /// Whether each normalized `token_symbol` may only be used by one mint. See `SymbolClaimAccount`.
+    pub unique_symbols: bool,
+    /// Addresses that may verify and unverify RegistryNodeAccounts. Unused slots are zeroed.
+    pub curators: [[u8; 32]; MAX_CURATORS],
+    /// The address proposed by TransferFeeAuthority, which becomes the `fee_update_authority` once it
+    /// signs AcceptFeeAuthority. Zeroed if no transfer is pending.
+    pub pending_fee_update_authority: [u8; 32],
 }
 
-#[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
+impl RegistryMetaAccount {
+    /// Deserialize the data of a RegistryMetaAccount, which holds an `AccountHeader` followed by
+    /// the borsh serialization of the meta.
+    pub fn unpack_account_data(data: &[u8]) -> Result<Self, ProgramError> {
+        Ok(Self::try_from_slice(unpack_account(
+            data,
+            &REGISTRY_META_DISCRIMINATOR,
+        )?)?)
+    }
+
+    /// The inverse of `unpack_account_data`.
+    pub fn pack_account_data(&self, bump_seed: u8) -> Result<Vec<u8>, ProgramError> {
+        pack_account(&REGISTRY_META_DISCRIMINATOR, bump_seed, self)
+    }
+
+    /// Whether `address` may create a RegistryNodeAccount without paying `fee_amount`.
+    pub fn is_fee_exempt(&self, address: &[u8; 32]) -> bool {
+        *address == self.fee_update_authority
+            || (*address != [0; 32] && self.fee_exempt_accounts.contains(address))
+    }
+
+    /// Whether `address` may verify and unverify RegistryNodeAccounts.
+    pub fn is_curator(&self, address: &[u8; 32]) -> bool {
+        *address == self.fee_update_authority
+            || (*address != [0; 32] && self.curators.contains(address))
+    }
+}
+
+/// The fixed-size fields of a RegistryNodeAccount come first, so that they can be read and written
+/// at fixed offsets without deserializing the whole node (see `RegistryNodeAccount::unpack_token_mint`).
+/// Fields added since version 1 follow the token metadata, as `REGISTRY_ACCOUNT_VERSION` describes.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Default, PartialEq)]
 pub struct RegistryNodeAccount {
     /// The address of the next RegistryNodeAccount.
     pub next_registry_node: [u8; 32],
@@ -22,6 +216,21 @@ pub struct RegistryNodeAccount {
     pub prev_registry_node: [u8; 32],
     /// The address of the token Mint. Only one RegistryNodeAccount per Mint address is allowed.
     pub token_mint: [u8; 32],
+    /// The address that is allowed to update all token properties (except the mint address), delete the node, or change the RegistryNodeAccount::token_update_authority.
+    pub token_update_authority: [u8; 32],
+    /// Where this node is in its lifecycle. See `EntryState`.
+    pub state: EntryState,
+    /// The `decimals` of the token Mint. This and the other `mint_*` fields are a snapshot of the
+    /// Mint, taken by CreateEntry, UpdateEntry and RefreshMintInfo.
+    pub mint_decimals: u8,
+    /// The `supply` of the token Mint.
+    pub mint_supply: u64,
+    /// The `mint_authority` of the token Mint. Zeroed if the Mint has none.
+    pub mint_authority: [u8; 32],
+    /// The `freeze_authority` of the token Mint. Zeroed if the Mint has none.
+    pub mint_freeze_authority: [u8; 32],
+    /// The slot at which the `mint_*` fields were last updated.
+    pub mint_snapshot_slot: u64,
     /// The token ticker.
     pub token_symbol: String,
     /// The token name.
@@ -32,30 +241,351 @@ pub struct RegistryNodeAccount {
     pub token_tags: Vec<String>,
     /// A list of links for the token (e.g, "website", "twitter").
     pub token_extensions: Vec<Vec<String>>,
-    /// The address that is allowed to update all token properties (except the mint address), delete the node, or change the RegistryNodeAccount::token_update_authority.
+    /// Whether a curator of the registry has approved the node's metadata. Cleared whenever the
+    /// metadata changes, by UpdateEntry or by a CreateEntry that replaces a Deleted node.
+    pub verified: bool,
+    /// The curator that verified the node. Zeroed if the node is not verified.
+    pub verifier: [u8; 32],
+    /// The unix timestamp at which the node was verified. Zero if the node is not verified.
+    pub verified_at: i64,
+    /// The address proposed by TransferTokenAuthority, which becomes the `token_update_authority`
+    /// once it signs AcceptTokenAuthority. Zeroed if no transfer is pending.
+    pub pending_token_update_authority: [u8; 32],
+}
+
+/// The lifecycle of a RegistryNodeAccount. The allowed transitions are:
+///
+/// * `CreateEntry`: a new node is Active. A Deleted node may also be replaced by anyone, which makes
+///   it Active under a new `token_update_authority`.
+/// * `DeleteEntry`: Active -> Deleted.
+/// * `RestoreEntry`: Deleted -> Active, by the node's `token_update_authority`.
+/// * `CloseEntry`: Active or Deleted -> Closed. The node is wiped and given back to the system
+///   program at once, so a Closed node is never stored, and the mint can be registered again.
+///
+/// `UpdateEntry` and `TransferTokenAuthority` are only allowed on Active nodes. The variants are
+/// ordered so that Active and Deleted serialize the same as the former `deleted: bool` field.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EntryState {
+    Active,
+    Deleted,
+    Closed,
+}
+
+/* Written out, as `#[default]` on enum variants needs a newer compiler than BPF toolchains have. */
+#[allow(clippy::derivable_impls)]
+impl Default for EntryState {
+    fn default() -> Self {
+        EntryState::Active
+    }
+}
+
+/* Offsets of the fixed-size fields of a RegistryNodeAccount within its account data. */
+const TOKEN_MINT_OFFSET: usize = AccountHeader::LEN + 64;
+const TOKEN_UPDATE_AUTHORITY_OFFSET: usize = TOKEN_MINT_OFFSET + 32;
+const STATE_OFFSET: usize = TOKEN_UPDATE_AUTHORITY_OFFSET + 32;
+
+impl RegistryNodeAccount {
+    /// Deserialize the data of a RegistryNodeAccount (or the RegistryHeadAccount/RegistryTailAccount),
+    /// which holds an `AccountHeader` followed by the borsh serialization of the node.
+    pub fn unpack_account_data(data: &[u8]) -> Result<Self, ProgramError> {
+        Ok(Self::try_from_slice(unpack_account(
+            data,
+            &REGISTRY_NODE_DISCRIMINATOR,
+        )?)?)
+    }
+
+    /// The inverse of `unpack_account_data`.
+    pub fn pack_account_data(&self, bump_seed: u8) -> Result<Vec<u8>, ProgramError> {
+        pack_account(&REGISTRY_NODE_DISCRIMINATOR, bump_seed, self)
+    }
+
+    /// Read `token_mint` from the data of a RegistryNodeAccount, without deserializing it.
+    pub fn unpack_token_mint(data: &[u8]) -> Result<&[u8; 32], ProgramError> {
+        let field = unpack_node_field(data, TOKEN_MINT_OFFSET, 32)?;
+        Ok(array_ref![field, 0, 32])
+    }
+
+    /// Read `token_update_authority` from the data of a RegistryNodeAccount, without deserializing it.
+    pub fn unpack_token_update_authority(data: &[u8]) -> Result<&[u8; 32], ProgramError> {
+        let field = unpack_node_field(data, TOKEN_UPDATE_AUTHORITY_OFFSET, 32)?;
+        Ok(array_ref![field, 0, 32])
+    }
+
+    /// Read `state` from the data of a RegistryNodeAccount, without deserializing it.
+    pub fn unpack_state(data: &[u8]) -> Result<EntryState, ProgramError> {
+        Ok(EntryState::try_from_slice(unpack_node_field(
+            data,
+            STATE_OFFSET,
+            1,
+        )?)?)
+    }
+
+    /// Overwrite `state` in the data of a RegistryNodeAccount, without deserializing it.
+    pub fn pack_state(data: &mut [u8], state: EntryState) -> Result<(), ProgramError> {
+        unpack_node_field(data, STATE_OFFSET, 1)?;
+        data[STATE_OFFSET] = state as u8;
+        Ok(())
+    }
+
+    /// Mark the node as verified by `verifier` at `verified_at`.
+    pub fn verify(&mut self, verifier: [u8; 32], verified_at: i64) {
+        self.verified = true;
+        self.verifier = verifier;
+        self.verified_at = verified_at;
+    }
+
+    /// Clear the verification of the node, if any.
+    pub fn clear_verification(&mut self) {
+        self.verified = false;
+        self.verifier = [0; 32];
+        self.verified_at = 0;
+    }
+}
+
+fn unpack_account<'d>(data: &'d [u8], discriminator: &[u8; 8]) -> Result<&'d [u8], ProgramError> {
+    let header = AccountHeader::unpack(data, discriminator)?;
+    data[AccountHeader::LEN..]
+        .get(..header.data_length as usize)
+        .ok_or(ProgramError::InvalidAccountData)
+}
+
+fn pack_account<T: BorshSerialize>(
+    discriminator: &[u8; 8],
+    bump_seed: u8,
+    account: &T,
+) -> Result<Vec<u8>, ProgramError> {
+    pack_account_bytes(discriminator, bump_seed, &account.try_to_vec()?)
+}
+
+/// Prefix `account_data`, the borsh serialization of an account, with a current-version header.
+fn pack_account_bytes(
+    discriminator: &[u8; 8],
+    bump_seed: u8,
+    account_data: &[u8],
+) -> Result<Vec<u8>, ProgramError> {
+    let header = AccountHeader {
+        discriminator: *discriminator,
+        version: REGISTRY_ACCOUNT_VERSION,
+        bump_seed,
+        data_length: account_data.len() as u32,
+    };
+    let mut data = header.try_to_vec()?;
+    data.extend_from_slice(account_data);
+    Ok(data)
+}
+
+/// A step of MigrateAccount, which upgrades the borsh serialization of an account from one layout
+/// version to the next. Version 0 accounts are passed whole, as they have no `AccountHeader`.
+type MigrationStep = fn(&[u8]) -> Result<Vec<u8>, ProgramError>;
+
+/// The steps that upgrade a RegistryMetaAccount, indexed by the version that they upgrade from.
+const REGISTRY_META_MIGRATIONS: [MigrationStep; REGISTRY_ACCOUNT_VERSION as usize] = [
+    RegistryMetaAccountV0::migrate,
+    |data| append_fields(data, &String::new()),
+    |data| append_fields(data, &false),
+    |data| append_fields(data, &[[0u8; 32]; MAX_CURATORS]),
+    |data| append_fields(data, &[0u8; 32]),
+];
+
+/// The steps that upgrade a RegistryNodeAccount, indexed by the version that they upgrade from.
+const REGISTRY_NODE_MIGRATIONS: [MigrationStep; REGISTRY_ACCOUNT_VERSION as usize] = [
+    RegistryNodeAccountV0::migrate,
+    |data| Ok(data.to_vec()),
+    |data| Ok(data.to_vec()),
+    |data| append_fields(data, &(false, [0u8; 32], 0i64)),
+    |data| append_fields(data, &[0u8; 32]),
+];
+
+/// Append `fields`, the initial values of the fields that a layout version adds at the end of an
+/// account, to its borsh serialization.
+fn append_fields<T: BorshSerialize>(data: &[u8], fields: &T) -> Result<Vec<u8>, ProgramError> {
+    let mut data = data.to_vec();
+    fields.serialize(&mut data)?;
+    Ok(data)
+}
+
+/// Upgrade `data`, an account of the type identified by `discriminator` in the layout `version`, to
+/// the current layout one version at a time, with `bump_seed` in its new header.
+pub fn migrate_account_data(
+    data: &[u8],
+    discriminator: &[u8; 8],
+    version: u8,
+    bump_seed: u8,
+) -> Result<Vec<u8>, ProgramError> {
+    let migrations: &[MigrationStep] = match *discriminator {
+        REGISTRY_META_DISCRIMINATOR => &REGISTRY_META_MIGRATIONS,
+        REGISTRY_NODE_DISCRIMINATOR => &REGISTRY_NODE_MIGRATIONS,
+        _ => return Err(RegistryError::InvalidAccountType.into()),
+    };
+    let migrations = migrations
+        .get(version as usize..)
+        .ok_or(RegistryError::InvalidAccountVersion)?;
+    let mut account_data = if version == 0 {
+        data.to_vec()
+    } else {
+        let header = AccountHeader::unpack_any_version(data, discriminator)?;
+        data[AccountHeader::LEN..]
+            .get(..header.data_length as usize)
+            .ok_or(ProgramError::InvalidAccountData)?
+            .to_vec()
+    };
+    for migration in migrations {
+        account_data = migration(&account_data)?;
+    }
+    pack_account_bytes(discriminator, bump_seed, &account_data)
+}
+
+fn unpack_node_field(data: &[u8], offset: usize, length: usize) -> Result<&[u8], ProgramError> {
+    AccountHeader::unpack(data, &REGISTRY_NODE_DISCRIMINATOR)?;
+    data.get(offset..offset + length)
+        .ok_or(ProgramError::InvalidAccountData)
+}
+
+/// Marks a token symbol as taken, in a registry with `RegistryMetaAccount::unique_symbols` set. It
+/// lives at the PDA derived from `RegistrySeeds::symbol_claim`.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
+pub struct SymbolClaimAccount {
+    /// The address of the Mint whose RegistryNodeAccount holds the symbol.
+    pub token_mint: [u8; 32],
+}
+
+impl SymbolClaimAccount {
+    /// Deserialize the data of a SymbolClaimAccount, which holds an `AccountHeader` followed by the
+    /// borsh serialization of the claim.
+    pub fn unpack_account_data(data: &[u8]) -> Result<Self, ProgramError> {
+        Ok(Self::try_from_slice(unpack_account(
+            data,
+            &SYMBOL_CLAIM_DISCRIMINATOR,
+        )?)?)
+    }
+
+    /// The inverse of `unpack_account_data`.
+    pub fn pack_account_data(&self, bump_seed: u8) -> Result<Vec<u8>, ProgramError> {
+        pack_account(&SYMBOL_CLAIM_DISCRIMINATOR, bump_seed, self)
+    }
+}
+
+/// The layout of a RegistryMetaAccount before account headers were introduced (version 0).
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
+pub struct RegistryMetaAccountV0 {
+    pub head_registry_node: [u8; 32],
+    pub fee_amount: u64,
+    pub fee_mint: [u8; 32],
+    pub fee_destination: [u8; 32],
+    pub fee_update_authority: [u8; 32],
+}
+
+impl RegistryMetaAccountV0 {
+    fn migrate(data: &[u8]) -> Result<Vec<u8>, ProgramError> {
+        Ok(RegistryMetaAccountV1::from(Self::try_from_slice(data)?).try_to_vec()?)
+    }
+}
+
+/// The layout of a RegistryMetaAccount in version 1, the first with an `AccountHeader`.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
+pub struct RegistryMetaAccountV1 {
+    pub head_registry_node: [u8; 32],
+    pub fee_amount: u64,
+    pub fee_mint: [u8; 32],
+    pub fee_destination: [u8; 32],
+    pub fee_update_authority: [u8; 32],
+    pub fee_exempt_accounts: [[u8; 32]; MAX_FEE_EXEMPT_ACCOUNTS],
+    pub max_tags: u8,
+    pub max_extensions: u8,
+}
+
+impl From<RegistryMetaAccountV0> for RegistryMetaAccountV1 {
+    fn from(registry_meta: RegistryMetaAccountV0) -> Self {
+        RegistryMetaAccountV1 {
+            head_registry_node: registry_meta.head_registry_node,
+            fee_amount: registry_meta.fee_amount,
+            fee_mint: registry_meta.fee_mint,
+            fee_destination: registry_meta.fee_destination,
+            fee_update_authority: registry_meta.fee_update_authority,
+            fee_exempt_accounts: [[0; 32]; MAX_FEE_EXEMPT_ACCOUNTS],
+            max_tags: DEFAULT_MAX_TAGS,
+            max_extensions: DEFAULT_MAX_EXTENSIONS,
+        }
+    }
+}
+
+/// The layout of a RegistryNodeAccount before account headers were introduced (version 0). The
+/// account data holds the borsh serialization prefixed by its big-endian u32 length.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
+pub struct RegistryNodeAccountV0 {
+    pub next_registry_node: [u8; 32],
+    pub prev_registry_node: [u8; 32],
+    pub token_mint: [u8; 32],
+    pub token_symbol: String,
+    pub token_name: String,
+    pub token_logo_url: String,
+    pub token_tags: Vec<String>,
+    pub token_extensions: Vec<Vec<String>>,
     pub token_update_authority: [u8; 32],
-    /// Has this account been deleted?
     pub deleted: bool,
 }
 
-impl RegistryNodeAccount {
-    pub fn max_filled() -> RegistryNodeAccount {
-        RegistryNodeAccount {
-            next_registry_node: [0; 32],
-            prev_registry_node: [0; 32],
-            token_mint: [0; 32],
-            token_symbol: String::from_utf8(vec![b'\0'; 16]).unwrap(),
-            token_name: String::from_utf8(vec![b'\0'; 32]).unwrap(),
-            token_logo_url: String::from_utf8(vec![b'\0'; 256]).unwrap(),
-            token_tags: vec![String::from_utf8(vec![b'\0'; 256]).unwrap(); 2],
-            token_extensions: vec![vec![String::from_utf8(vec![b'\0'; 256]).unwrap(); 2]; 2],
-            token_update_authority: [0; 32],
-            deleted: false,
+impl RegistryNodeAccountV0 {
+    pub fn unpack_account_data(data: &[u8]) -> Result<Self, ProgramError> {
+        if data.len() < 4 {
+            return Err(ProgramError::InvalidAccountData);
         }
+        let length = u32::from_be_bytes(*array_ref![data, 0, 4]) as usize;
+        let node_data = data[4..]
+            .get(..length)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        Ok(Self::try_from_slice(node_data)?)
+    }
+
+    fn migrate(data: &[u8]) -> Result<Vec<u8>, ProgramError> {
+        Ok(RegistryNodeAccountV1::from(Self::unpack_account_data(data)?).try_to_vec()?)
     }
 }
 
+/// The layout of a RegistryNodeAccount in version 1, the first with an `AccountHeader`.
 #[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
+pub struct RegistryNodeAccountV1 {
+    pub next_registry_node: [u8; 32],
+    pub prev_registry_node: [u8; 32],
+    pub token_mint: [u8; 32],
+    pub token_update_authority: [u8; 32],
+    pub state: EntryState,
+    pub mint_decimals: u8,
+    pub mint_supply: u64,
+    pub mint_authority: [u8; 32],
+    pub mint_freeze_authority: [u8; 32],
+    pub mint_snapshot_slot: u64,
+    pub token_symbol: String,
+    pub token_name: String,
+    pub token_logo_url: String,
+    pub token_tags: Vec<String>,
+    pub token_extensions: Vec<Vec<String>>,
+}
+
+/// The `mint_*` fields are left zeroed, until the next RefreshMintInfo or UpdateEntry.
+impl From<RegistryNodeAccountV0> for RegistryNodeAccountV1 {
+    fn from(registry_node: RegistryNodeAccountV0) -> Self {
+        RegistryNodeAccountV1 {
+            next_registry_node: registry_node.next_registry_node,
+            prev_registry_node: registry_node.prev_registry_node,
+            token_mint: registry_node.token_mint,
+            token_update_authority: registry_node.token_update_authority,
+            state: if registry_node.deleted {
+                EntryState::Deleted
+            } else {
+                EntryState::Active
+            },
+            token_symbol: registry_node.token_symbol,
+            token_name: registry_node.token_name,
+            token_logo_url: registry_node.token_logo_url,
+            token_tags: registry_node.token_tags,
+            token_extensions: registry_node.token_extensions,
+            ..RegistryNodeAccountV1::default()
+        }
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Default, PartialEq)]
 pub struct CreateUpdateEntryInstructionData {
     pub token_symbol: String,
     pub token_name: String,
@@ -63,3 +593,14 @@ pub struct CreateUpdateEntryInstructionData {
     pub token_tags: Vec<String>,
     pub token_extensions: Vec<Vec<String>>,
 }
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
+pub struct UpdateFeeExemptionsInstructionData {
+    pub fee_exempt_accounts: Vec<[u8; 32]>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
+pub struct UpdateEntryLimitsInstructionData {
+    pub max_tags: u8,
+    pub max_extensions: u8,
+}
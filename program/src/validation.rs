@@ -0,0 +1,176 @@
+use crate::{
+    error::RegistryError,
+    state::{
+        RegistryMetaAccount, MAX_EXTENSION_FIELD_LENGTH, MAX_LOGO_URL_LENGTH, MAX_NAME_LENGTH,
+        MAX_REGISTRY_ID_LENGTH, MAX_SYMBOL_LENGTH, MAX_TAG_LENGTH,
+    },
+};
+use solana_program::pubkey::MAX_SEED_LEN;
+
+/// Check every field of a CreateEntry or UpdateEntry against the field limits in `state.rs` and the
+/// tag and extension limits of the registry, before anything is written to the account.
+pub fn validate_entry(
+    registry_meta: &RegistryMetaAccount,
+    token_symbol: &str,
+    token_name: &str,
+    token_logo_url: &str,
+    token_tags: &[String],
+    token_extensions: &[Vec<String>],
+) -> Result<(), RegistryError> {
+    validate_symbol(token_symbol)?;
+    validate_name(token_name)?;
+    validate_logo_url(token_logo_url)?;
+    validate_tags(token_tags, registry_meta.max_tags as usize)?;
+    validate_extensions(token_extensions, registry_meta.max_extensions as usize)?;
+    Ok(())
+}
+
+/// A symbol is a non-empty ticker without whitespace or control characters (e.g, "USDC").
+pub fn validate_symbol(token_symbol: &str) -> Result<(), RegistryError> {
+    if token_symbol.len() > MAX_SYMBOL_LENGTH {
+        return Err(RegistryError::SymbolTooLong);
+    }
+    if token_symbol.is_empty()
+        || token_symbol
+            .chars()
+            .any(|c| c.is_control() || c.is_whitespace())
+    {
+        return Err(RegistryError::InvalidSymbol);
+    }
+    Ok(())
+}
+
+/// A name is any non-empty text without control characters (e.g, "USD Coin").
+pub fn validate_name(token_name: &str) -> Result<(), RegistryError> {
+    if token_name.len() > MAX_NAME_LENGTH {
+        return Err(RegistryError::NameTooLong);
+    }
+    if token_name.trim().is_empty() || token_name.chars().any(char::is_control) {
+        return Err(RegistryError::InvalidName);
+    }
+    Ok(())
+}
+
+/// A logo URL may be empty, but otherwise must be printable ASCII without whitespace.
+pub fn validate_logo_url(token_logo_url: &str) -> Result<(), RegistryError> {
+    if token_logo_url.len() > MAX_LOGO_URL_LENGTH {
+        return Err(RegistryError::LogoUrlTooLong);
+    }
+    if !token_logo_url.bytes().all(|b| b.is_ascii_graphic()) {
+        return Err(RegistryError::InvalidLogoUrl);
+    }
+    Ok(())
+}
+
+/// Tags are identifiers made of ASCII letters, digits, '-' and '_' (e.g, "stablecoin").
+pub fn validate_tags(token_tags: &[String], max_tags: usize) -> Result<(), RegistryError> {
+    if token_tags.len() > max_tags {
+        return Err(RegistryError::TooManyTags);
+    }
+    for token_tag in token_tags {
+        if token_tag.len() > MAX_TAG_LENGTH {
+            return Err(RegistryError::TagTooLong);
+        }
+        if !is_identifier(token_tag) {
+            return Err(RegistryError::InvalidTag);
+        }
+    }
+    Ok(())
+}
+
+/// Extensions are [key, value] pairs, where the key is an identifier (e.g, "website") and the
+/// value is printable ASCII without whitespace (e.g, "https://www.centre.io/").
+pub fn validate_extensions(
+    token_extensions: &[Vec<String>],
+    max_extensions: usize,
+) -> Result<(), RegistryError> {
+    if token_extensions.len() > max_extensions {
+        return Err(RegistryError::TooManyExtensions);
+    }
+    for token_extension in token_extensions {
+        let (key, value) = match &token_extension[..] {
+            [key, value] => (key, value),
+            _ => return Err(RegistryError::InvalidExtension),
+        };
+        if key.len() > MAX_EXTENSION_FIELD_LENGTH || value.len() > MAX_EXTENSION_FIELD_LENGTH {
+            return Err(RegistryError::ExtensionTooLong);
+        }
+        if !is_identifier(key) {
+            return Err(RegistryError::InvalidExtensionKey);
+        }
+        if value.is_empty() || !value.bytes().all(|b| b.is_ascii_graphic()) {
+            return Err(RegistryError::InvalidExtensionValue);
+        }
+    }
+    Ok(())
+}
+
+/// The form of a symbol that `RegistryMetaAccount::unique_symbols` is enforced on: case-folded,
+/// without invisible characters, and with characters that are easily mistaken for one another mapped
+/// to a single one. For example, "USDC", "usdc", "ＵＳＤＣ" and "USDС" (with a Cyrillic "С") all
+/// normalize to "usdc".
+pub fn normalize_symbol(token_symbol: &str) -> Result<String, RegistryError> {
+    let normalized_symbol: String = token_symbol
+        .chars()
+        .filter(|c| !is_invisible(*c))
+        .flat_map(char::to_lowercase)
+        .map(fold_confusable)
+        .collect();
+    if normalized_symbol.is_empty() || normalized_symbol.len() > MAX_SEED_LEN {
+        return Err(RegistryError::InvalidSymbol);
+    }
+    Ok(normalized_symbol)
+}
+
+/// A registry id is either empty (the default registry) or an identifier (e.g, "verified").
+pub fn validate_registry_id(registry_id: &str) -> Result<(), RegistryError> {
+    if registry_id.len() > MAX_REGISTRY_ID_LENGTH
+        || !(registry_id.is_empty() || is_identifier(registry_id))
+    {
+        return Err(RegistryError::InvalidRegistryId);
+    }
+    Ok(())
+}
+
+fn is_invisible(c: char) -> bool {
+    matches!(
+        c,
+        '\u{00ad}' | '\u{034f}' | '\u{180e}' | '\u{200b}'..='\u{200f}' | '\u{2060}'..='\u{2064}' | '\u{feff}'
+    )
+}
+
+/// Map a lowercase character to the lowercase ASCII character it is most easily mistaken for.
+fn fold_confusable(c: char) -> char {
+    match c {
+        /* Fullwidth forms of ASCII characters. */
+        '\u{ff01}'..='\u{ff5e}' => fold_confusable(char::from_u32(c as u32 - 0xfee0).unwrap_or(c)),
+        '0' | 'ο' | 'о' => 'o',
+        '1' | 'i' | 'ı' | 'ι' | 'і' | 'ӏ' => 'l',
+        'α' | 'а' => 'a',
+        'β' | 'в' => 'b',
+        'с' | 'ϲ' => 'c',
+        'ԁ' => 'd',
+        'ε' | 'е' => 'e',
+        'η' | 'н' | 'һ' => 'h',
+        'ј' => 'j',
+        'κ' | 'к' => 'k',
+        'μ' | 'м' => 'm',
+        'ν' => 'n',
+        'ρ' | 'р' => 'p',
+        'ԛ' => 'q',
+        'ѕ' => 's',
+        'τ' | 'т' => 't',
+        'υ' | 'у' => 'y',
+        'ԝ' => 'w',
+        'χ' | 'х' => 'x',
+        'ζ' => 'z',
+        _ => c,
+    }
+}
+
+fn is_identifier(value: &str) -> bool {
+    !value.is_empty()
+        && value
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+}
@@ -0,0 +1,382 @@
+//! Helpers shared by the program-test suites, which run `Processor::process` natively under
+//! `cargo test`, or against the BPF build under `cargo test-bpf`.
+#![allow(dead_code)]
+
+use registry::{
+    error::RegistryError,
+    instruction,
+    processor::Processor,
+    state::{CreateUpdateEntryInstructionData, RegistryMetaAccount, RegistryNodeAccount},
+};
+use solana_program_test::{processor, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    account::Account,
+    instruction::{Instruction, InstructionError},
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction,
+    transaction::{Transaction, TransactionError},
+};
+
+/// The lamports that `TestRegistry::new_user` funds a user with.
+pub const USER_LAMPORTS: u64 = 10_000_000_000;
+
+/// The tokens of the fee mint that `TestRegistry::new_fee_payer` mints to a user.
+pub const USER_FEE_TOKENS: u64 = 1_000_000;
+
+/// A ProgramTest of the registry. SPL Token and the ATA program also run natively, rather than
+/// from their bundled BPF builds.
+pub fn program_test() -> ProgramTest {
+    let mut program_test =
+        ProgramTest::new("registry", registry::id(), processor!(Processor::process));
+    program_test.add_program(
+        "spl_token",
+        spl_token::id(),
+        processor!(spl_token::processor::Processor::process),
+    );
+    program_test.add_program(
+        "spl_associated_token_account",
+        spl_associated_token_account::id(),
+        processor!(spl_associated_token_account::processor::process_instruction),
+    );
+    program_test
+}
+
+/// The TransactionError of a RegistryError returned by the instruction at `index`.
+pub fn registry_error(index: u8, error: RegistryError) -> TransactionError {
+    TransactionError::InstructionError(index, InstructionError::Custom(error as u32))
+}
+
+/// A valid entry for the symbol `token_symbol`.
+pub fn entry_data(token_symbol: &str) -> CreateUpdateEntryInstructionData {
+    CreateUpdateEntryInstructionData {
+        token_symbol: token_symbol.to_string(),
+        token_name: format!("{} Token", token_symbol),
+        token_logo_url: "https://example.com/logo.png".to_string(),
+        token_tags: vec!["stablecoin".to_string()],
+        token_extensions: vec![vec![
+            "website".to_string(),
+            "https://example.com".to_string(),
+        ]],
+    }
+}
+
+/// An account owned by `owner` holding `data`, funded to be rent exempt.
+pub fn program_account(owner: &Pubkey, data: Vec<u8>) -> Account {
+    Account {
+        lamports: 1_000_000_000,
+        data,
+        owner: *owner,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+/// The data of an initialized SPL Token mint.
+pub fn mint_data(mint_authority: Option<Pubkey>) -> Vec<u8> {
+    let mint = spl_token::state::Mint {
+        mint_authority: mint_authority.into(),
+        decimals: 6,
+        is_initialized: true,
+        ..spl_token::state::Mint::default()
+    };
+    let mut data = vec![0; spl_token::state::Mint::LEN];
+    spl_token::state::Mint::pack(mint, &mut data).unwrap();
+    data
+}
+
+/// The data of an initialized SPL Token account.
+pub fn token_account_data(mint: &Pubkey, owner: &Pubkey, amount: u64) -> Vec<u8> {
+    let token_account = spl_token::state::Account {
+        mint: *mint,
+        owner: *owner,
+        amount,
+        state: spl_token::state::AccountState::Initialized,
+        ..spl_token::state::Account::default()
+    };
+    let mut data = vec![0; spl_token::state::Account::LEN];
+    spl_token::state::Account::pack(token_account, &mut data).unwrap();
+    data
+}
+
+/// Sign `instructions` with the context payer and `signers`, and process them in one transaction.
+pub async fn process(
+    context: &mut ProgramTestContext,
+    instructions: &[Instruction],
+    signers: &[&Keypair],
+) -> Result<(), TransactionError> {
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend(
+        signers
+            .iter()
+            .copied()
+            .filter(|signer| signer.pubkey() != context.payer.pubkey()),
+    );
+    let recent_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+    let transaction = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&context.payer.pubkey()),
+        &all_signers,
+        recent_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .map_err(|error| error.unwrap())
+}
+
+/// A registry initialized by the context payer, who is its `fee_update_authority` and the mint
+/// authority and freeze authority of its fee mint.
+pub struct TestRegistry {
+    pub context: ProgramTestContext,
+    pub registry_id: String,
+    pub fee_mint: Pubkey,
+    pub fee_destination: Pubkey,
+}
+
+impl TestRegistry {
+    pub async fn start(fee_amount: u64) -> Self {
+        Self::start_with(program_test(), "", Keypair::new(), fee_amount).await
+    }
+
+    /// Start `program_test` and initialize the registry `registry_id`, with `fee_mint` as the
+    /// address of the fee mint.
+    pub async fn start_with(
+        program_test: ProgramTest,
+        registry_id: &str,
+        fee_mint: Keypair,
+        fee_amount: u64,
+    ) -> Self {
+        let context = program_test.start_with_context().await;
+        let mut test_registry = TestRegistry {
+            context,
+            registry_id: registry_id.to_string(),
+            fee_mint: fee_mint.pubkey(),
+            fee_destination: Pubkey::new_unique(),
+        };
+        let payer = test_registry.payer();
+        test_registry
+            .create_mint_at(&fee_mint, &payer, Some(&payer))
+            .await;
+        let initialize_registry = instruction::initialize_registry(
+            &registry::id(),
+            registry_id,
+            &payer,
+            &test_registry.fee_mint,
+            &test_registry.fee_destination,
+            fee_amount,
+        );
+        test_registry
+            .process(&[initialize_registry], &[])
+            .await
+            .unwrap();
+        test_registry
+    }
+
+    pub fn payer(&self) -> Pubkey {
+        self.context.payer.pubkey()
+    }
+
+    pub async fn process(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<(), TransactionError> {
+        process(&mut self.context, instructions, signers).await
+    }
+
+    pub async fn get_account(&mut self, address: &Pubkey) -> Option<Account> {
+        self.context
+            .banks_client
+            .get_account(*address)
+            .await
+            .unwrap()
+    }
+
+    pub async fn meta(&mut self) -> RegistryMetaAccount {
+        let address = instruction::get_registry_meta_address(&registry::id(), &self.registry_id);
+        let account = self.get_account(&address).await.unwrap();
+        RegistryMetaAccount::unpack_account_data(&account.data).unwrap()
+    }
+
+    pub async fn node(&mut self, mint: &Pubkey) -> RegistryNodeAccount {
+        let address =
+            instruction::get_registry_node_address(&registry::id(), &self.registry_id, mint);
+        let account = self.get_account(&address).await.unwrap();
+        RegistryNodeAccount::unpack_account_data(&account.data).unwrap()
+    }
+
+    /// The first node of the linked list, which a CreateEntry inserts before.
+    pub async fn registry_first(&mut self) -> Pubkey {
+        let address = instruction::get_registry_head_address(&registry::id(), &self.registry_id);
+        let account = self.get_account(&address).await.unwrap();
+        let registry_head = RegistryNodeAccount::unpack_account_data(&account.data).unwrap();
+        Pubkey::new_from_array(registry_head.next_registry_node)
+    }
+
+    /// The mints of the entries of the linked list, from head to tail.
+    pub async fn mints(&mut self) -> Vec<Pubkey> {
+        let tail = instruction::get_registry_tail_address(&registry::id(), &self.registry_id);
+        let mut mints = vec![];
+        let mut address = self.registry_first().await;
+        while address != tail {
+            let account = self.get_account(&address).await.unwrap();
+            let registry_node = RegistryNodeAccount::unpack_account_data(&account.data).unwrap();
+            mints.push(Pubkey::new_from_array(registry_node.token_mint));
+            address = Pubkey::new_from_array(registry_node.next_registry_node);
+        }
+        mints
+    }
+
+    pub async fn token_balance(&mut self, owner: &Pubkey) -> u64 {
+        let address =
+            spl_associated_token_account::get_associated_token_address(owner, &self.fee_mint);
+        let account = self.get_account(&address).await.unwrap();
+        spl_token::state::Account::unpack(&account.data)
+            .unwrap()
+            .amount
+    }
+
+    /// A new system account funded with `USER_LAMPORTS`.
+    pub async fn new_user(&mut self) -> Keypair {
+        let user = Keypair::new();
+        let transfer = system_instruction::transfer(&self.payer(), &user.pubkey(), USER_LAMPORTS);
+        self.process(&[transfer], &[]).await.unwrap();
+        user
+    }
+
+    /// A new user holding `USER_FEE_TOKENS` of the fee mint in their ATA.
+    pub async fn new_fee_payer(&mut self) -> Keypair {
+        let user = self.new_user().await;
+        let payer = self.payer();
+        let ata = spl_associated_token_account::get_associated_token_address(
+            &user.pubkey(),
+            &self.fee_mint,
+        );
+        let instructions = [
+            spl_associated_token_account::instruction::create_associated_token_account(
+                &payer,
+                &user.pubkey(),
+                &self.fee_mint,
+            ),
+            spl_token::instruction::mint_to(
+                &spl_token::id(),
+                &self.fee_mint,
+                &ata,
+                &payer,
+                &[],
+                USER_FEE_TOKENS,
+            )
+            .unwrap(),
+        ];
+        self.process(&instructions, &[]).await.unwrap();
+        user
+    }
+
+    /// Create a mint at the address of `mint` with `mint_authority`.
+    pub async fn create_mint_at(
+        &mut self,
+        mint: &Keypair,
+        mint_authority: &Pubkey,
+        freeze_authority: Option<&Pubkey>,
+    ) {
+        let payer = self.payer();
+        let rent = self.context.banks_client.get_rent().await.unwrap();
+        let instructions = [
+            system_instruction::create_account(
+                &payer,
+                &mint.pubkey(),
+                rent.minimum_balance(spl_token::state::Mint::LEN),
+                spl_token::state::Mint::LEN as u64,
+                &spl_token::id(),
+            ),
+            spl_token::instruction::initialize_mint(
+                &spl_token::id(),
+                &mint.pubkey(),
+                mint_authority,
+                freeze_authority,
+                6,
+            )
+            .unwrap(),
+        ];
+        self.process(&instructions, &[mint]).await.unwrap();
+    }
+
+    /// A new mint whose mint authority is the context payer.
+    pub async fn create_mint(&mut self) -> Pubkey {
+        let mint = Keypair::new();
+        let payer = self.payer();
+        self.create_mint_at(&mint, &payer, None).await;
+        mint.pubkey()
+    }
+
+    /// A new SPL Token multisig of `signers`, requiring `m` of them.
+    pub async fn create_multisig(&mut self, signers: &[&Pubkey], m: u8) -> Pubkey {
+        let multisig = Keypair::new();
+        let payer = self.payer();
+        let rent = self.context.banks_client.get_rent().await.unwrap();
+        let instructions = [
+            system_instruction::create_account(
+                &payer,
+                &multisig.pubkey(),
+                rent.minimum_balance(spl_token::state::Multisig::LEN),
+                spl_token::state::Multisig::LEN as u64,
+                &spl_token::id(),
+            ),
+            spl_token::instruction::initialize_multisig(
+                &spl_token::id(),
+                &multisig.pubkey(),
+                signers,
+                m,
+            )
+            .unwrap(),
+        ];
+        self.process(&instructions, &[&multisig]).await.unwrap();
+        multisig.pubkey()
+    }
+
+    /// A CreateEntry instruction for `mint`, paid for by `payer`.
+    pub async fn create_entry_instruction(
+        &mut self,
+        payer: &Pubkey,
+        mint: &Pubkey,
+        mint_authority: &Pubkey,
+        data: CreateUpdateEntryInstructionData,
+    ) -> Instruction {
+        let registry_meta = self.meta().await;
+        let registry_first = self.registry_first().await;
+        instruction::create_entry(
+            &registry::id(),
+            payer,
+            mint,
+            mint_authority,
+            &[],
+            &registry_meta,
+            &registry_first,
+            data,
+        )
+    }
+
+    /// Register a new mint with the symbol `token_symbol`, with the context payer as its mint
+    /// authority and `token_update_authority`.
+    pub async fn register(&mut self, token_symbol: &str) -> Pubkey {
+        let mint = self.create_mint().await;
+        let payer = self.payer();
+        let create_entry = self
+            .create_entry_instruction(&payer, &mint, &payer, entry_data(token_symbol))
+            .await;
+        self.process(&[create_entry], &[]).await.unwrap();
+        mint
+    }
+
+    /// Register `mint`, paid for by `user`, who becomes its `token_update_authority`.
+    pub async fn register_as(&mut self, user: &Keypair, mint: &Pubkey, token_symbol: &str) {
+        let payer = self.payer();
+        let create_entry = self
+            .create_entry_instruction(&user.pubkey(), mint, &payer, entry_data(token_symbol))
+            .await;
+        self.process(&[create_entry], &[user]).await.unwrap();
+    }
+}
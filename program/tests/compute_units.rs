@@ -0,0 +1,291 @@
+//! Benchmarks of the compute units that the stored bump seeds save in every instruction.
+//!
+//! The units are read from the "consumed" line that the runtime logs for the BPF build of the
+//! registry, so they are only measured under `cargo test-bpf`: natively run programs are not
+//! metered, and the benchmark is ignored by `cargo test`. Measure the instructions with every PDA
+//! re-derived by `find_program_address` first, then with the stored bump seeds, which prints both
+//! and checks that every instruction got cheaper:
+//!
+//!     cargo test-bpf --features find-bump-seeds --test compute_units -- --ignored
+//!     cargo test-bpf --test compute_units -- --ignored --nocapture
+mod common;
+
+use common::{entry_data, TestRegistry};
+use registry::{
+    instruction::{self, BatchEntry},
+    state::CreateUpdateEntryInstructionData,
+};
+use solana_sdk::{
+    instruction::Instruction,
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+/// The file that the `find-bump-seeds` run leaves its measurements in, for the next run.
+fn find_bump_seeds_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("compute_units_find_bump_seeds.txt")
+}
+
+/// The compute units consumed by the registry in the log of a transaction, if it was metered.
+fn registry_units(logs: &[String]) -> Option<u64> {
+    let prefix = format!("Program {} consumed ", registry::id());
+    logs.iter()
+        .find_map(|log| log.strip_prefix(&prefix)?.split(' ').next()?.parse().ok())
+}
+
+/// The instructions that the benchmark measures, with the units each consumed.
+struct Benchmark {
+    test_registry: TestRegistry,
+    units: Vec<(&'static str, Option<u64>)>,
+}
+
+impl Benchmark {
+    /// Process `instruction`, recording the units it consumes as the cost of `name`.
+    async fn measure(
+        &mut self,
+        name: &'static str,
+        instruction: Instruction,
+        signers: &[&Keypair],
+    ) {
+        let context = &mut self.test_registry.context;
+        let mut all_signers = vec![&context.payer];
+        all_signers.extend(signers);
+        let recent_blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &all_signers,
+            recent_blockhash,
+        );
+        let simulation = context
+            .banks_client
+            .simulate_transaction(transaction.clone())
+            .await
+            .unwrap();
+        let units = simulation
+            .simulation_details
+            .and_then(|details| registry_units(&details.logs));
+        context
+            .banks_client
+            .process_transaction(transaction)
+            .await
+            .unwrap();
+        self.units.push((name, units));
+    }
+}
+
+#[tokio::test]
+#[ignore = "only metered under cargo test-bpf, see the module docs"]
+async fn stored_bump_seeds_save_compute_units() {
+    let mut benchmark = Benchmark {
+        test_registry: TestRegistry::start(0).await,
+        units: vec![],
+    };
+    let program_id = registry::id();
+    let test_registry = &mut benchmark.test_registry;
+    let payer = test_registry.payer();
+    let fee_mint = test_registry.fee_mint;
+    let fee_destination = test_registry.fee_destination;
+    let user = test_registry.new_user().await;
+    let mint = test_registry.create_mint().await;
+    let batch_mints = [
+        test_registry.create_mint().await,
+        test_registry.create_mint().await,
+    ];
+
+    /* The registry settings, all updated by the fee_update_authority. */
+    let update_fees = instruction::update_fees(
+        &program_id,
+        "",
+        &payer,
+        &[],
+        &payer,
+        &fee_mint,
+        &fee_destination,
+        0,
+    );
+    benchmark.measure("UpdateFees", update_fees, &[]).await;
+    let update_fee_exemptions =
+        instruction::update_fee_exemptions(&program_id, "", &payer, &[], &[payer]);
+    benchmark
+        .measure("UpdateFeeExemptions", update_fee_exemptions, &[])
+        .await;
+    let update_entry_limits = instruction::update_entry_limits(&program_id, "", &payer, &[], 4, 4);
+    benchmark
+        .measure("UpdateEntryLimits", update_entry_limits, &[])
+        .await;
+    let update_symbol_uniqueness =
+        instruction::update_symbol_uniqueness(&program_id, "", &payer, &[], true);
+    benchmark
+        .measure("UpdateSymbolUniqueness", update_symbol_uniqueness, &[])
+        .await;
+    let update_curators = instruction::update_curators(&program_id, "", &payer, &[], &[payer]);
+    benchmark
+        .measure("UpdateCurators", update_curators, &[])
+        .await;
+
+    /* The life of a single entry. */
+    let create_entry = benchmark
+        .test_registry
+        .create_entry_instruction(&payer, &mint, &payer, entry_data("FIRST"))
+        .await;
+    benchmark.measure("CreateEntry", create_entry, &[]).await;
+    let update_entry = instruction::update_entry(
+        &program_id,
+        "",
+        &payer,
+        &[],
+        &payer,
+        &mint,
+        "FIRST",
+        CreateUpdateEntryInstructionData {
+            token_name: "Renamed".to_string(),
+            ..entry_data("FIRST")
+        },
+    );
+    benchmark.measure("UpdateEntry", update_entry, &[]).await;
+    let refresh_mint_info = instruction::refresh_mint_info(&program_id, "", &mint);
+    benchmark
+        .measure("RefreshMintInfo", refresh_mint_info, &[])
+        .await;
+    let verify_entry = instruction::verify_entry(&program_id, "", &payer, &[], &mint);
+    benchmark.measure("VerifyEntry", verify_entry, &[]).await;
+    let unverify_entry = instruction::unverify_entry(&program_id, "", &payer, &[], &mint);
+    benchmark
+        .measure("UnverifyEntry", unverify_entry, &[])
+        .await;
+    let transfer_token_authority = || {
+        instruction::transfer_token_authority(&program_id, "", &payer, &[], &mint, &user.pubkey())
+    };
+    benchmark
+        .measure("TransferTokenAuthority", transfer_token_authority(), &[])
+        .await;
+    let cancel_token_authority_transfer =
+        instruction::cancel_token_authority_transfer(&program_id, "", &payer, &[], &mint);
+    benchmark
+        .measure(
+            "CancelTokenAuthorityTransfer",
+            cancel_token_authority_transfer,
+            &[],
+        )
+        .await;
+    benchmark
+        .test_registry
+        .process(&[transfer_token_authority()], &[])
+        .await
+        .unwrap();
+    let accept_token_authority =
+        instruction::accept_token_authority(&program_id, "", &user.pubkey(), &[], &mint);
+    benchmark
+        .measure("AcceptTokenAuthority", accept_token_authority, &[&user])
+        .await;
+    let reclaim_token_authority =
+        instruction::reclaim_token_authority(&program_id, "", &payer, &[], &mint, &payer);
+    benchmark
+        .measure("ReclaimTokenAuthority", reclaim_token_authority, &[])
+        .await;
+    let delete_entry =
+        || instruction::delete_entry(&program_id, "", &payer, &[], &mint, "FIRST", &payer);
+    benchmark.measure("DeleteEntry", delete_entry(), &[]).await;
+    let restore_entry =
+        instruction::restore_entry(&program_id, "", &payer, &[], &payer, &mint, "FIRST");
+    benchmark.measure("RestoreEntry", restore_entry, &[]).await;
+    let registry_node = benchmark.test_registry.node(&mint).await;
+    let close_entry =
+        instruction::close_entry(&program_id, "", &payer, &[], &mint, &registry_node, &payer);
+    benchmark.measure("CloseEntry", close_entry, &[]).await;
+
+    let registry_meta = benchmark.test_registry.meta().await;
+    let registry_first = benchmark.test_registry.registry_first().await;
+    let entries = batch_mints
+        .iter()
+        .zip(["BATCH1", "BATCH2"])
+        .map(|(mint, token_symbol)| BatchEntry {
+            mint: *mint,
+            mint_authority: payer,
+            signers: vec![],
+            data: entry_data(token_symbol),
+        })
+        .collect();
+    let create_entries = instruction::create_entries(
+        &program_id,
+        &payer,
+        &registry_meta,
+        &registry_first,
+        entries,
+    );
+    benchmark
+        .measure("CreateEntries", create_entries, &[])
+        .await;
+
+    /* Last, as it hands the registry over to the user. */
+    let transfer_fee_authority =
+        || instruction::transfer_fee_authority(&program_id, "", &payer, &[], &user.pubkey());
+    benchmark
+        .measure("TransferFeeAuthority", transfer_fee_authority(), &[])
+        .await;
+    let cancel_fee_authority_transfer =
+        instruction::cancel_fee_authority_transfer(&program_id, "", &payer, &[]);
+    benchmark
+        .measure(
+            "CancelFeeAuthorityTransfer",
+            cancel_fee_authority_transfer,
+            &[],
+        )
+        .await;
+    benchmark
+        .test_registry
+        .process(&[transfer_fee_authority()], &[])
+        .await
+        .unwrap();
+    let accept_fee_authority =
+        instruction::accept_fee_authority(&program_id, "", &user.pubkey(), &[]);
+    benchmark
+        .measure("AcceptFeeAuthority", accept_fee_authority, &[&user])
+        .await;
+
+    let units: Vec<(&str, u64)> = benchmark
+        .units
+        .into_iter()
+        .map(|(name, units)| Some((name, units?)))
+        .collect::<Option<_>>()
+        .expect("Compute units are only metered under cargo test-bpf");
+    let lines: Vec<String> = units
+        .iter()
+        .map(|(name, units)| format!("{} {}", name, units))
+        .collect();
+    if cfg!(feature = "find-bump-seeds") {
+        fs::write(find_bump_seeds_path(), lines.join("\n")).unwrap();
+        println!("{}", lines.join("\n"));
+        return;
+    }
+
+    let find: BTreeMap<String, u64> = fs::read_to_string(find_bump_seeds_path())
+        .expect("Measure with --features find-bump-seeds first")
+        .lines()
+        .map(|line| {
+            let (name, units) = line.split_once(' ').unwrap();
+            (name.to_string(), units.parse().unwrap())
+        })
+        .collect();
+    println!(
+        "{:<32}{:>12}{:>12}{:>12}",
+        "instruction", "find", "stored", "saved"
+    );
+    for (name, stored) in units {
+        let find = find[name];
+        println!(
+            "{:<32}{:>12}{:>12}{:>12}",
+            name,
+            find,
+            stored,
+            find as i64 - stored as i64
+        );
+        assert!(
+            stored < find,
+            "{} costs no less with stored bump seeds",
+            name
+        );
+    }
+}
@@ -0,0 +1,142 @@
+//! Tests of malformed instructions, and of decoding the events logged by the processor.
+
+mod common;
+
+use common::{registry_error, TestRegistry};
+use registry::{
+    error::RegistryError,
+    event::{AuthorityType, RegistryEvent, RegistryEventLog, EVENT_VERSION},
+    instruction,
+};
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+
+#[tokio::test]
+async fn invalid_instruction_data() {
+    let mut test_registry = TestRegistry::start(0).await;
+    let invalid_data: &[&[u8]] = &[
+        /* Empty */
+        &[],
+        /* An unknown legacy instruction */
+        &[200],
+        /* A legacy InitializeRegistry with a truncated fee_amount */
+        &[0, 1, 2],
+        /* An unsupported version of the versioned format */
+        &[0xff, 0],
+        /* A versioned instruction with an unknown variant */
+        &[0xff, 1, 200],
+    ];
+    for data in invalid_data {
+        let instruction = Instruction {
+            program_id: registry::id(),
+            accounts: vec![],
+            data: data.to_vec(),
+        };
+        assert_eq!(
+            test_registry
+                .process(&[instruction], &[])
+                .await
+                .unwrap_err(),
+            registry_error(0, RegistryError::InvalidInstructionData)
+        );
+    }
+}
+
+#[tokio::test]
+async fn legacy_instruction_data() {
+    let mut test_registry = TestRegistry::start(0).await;
+    let payer = test_registry.payer();
+    let (fee_mint, fee_destination) = (test_registry.fee_mint, test_registry.fee_destination);
+    let mut update_fees = instruction::update_fees(
+        &registry::id(),
+        "",
+        &payer,
+        &[],
+        &payer,
+        &fee_mint,
+        &fee_destination,
+        0,
+    );
+    update_fees.data = [&[1][..], &300u64.to_be_bytes()].concat();
+    test_registry.process(&[update_fees], &[]).await.unwrap();
+    assert_eq!(test_registry.meta().await.fee_amount, 300);
+}
+
+#[tokio::test]
+async fn invalid_number_of_accounts() {
+    let mut test_registry = TestRegistry::start(0).await;
+    let payer = test_registry.payer();
+    let mint = test_registry.register("COUNT").await;
+    let instructions = [
+        instruction::update_entry_limits(&registry::id(), "", &payer, &[], 1, 1),
+        instruction::refresh_mint_info(&registry::id(), "", &mint),
+        instruction::delete_entry(&registry::id(), "", &payer, &[], &mint, "COUNT", &payer),
+    ];
+    for mut instruction in instructions {
+        instruction.accounts.pop();
+        assert_eq!(
+            test_registry
+                .process(&[instruction], &[])
+                .await
+                .unwrap_err(),
+            registry_error(0, RegistryError::InvalidNumberOfAccounts)
+        );
+    }
+
+    /* At most MAX_SIGNERS multisig signers may follow the fixed accounts. */
+    let signers: Vec<Pubkey> = (0..=spl_token::instruction::MAX_SIGNERS)
+        .map(|_| Pubkey::new_unique())
+        .collect();
+    let mut delete_entry =
+        instruction::delete_entry(&registry::id(), "", &payer, &[], &mint, "COUNT", &payer);
+    delete_entry.accounts.extend(
+        signers
+            .iter()
+            .map(|signer| solana_sdk::instruction::AccountMeta::new_readonly(*signer, false)),
+    );
+    assert_eq!(
+        test_registry
+            .process(&[delete_entry], &[])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::InvalidNumberOfAccounts)
+    );
+}
+
+#[test]
+fn event_round_trip() {
+    let event_log = RegistryEventLog {
+        registry_meta: Pubkey::new_unique().to_bytes(),
+        event: RegistryEvent::AuthorityTransferred {
+            authority: AuthorityType::Token {
+                mint: Pubkey::new_unique().to_bytes(),
+            },
+            previous_authority: Pubkey::new_unique().to_bytes(),
+            new_authority: Pubkey::new_unique().to_bytes(),
+        },
+    };
+    let data = event_log.pack().unwrap();
+    assert_eq!(data[0], EVENT_VERSION);
+    assert_eq!(RegistryEventLog::unpack(&data), Ok(event_log));
+}
+
+#[test]
+fn invalid_event_data() {
+    let event_log = RegistryEventLog {
+        registry_meta: [0; 32],
+        event: RegistryEvent::SymbolUniquenessUpdated {
+            unique_symbols: true,
+        },
+    };
+    let mut data = event_log.pack().unwrap();
+    for invalid_data in [&[][..], &data[1..], &data[..data.len() - 1]] {
+        assert_eq!(
+            RegistryEventLog::unpack(invalid_data),
+            Err(RegistryError::InvalidEventData)
+        );
+    }
+    data[0] = EVENT_VERSION + 1;
+    assert_eq!(
+        RegistryEventLog::unpack(&data),
+        Err(RegistryError::InvalidEventData)
+    );
+}
@@ -0,0 +1,254 @@
+//! Tests of MigrateAccount, on registry accounts stored in earlier layouts.
+
+mod common;
+
+use borsh::BorshSerialize;
+use common::{program_account, program_test, registry_error, TestRegistry};
+use registry::{
+    error::RegistryError,
+    instruction,
+    state::{
+        AccountHeader, EntryState, RegistryMetaAccount, RegistryMetaAccountV0, RegistryNodeAccount,
+        RegistryNodeAccountV0, RegistrySeeds, DEFAULT_MAX_EXTENSIONS, DEFAULT_MAX_TAGS,
+        MAX_CURATORS, MAX_FEE_EXEMPT_ACCOUNTS, REGISTRY_ACCOUNT_VERSION,
+        REGISTRY_META_DISCRIMINATOR, REGISTRY_NODE_DISCRIMINATOR,
+    },
+};
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+};
+
+fn node_v0_data(registry_node: &RegistryNodeAccountV0) -> Vec<u8> {
+    let node_data = registry_node.try_to_vec().unwrap();
+    let mut data = (node_data.len() as u32).to_be_bytes().to_vec();
+    data.extend(node_data);
+    data
+}
+
+#[tokio::test]
+async fn migrate_registry_meta() {
+    let fee_update_authority = Pubkey::new_unique();
+    let registry_meta_v0 = RegistryMetaAccountV0 {
+        fee_amount: 7,
+        fee_update_authority: fee_update_authority.to_bytes(),
+        ..RegistryMetaAccountV0::default()
+    };
+    let registry_meta_address = instruction::get_registry_meta_address(&registry::id(), "");
+    let mut program_test = program_test();
+    program_test.add_account(
+        registry_meta_address,
+        program_account(&registry::id(), registry_meta_v0.try_to_vec().unwrap()),
+    );
+    let mut context = program_test.start_with_context().await;
+    let payer = context.payer.pubkey();
+
+    let migrate_account =
+        instruction::migrate_account(&registry::id(), "", &payer, &registry_meta_address);
+    common::process(&mut context, &[migrate_account], &[])
+        .await
+        .unwrap();
+    let account = context
+        .banks_client
+        .get_account(registry_meta_address)
+        .await
+        .unwrap()
+        .unwrap();
+    let header = AccountHeader::unpack(&account.data, &REGISTRY_META_DISCRIMINATOR).unwrap();
+    assert_eq!(header.version, REGISTRY_ACCOUNT_VERSION);
+    let registry_meta = RegistryMetaAccount::unpack_account_data(&account.data).unwrap();
+    assert_eq!(registry_meta.fee_amount, 7);
+    assert_eq!(
+        registry_meta.fee_update_authority,
+        fee_update_authority.to_bytes()
+    );
+    assert_eq!(registry_meta.max_tags, DEFAULT_MAX_TAGS);
+    assert_eq!(registry_meta.max_extensions, DEFAULT_MAX_EXTENSIONS);
+}
+
+#[tokio::test]
+async fn migrate_registry_node() {
+    let mint = Pubkey::new_unique();
+    let registry_node_v0 = RegistryNodeAccountV0 {
+        token_mint: mint.to_bytes(),
+        token_symbol: "OLD".to_string(),
+        token_name: "Old Token".to_string(),
+        deleted: true,
+        ..RegistryNodeAccountV0::default()
+    };
+    let node_address = instruction::get_registry_node_address(&registry::id(), "", &mint);
+    let mut program_test = program_test();
+    program_test.add_account(
+        node_address,
+        program_account(&registry::id(), node_v0_data(&registry_node_v0)),
+    );
+    let mut test_registry = TestRegistry::start_with(program_test, "", Keypair::new(), 0).await;
+    let payer = test_registry.payer();
+
+    let migrate_account = instruction::migrate_account(&registry::id(), "", &payer, &node_address);
+    test_registry
+        .process(&[migrate_account], &[])
+        .await
+        .unwrap();
+    let account = test_registry.get_account(&node_address).await.unwrap();
+    let header = AccountHeader::unpack(&account.data, &REGISTRY_NODE_DISCRIMINATOR).unwrap();
+    assert_eq!(header.version, REGISTRY_ACCOUNT_VERSION);
+    let registry_node = RegistryNodeAccount::unpack_account_data(&account.data).unwrap();
+    assert_eq!(registry_node.token_mint, mint.to_bytes());
+    assert_eq!(registry_node.token_symbol, "OLD");
+    assert_eq!(registry_node.token_name, "Old Token");
+    assert_eq!(registry_node.state, EntryState::Deleted);
+
+    /* Migrating twice is an error, rather than a silent no-op. */
+    let user = test_registry.new_user().await;
+    let migrate_account =
+        instruction::migrate_account(&registry::id(), "", &user.pubkey(), &node_address);
+    assert_eq!(
+        test_registry
+            .process(&[migrate_account], &[&user])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::AccountAlreadyMigrated)
+    );
+
+    /* So is migrating accounts of a later version. */
+    let head_address = instruction::get_registry_head_address(&registry::id(), "");
+    let mut head_account = test_registry.get_account(&head_address).await.unwrap();
+    head_account.data[8] = REGISTRY_ACCOUNT_VERSION + 1;
+    test_registry
+        .context
+        .set_account(&head_address, &head_account.into());
+    let migrate_account = instruction::migrate_account(&registry::id(), "", &payer, &head_address);
+    assert_eq!(
+        test_registry
+            .process(&[migrate_account], &[])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::InvalidAccountVersion)
+    );
+}
+
+/// The data of accounts of the default registry stored by earlier versions of the program, byte
+/// for byte, with the version of their layout. The meta has a `fee_amount` of 500 and the addresses
+/// [1; 32] (head), [2; 32] (fee mint), [3; 32] (fee destination) and [4; 32]
+/// (`fee_update_authority`). The node is a Deleted "OLD" entry with the addresses [5; 32] (next),
+/// [6; 32] (prev), [7; 32] (mint) and [8; 32] (`token_update_authority`).
+const REGISTRY_META_FIXTURES: &[(u8, &[u8])] = &[
+    (0, include_bytes!("fixtures/registry_meta_v0.bin")),
+    (1, include_bytes!("fixtures/registry_meta_v1.bin")),
+    (2, include_bytes!("fixtures/registry_meta_v2.bin")),
+    (3, include_bytes!("fixtures/registry_meta_v3.bin")),
+    (4, include_bytes!("fixtures/registry_meta_v4.bin")),
+];
+const REGISTRY_NODE_FIXTURES: &[(u8, &[u8])] = &[
+    (0, include_bytes!("fixtures/registry_node_v0.bin")),
+    (1, include_bytes!("fixtures/registry_node_v1.bin")),
+    (2, include_bytes!("fixtures/registry_node_v2.bin")),
+    (3, include_bytes!("fixtures/registry_node_v3.bin")),
+    (4, include_bytes!("fixtures/registry_node_v4.bin")),
+];
+
+/// Store `data` at `address`, and migrate it to the current layout.
+async fn migrate_fixture(address: Pubkey, data: &[u8]) -> Vec<u8> {
+    let mut program_test = program_test();
+    program_test.add_account(address, program_account(&registry::id(), data.to_vec()));
+    let mut context = program_test.start_with_context().await;
+    let payer = context.payer.pubkey();
+    let migrate_account = instruction::migrate_account(&registry::id(), "", &payer, &address);
+    common::process(&mut context, &[migrate_account], &[])
+        .await
+        .unwrap();
+    let account = context.banks_client.get_account(address).await.unwrap();
+    account.unwrap().data
+}
+
+#[tokio::test]
+async fn migrate_fixtures() {
+    let registry_meta_address = instruction::get_registry_meta_address(&registry::id(), "");
+    for (version, data) in REGISTRY_META_FIXTURES {
+        let data = migrate_fixture(registry_meta_address, data).await;
+        let registry_meta = RegistryMetaAccount::unpack_account_data(&data)
+            .unwrap_or_else(|_| panic!("meta of version {} did not migrate", version));
+        assert_eq!(registry_meta.head_registry_node, [1; 32]);
+        assert_eq!(registry_meta.fee_amount, 500);
+        assert_eq!(registry_meta.fee_mint, [2; 32]);
+        assert_eq!(registry_meta.fee_destination, [3; 32]);
+        assert_eq!(registry_meta.fee_update_authority, [4; 32]);
+        assert_eq!(
+            registry_meta.fee_exempt_accounts,
+            [[0; 32]; MAX_FEE_EXEMPT_ACCOUNTS]
+        );
+        assert_eq!(registry_meta.max_tags, DEFAULT_MAX_TAGS);
+        assert_eq!(registry_meta.max_extensions, DEFAULT_MAX_EXTENSIONS);
+        assert_eq!(registry_meta.registry_id, "");
+        assert!(!registry_meta.unique_symbols);
+        assert_eq!(registry_meta.curators, [[0; 32]; MAX_CURATORS]);
+        assert_eq!(registry_meta.pending_fee_update_authority, [0; 32]);
+    }
+
+    let mint = Pubkey::new_from_array([7; 32]);
+    let node_address = instruction::get_registry_node_address(&registry::id(), "", &mint);
+    for (version, data) in REGISTRY_NODE_FIXTURES {
+        let data = migrate_fixture(node_address, data).await;
+        let registry_node = RegistryNodeAccount::unpack_account_data(&data)
+            .unwrap_or_else(|_| panic!("node of version {} did not migrate", version));
+        assert_eq!(registry_node.next_registry_node, [5; 32]);
+        assert_eq!(registry_node.prev_registry_node, [6; 32]);
+        assert_eq!(registry_node.token_mint, mint.to_bytes());
+        assert_eq!(registry_node.token_update_authority, [8; 32]);
+        assert_eq!(registry_node.state, EntryState::Deleted);
+        assert_eq!(registry_node.token_symbol, "OLD");
+        assert_eq!(registry_node.token_name, "Old Token");
+        assert_eq!(registry_node.token_logo_url, "https://example.com/old.png");
+        assert_eq!(registry_node.token_tags, vec!["defi".to_string()]);
+        assert_eq!(
+            registry_node.token_extensions,
+            vec![vec![
+                "website".to_string(),
+                "https://example.com".to_string()
+            ]]
+        );
+        assert!(!registry_node.verified);
+        assert_eq!(registry_node.verifier, [0; 32]);
+        assert_eq!(registry_node.verified_at, 0);
+        assert_eq!(registry_node.pending_token_update_authority, [0; 32]);
+    }
+}
+
+#[tokio::test]
+async fn migrate_node_of_named_registry() {
+    /* The version 3 fixture, moved to the PDA of its mint in the "named" registry. */
+    let mint = [7; 32];
+    let (node_address, bump_seed) =
+        Pubkey::find_program_address(&RegistrySeeds::new("named").node(&mint), &registry::id());
+    let mut data = include_bytes!("fixtures/registry_node_v3.bin").to_vec();
+    data[9] = bump_seed;
+    let mut program_test = program_test();
+    program_test.add_account(node_address, program_account(&registry::id(), data));
+    let mut test_registry =
+        TestRegistry::start_with(program_test, "named", Keypair::new(), 0).await;
+    let payer = test_registry.payer();
+
+    /* Without its meta, the node is taken for one of the default registry. */
+    let migrate_account = instruction::migrate_account(&registry::id(), "", &payer, &node_address);
+    assert_eq!(
+        test_registry
+            .process(&[migrate_account], &[])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::InvalidProgramDerivedAccount)
+    );
+
+    let migrate_account =
+        instruction::migrate_account(&registry::id(), "named", &payer, &node_address);
+    test_registry
+        .process(&[migrate_account], &[])
+        .await
+        .unwrap();
+    let account = test_registry.get_account(&node_address).await.unwrap();
+    let header = AccountHeader::unpack(&account.data, &REGISTRY_NODE_DISCRIMINATOR).unwrap();
+    assert_eq!(header.bump_seed, bump_seed);
+    let registry_node = RegistryNodeAccount::unpack_account_data(&account.data).unwrap();
+    assert_eq!(registry_node.token_mint, mint);
+    assert_eq!(registry_node.token_symbol, "OLD");
+}
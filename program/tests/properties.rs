@@ -0,0 +1,217 @@
+//! Property tests of instruction decoding and of the serialization of RegistryNodeAccounts. The
+//! fuzz targets in `fuzz/` exercise the same functions with coverage guidance.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use proptest::{collection::vec, prelude::*};
+use registry::{
+    error::RegistryError,
+    instruction::RegistryInstruction,
+    state::{
+        AccountHeader, CreateUpdateEntryInstructionData, EntryState, RegistryNodeAccount,
+        REGISTRY_ACCOUNT_VERSION, REGISTRY_NODE_DISCRIMINATOR,
+    },
+};
+
+fn entry_data() -> impl Strategy<Value = CreateUpdateEntryInstructionData> {
+    (
+        any::<String>(),
+        any::<String>(),
+        any::<String>(),
+        vec(any::<String>(), 0..10),
+        vec(vec(any::<String>(), 0..3), 0..10),
+    )
+        .prop_map(
+            |(token_symbol, token_name, token_logo_url, token_tags, token_extensions)| {
+                CreateUpdateEntryInstructionData {
+                    token_symbol,
+                    token_name,
+                    token_logo_url,
+                    token_tags,
+                    token_extensions,
+                }
+            },
+        )
+}
+
+fn entry_state() -> impl Strategy<Value = EntryState> {
+    prop_oneof![
+        Just(EntryState::Active),
+        Just(EntryState::Deleted),
+        Just(EntryState::Closed),
+    ]
+}
+
+fn registry_node() -> impl Strategy<Value = RegistryNodeAccount> {
+    (
+        (
+            any::<[u8; 32]>(),
+            any::<[u8; 32]>(),
+            any::<[u8; 32]>(),
+            any::<[u8; 32]>(),
+            entry_state(),
+            any::<[u8; 32]>(),
+        ),
+        (
+            any::<u8>(),
+            any::<u64>(),
+            any::<[u8; 32]>(),
+            any::<[u8; 32]>(),
+            any::<u64>(),
+        ),
+        (any::<bool>(), any::<[u8; 32]>(), any::<i64>()),
+        entry_data(),
+    )
+        .prop_map(|(links, mint_info, verification, data)| {
+            let (
+                next_registry_node,
+                prev_registry_node,
+                token_mint,
+                token_update_authority,
+                state,
+                pending_token_update_authority,
+            ) = links;
+            let (
+                mint_decimals,
+                mint_supply,
+                mint_authority,
+                mint_freeze_authority,
+                mint_snapshot_slot,
+            ) = mint_info;
+            let (verified, verifier, verified_at) = verification;
+            RegistryNodeAccount {
+                next_registry_node,
+                prev_registry_node,
+                token_mint,
+                token_update_authority,
+                state,
+                pending_token_update_authority,
+                mint_decimals,
+                mint_supply,
+                mint_authority,
+                mint_freeze_authority,
+                mint_snapshot_slot,
+                verified,
+                verifier,
+                verified_at,
+                token_symbol: data.token_symbol,
+                token_name: data.token_name,
+                token_logo_url: data.token_logo_url,
+                token_tags: data.token_tags,
+                token_extensions: data.token_extensions,
+            }
+        })
+}
+
+/// Instruction data that is mostly arbitrary, but often starts like a valid instruction so that
+/// decoding gets past the first byte.
+fn instruction_bytes() -> impl Strategy<Value = Vec<u8>> {
+    prop_oneof![
+        vec(any::<u8>(), 0..64),
+        (0u8..=9, vec(any::<u8>(), 0..64)).prop_map(|(tag, rest)| [vec![tag], rest].concat()),
+        vec(any::<u8>(), 0..64).prop_map(|rest| [vec![0xff, 1], rest].concat()),
+    ]
+}
+
+/// Account data that is mostly arbitrary, but often starts with a valid node header.
+fn node_account_bytes() -> impl Strategy<Value = Vec<u8>> {
+    prop_oneof![
+        vec(any::<u8>(), 0..256),
+        (any::<u8>(), any::<u32>(), vec(any::<u8>(), 0..256)).prop_map(
+            |(bump_seed, data_length, rest)| {
+                let header = AccountHeader {
+                    discriminator: REGISTRY_NODE_DISCRIMINATOR,
+                    version: REGISTRY_ACCOUNT_VERSION,
+                    bump_seed,
+                    data_length: data_length % 512,
+                };
+                [header.try_to_vec().unwrap(), rest].concat()
+            }
+        ),
+    ]
+}
+
+fn create_entry(data: CreateUpdateEntryInstructionData) -> RegistryInstruction {
+    RegistryInstruction::CreateEntry {
+        token_symbol: data.token_symbol,
+        token_name: data.token_name,
+        token_logo_url: data.token_logo_url,
+        token_tags: data.token_tags,
+        token_extensions: data.token_extensions,
+    }
+}
+
+proptest! {
+    #[test]
+    fn entry_data_round_trip(data in entry_data()) {
+        let serialized = data.try_to_vec().unwrap();
+        prop_assert_eq!(
+            CreateUpdateEntryInstructionData::try_from_slice(&serialized).unwrap(),
+            data
+        );
+    }
+
+    #[test]
+    fn create_entry_round_trip(data in entry_data()) {
+        /* The versioned and the legacy wire format decode to the same instruction. */
+        let legacy_input = [vec![2], data.try_to_vec().unwrap()].concat();
+        let instruction = create_entry(data);
+        prop_assert_eq!(
+            RegistryInstruction::unpack(&instruction.pack()),
+            Ok(instruction.clone())
+        );
+        prop_assert_eq!(RegistryInstruction::unpack(&legacy_input), Ok(instruction));
+    }
+
+    #[test]
+    fn registry_node_round_trip(registry_node in registry_node(), bump_seed in any::<u8>()) {
+        let data = registry_node.pack_account_data(bump_seed).unwrap();
+        let header = AccountHeader::unpack(&data, &REGISTRY_NODE_DISCRIMINATOR).unwrap();
+        prop_assert_eq!(header.bump_seed, bump_seed);
+        prop_assert_eq!(
+            RegistryNodeAccount::unpack_account_data(&data).unwrap(),
+            registry_node.clone()
+        );
+
+        /* The fixed-offset accessors agree with the deserialized node. */
+        prop_assert_eq!(
+            RegistryNodeAccount::unpack_token_mint(&data).unwrap(),
+            &registry_node.token_mint
+        );
+        prop_assert_eq!(
+            RegistryNodeAccount::unpack_token_update_authority(&data).unwrap(),
+            &registry_node.token_update_authority
+        );
+        prop_assert_eq!(RegistryNodeAccount::unpack_state(&data).unwrap(), registry_node.state);
+    }
+
+    #[test]
+    fn registry_node_pack_state(registry_node in registry_node(), state in entry_state()) {
+        let mut data = registry_node.pack_account_data(255).unwrap();
+        RegistryNodeAccount::pack_state(&mut data, state).unwrap();
+        prop_assert_eq!(
+            RegistryNodeAccount::unpack_account_data(&data).unwrap(),
+            RegistryNodeAccount {
+                state,
+                ..registry_node
+            }
+        );
+    }
+
+    #[test]
+    fn unpack_arbitrary_instruction_data(input in instruction_bytes()) {
+        /* Decoding must never panic, and every failure is InvalidInstructionData. */
+        if let Err(error) = RegistryInstruction::unpack(&input) {
+            prop_assert_eq!(error, RegistryError::InvalidInstructionData);
+        }
+    }
+
+    #[test]
+    fn unpack_arbitrary_node_data(mut input in node_account_bytes(), state in entry_state()) {
+        /* Neither deserializing nor the fixed-offset accessors may panic. */
+        let _ = RegistryNodeAccount::unpack_account_data(&input);
+        let _ = RegistryNodeAccount::unpack_token_mint(&input);
+        let _ = RegistryNodeAccount::unpack_token_update_authority(&input);
+        let _ = RegistryNodeAccount::unpack_state(&input);
+        let _ = RegistryNodeAccount::pack_state(&mut input, state);
+    }
+}
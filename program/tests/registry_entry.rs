@@ -0,0 +1,1548 @@
+//! Tests of the instructions that create, update and remove entries, and of their lifecycle.
+
+mod common;
+
+use common::{
+    entry_data, mint_data, program_account, program_test, registry_error, token_account_data,
+    TestRegistry, USER_FEE_TOKENS,
+};
+use registry::{
+    error::RegistryError,
+    instruction,
+    state::{
+        AccountHeader, CreateUpdateEntryInstructionData, EntryState, RegistryMetaAccount,
+        MAX_EXTENSION_FIELD_LENGTH, MAX_LOGO_URL_LENGTH, MAX_NAME_LENGTH, MAX_SYMBOL_LENGTH,
+        MAX_TAG_LENGTH, REGISTRY_ACCOUNT_VERSION, REGISTRY_NODE_DISCRIMINATOR,
+    },
+};
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    system_instruction, system_program,
+};
+
+/// A change to valid entry data, with the error it causes.
+type InvalidCase = (fn(&mut CreateUpdateEntryInstructionData), RegistryError);
+
+#[tokio::test]
+async fn create_entry() {
+    let mut test_registry = TestRegistry::start(0).await;
+    let payer = test_registry.payer();
+    let first = test_registry.register("FIRST").await;
+    let second = test_registry.register("SECOND").await;
+
+    /* New entries are inserted at the front of the list. */
+    assert_eq!(test_registry.mints().await, vec![second, first]);
+    let registry_node = test_registry.node(&first).await;
+    assert_eq!(registry_node.token_mint, first.to_bytes());
+    assert_eq!(registry_node.token_update_authority, payer.to_bytes());
+    assert_eq!(registry_node.state, EntryState::Active);
+    assert_eq!(registry_node.token_symbol, "FIRST");
+    assert_eq!(registry_node.token_name, "FIRST Token");
+    assert_eq!(registry_node.mint_decimals, 6);
+    assert_eq!(registry_node.mint_authority, payer.to_bytes());
+    assert!(!registry_node.verified);
+
+    let create_entry = test_registry
+        .create_entry_instruction(&payer, &first, &payer, entry_data("AGAIN"))
+        .await;
+    assert_eq!(
+        test_registry
+            .process(&[create_entry], &[])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::PreviouslyRegisteredMint)
+    );
+}
+
+#[tokio::test]
+async fn create_entry_fees() {
+    let mut test_registry = TestRegistry::start(100).await;
+    let fee_destination = test_registry.fee_destination;
+
+    /* The fee update authority registers for free. */
+    test_registry.register("FREE").await;
+    assert_eq!(test_registry.token_balance(&fee_destination).await, 0);
+
+    let user = test_registry.new_fee_payer().await;
+    let mint = test_registry.create_mint().await;
+    test_registry.register_as(&user, &mint, "PAID").await;
+    assert_eq!(test_registry.token_balance(&fee_destination).await, 100);
+    assert_eq!(
+        test_registry.token_balance(&user.pubkey()).await,
+        USER_FEE_TOKENS - 100
+    );
+    assert_eq!(
+        test_registry.node(&mint).await.token_update_authority,
+        user.pubkey().to_bytes()
+    );
+
+    /* Exempt accounts register for free. */
+    let payer = test_registry.payer();
+    let update_fee_exemptions =
+        instruction::update_fee_exemptions(&registry::id(), "", &payer, &[], &[user.pubkey()]);
+    test_registry
+        .process(&[update_fee_exemptions], &[])
+        .await
+        .unwrap();
+    let mint = test_registry.create_mint().await;
+    test_registry.register_as(&user, &mint, "EXEMPT").await;
+    assert_eq!(test_registry.token_balance(&fee_destination).await, 100);
+}
+
+#[tokio::test]
+async fn create_entry_fee_errors() {
+    let mut test_registry = TestRegistry::start(USER_FEE_TOKENS + 1).await;
+    let payer = test_registry.payer();
+    let fee_mint = test_registry.fee_mint;
+    let mint = test_registry.create_mint().await;
+
+    let user = test_registry.new_user().await;
+    let create_entry = test_registry
+        .create_entry_instruction(&user.pubkey(), &mint, &payer, entry_data("NOATA"))
+        .await;
+    assert_eq!(
+        test_registry
+            .process(&[create_entry], &[&user])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::UninitializedAssociatedTokenAccount)
+    );
+
+    let user = test_registry.new_fee_payer().await;
+    let create_entry = test_registry
+        .create_entry_instruction(&user.pubkey(), &mint, &payer, entry_data("POOR"))
+        .await;
+    assert_eq!(
+        test_registry
+            .process(&[create_entry], &[&user])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::InsufficientFeeBalance)
+    );
+
+    let user_ata =
+        spl_associated_token_account::get_associated_token_address(&user.pubkey(), &fee_mint);
+    let freeze_account =
+        spl_token::instruction::freeze_account(&spl_token::id(), &user_ata, &fee_mint, &payer, &[])
+            .unwrap();
+    test_registry.process(&[freeze_account], &[]).await.unwrap();
+    let create_entry = test_registry
+        .create_entry_instruction(&user.pubkey(), &mint, &payer, entry_data("FROZEN"))
+        .await;
+    assert_eq!(
+        test_registry
+            .process(&[create_entry], &[&user])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::FrozenFeeSourceAccount)
+    );
+
+    let mut create_entry = test_registry
+        .create_entry_instruction(&payer, &mint, &payer, entry_data("BADDEST"))
+        .await;
+    create_entry.accounts[3].pubkey = user_ata;
+    assert_eq!(
+        test_registry
+            .process(&[create_entry], &[])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::InvalidAssociatedTokenAccount)
+    );
+}
+
+#[tokio::test]
+async fn create_entry_fee_source_of_other_mint() {
+    /* The ATA program only creates ATAs of the right mint, so store a mismatched one directly. */
+    let fee_mint = Keypair::new();
+    let user = Keypair::new();
+    let mut program_test = program_test();
+    program_test.add_account(
+        user.pubkey(),
+        program_account(&solana_sdk::system_program::id(), vec![]),
+    );
+    program_test.add_account(
+        spl_associated_token_account::get_associated_token_address(
+            &user.pubkey(),
+            &fee_mint.pubkey(),
+        ),
+        program_account(
+            &spl_token::id(),
+            token_account_data(&Pubkey::new_unique(), &user.pubkey(), USER_FEE_TOKENS),
+        ),
+    );
+    let mut test_registry = TestRegistry::start_with(program_test, "", fee_mint, 100).await;
+    let payer = test_registry.payer();
+    let mint = test_registry.create_mint().await;
+    let create_entry = test_registry
+        .create_entry_instruction(&user.pubkey(), &mint, &payer, entry_data("OTHER"))
+        .await;
+    assert_eq!(
+        test_registry
+            .process(&[create_entry], &[&user])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::InvalidFeeSourceMint)
+    );
+}
+
+#[tokio::test]
+async fn create_entry_account_errors() {
+    let uninitialized_mint = Pubkey::new_unique();
+    let mut program_test = program_test();
+    program_test.add_account(
+        uninitialized_mint,
+        program_account(&spl_token::id(), vec![]),
+    );
+    let mut test_registry = TestRegistry::start_with(program_test, "", Keypair::new(), 0).await;
+    let payer = test_registry.payer();
+    let first = test_registry.register("FIRST").await;
+    let mint = test_registry.create_mint().await;
+
+    let create_entry = test_registry
+        .create_entry_instruction(&payer, &Pubkey::new_unique(), &payer, entry_data("NONE"))
+        .await;
+    assert_eq!(
+        test_registry
+            .process(&[create_entry], &[])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::InvalidMint)
+    );
+
+    let create_entry = test_registry
+        .create_entry_instruction(&payer, &uninitialized_mint, &payer, entry_data("UNINIT"))
+        .await;
+    assert_eq!(
+        test_registry
+            .process(&[create_entry], &[])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::UninitializedMint)
+    );
+
+    /* New entries must be inserted before the current first entry. */
+    let registry_meta = test_registry.meta().await;
+    let create_entry = instruction::create_entry(
+        &registry::id(),
+        &payer,
+        &mint,
+        &payer,
+        &[],
+        &registry_meta,
+        &instruction::get_registry_tail_address(&registry::id(), ""),
+        entry_data("NOTFIRST"),
+    );
+    assert_eq!(
+        test_registry
+            .process(&[create_entry], &[])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::InvalidRegistryNodeFirst)
+    );
+
+    let mut create_entry = test_registry
+        .create_entry_instruction(&payer, &mint, &payer, entry_data("WRONGPDA"))
+        .await;
+    create_entry.accounts[9].pubkey =
+        instruction::get_registry_node_address(&registry::id(), "", &first);
+    assert_eq!(
+        test_registry
+            .process(&[create_entry], &[])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::InvalidProgramDerivedAccount)
+    );
+
+    let mut create_entry = test_registry
+        .create_entry_instruction(&payer, &mint, &payer, entry_data("SYSTEM"))
+        .await;
+    create_entry.accounts[4].pubkey = spl_token::id();
+    assert_eq!(
+        test_registry
+            .process(&[create_entry], &[])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::InvalidSystemProgram)
+    );
+
+    let mut create_entry = test_registry
+        .create_entry_instruction(&payer, &mint, &payer, entry_data("TOKEN"))
+        .await;
+    create_entry.accounts[5].pubkey = Pubkey::new_unique();
+    assert_eq!(
+        test_registry
+            .process(&[create_entry], &[])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::InvalidTokenProgram)
+    );
+}
+
+#[tokio::test]
+async fn create_entry_mint_authority() {
+    let mut test_registry = TestRegistry::start(0).await;
+    let payer = test_registry.payer();
+    let user = test_registry.new_user().await;
+    let mint = test_registry.create_mint().await;
+
+    let create_entry = test_registry
+        .create_entry_instruction(&user.pubkey(), &mint, &user.pubkey(), entry_data("STOLEN"))
+        .await;
+    assert_eq!(
+        test_registry
+            .process(&[create_entry], &[&user])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::InvalidMintAuthority)
+    );
+
+    /* A mint without a mint authority can only be registered with the fee update authority. */
+    let set_authority = spl_token::instruction::set_authority(
+        &spl_token::id(),
+        &mint,
+        None,
+        spl_token::instruction::AuthorityType::MintTokens,
+        &payer,
+        &[],
+    )
+    .unwrap();
+    test_registry.process(&[set_authority], &[]).await.unwrap();
+    let create_entry = test_registry
+        .create_entry_instruction(&user.pubkey(), &mint, &user.pubkey(), entry_data("REVOKED"))
+        .await;
+    assert_eq!(
+        test_registry
+            .process(&[create_entry], &[&user])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::RevokedMintAuthority)
+    );
+    let create_entry = test_registry
+        .create_entry_instruction(&user.pubkey(), &mint, &payer, entry_data("REVOKED"))
+        .await;
+    test_registry
+        .process(&[create_entry], &[&user])
+        .await
+        .unwrap();
+    assert_eq!(test_registry.node(&mint).await.mint_authority, [0; 32]);
+}
+
+#[tokio::test]
+async fn create_entry_validation() {
+    let mut test_registry = TestRegistry::start(0).await;
+    let payer = test_registry.payer();
+    let mint = test_registry.create_mint().await;
+
+    let cases: Vec<InvalidCase> = vec![
+        (
+            |data| data.token_symbol = "S".repeat(MAX_SYMBOL_LENGTH + 1),
+            RegistryError::SymbolTooLong,
+        ),
+        (
+            |data| data.token_symbol = "US DC".to_string(),
+            RegistryError::InvalidSymbol,
+        ),
+        (
+            |data| data.token_name = "N".repeat(MAX_NAME_LENGTH + 1),
+            RegistryError::NameTooLong,
+        ),
+        (
+            |data| data.token_name = " ".to_string(),
+            RegistryError::InvalidName,
+        ),
+        (
+            |data| data.token_logo_url = "u".repeat(MAX_LOGO_URL_LENGTH + 1),
+            RegistryError::LogoUrlTooLong,
+        ),
+        (
+            |data| data.token_logo_url = "https://example.com/a logo.png".to_string(),
+            RegistryError::InvalidLogoUrl,
+        ),
+        (
+            |data| data.token_tags = (0..9).map(|i| format!("tag{}", i)).collect(),
+            RegistryError::TooManyTags,
+        ),
+        (
+            |data| data.token_tags = vec!["t".repeat(MAX_TAG_LENGTH + 1)],
+            RegistryError::TagTooLong,
+        ),
+        (
+            |data| data.token_tags = vec!["stable coin".to_string()],
+            RegistryError::InvalidTag,
+        ),
+        (
+            |data| {
+                data.token_extensions = (0..17)
+                    .map(|i| vec![format!("key{}", i), "value".to_string()])
+                    .collect()
+            },
+            RegistryError::TooManyExtensions,
+        ),
+        (
+            |data| data.token_extensions = vec![vec!["website".to_string()]],
+            RegistryError::InvalidExtension,
+        ),
+        (
+            |data| {
+                data.token_extensions = vec![vec![
+                    "website".to_string(),
+                    "v".repeat(MAX_EXTENSION_FIELD_LENGTH + 1),
+                ]]
+            },
+            RegistryError::ExtensionTooLong,
+        ),
+        (
+            |data| data.token_extensions = vec![vec!["web site".to_string(), "value".to_string()]],
+            RegistryError::InvalidExtensionKey,
+        ),
+        (
+            |data| data.token_extensions = vec![vec!["website".to_string(), String::new()]],
+            RegistryError::InvalidExtensionValue,
+        ),
+    ];
+    for (modify, error) in cases {
+        let mut data = entry_data("VALID");
+        modify(&mut data);
+        let create_entry = test_registry
+            .create_entry_instruction(&payer, &mint, &payer, data)
+            .await;
+        assert_eq!(
+            test_registry
+                .process(&[create_entry], &[])
+                .await
+                .unwrap_err(),
+            registry_error(0, error)
+        );
+    }
+}
+
+#[tokio::test]
+async fn update_entry() {
+    let mut test_registry = TestRegistry::start(0).await;
+    let payer = test_registry.payer();
+    let mint = test_registry.register("OLD").await;
+
+    let update_entry = instruction::update_entry(
+        &registry::id(),
+        "",
+        &payer,
+        &[],
+        &payer,
+        &mint,
+        "OLD",
+        entry_data("NEW"),
+    );
+    test_registry.process(&[update_entry], &[]).await.unwrap();
+    let registry_node = test_registry.node(&mint).await;
+    assert_eq!(registry_node.token_symbol, "NEW");
+    assert_eq!(registry_node.token_name, "NEW Token");
+
+    let user = test_registry.new_user().await;
+    let update_entry = instruction::update_entry(
+        &registry::id(),
+        "",
+        &user.pubkey(),
+        &[],
+        &user.pubkey(),
+        &mint,
+        "NEW",
+        entry_data("HIJACKED"),
+    );
+    assert_eq!(
+        test_registry
+            .process(&[update_entry], &[&user])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::InvalidTokenUpdateAuthority)
+    );
+
+    let unregistered_mint = test_registry.create_mint().await;
+    let update_entry = instruction::update_entry(
+        &registry::id(),
+        "",
+        &payer,
+        &[],
+        &payer,
+        &unregistered_mint,
+        "NONE",
+        entry_data("NONE"),
+    );
+    assert_eq!(
+        test_registry
+            .process(&[update_entry], &[])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::NotYetRegisteredMint)
+    );
+
+    let mut update_entry = instruction::update_entry(
+        &registry::id(),
+        "",
+        &payer,
+        &[],
+        &payer,
+        &unregistered_mint,
+        "NONE",
+        entry_data("WRONGPDA"),
+    );
+    update_entry.accounts[3].pubkey =
+        instruction::get_registry_node_address(&registry::id(), "", &mint);
+    assert_eq!(
+        test_registry
+            .process(&[update_entry], &[])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::InvalidProgramDerivedAccount)
+    );
+}
+
+#[tokio::test]
+async fn delete_and_restore_entry() {
+    let mut test_registry = TestRegistry::start(0).await;
+    let payer = test_registry.payer();
+    let mint = test_registry.register("GONE").await;
+
+    let delete_entry = |destination: &Pubkey| {
+        instruction::delete_entry(&registry::id(), "", &payer, &[], &mint, "GONE", destination)
+    };
+    test_registry
+        .process(&[delete_entry(&payer)], &[])
+        .await
+        .unwrap();
+    assert_eq!(test_registry.node(&mint).await.state, EntryState::Deleted);
+    /* Deleted entries stay in the list until they are closed. */
+    assert_eq!(test_registry.mints().await, vec![mint]);
+
+    assert_eq!(
+        test_registry
+            .process(&[delete_entry(&Pubkey::new_unique())], &[])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::PreviouslyDeletedMint)
+    );
+    let update_entry = instruction::update_entry(
+        &registry::id(),
+        "",
+        &payer,
+        &[],
+        &payer,
+        &mint,
+        "GONE",
+        entry_data("BACK"),
+    );
+    assert_eq!(
+        test_registry
+            .process(&[update_entry], &[])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::PreviouslyDeletedMint)
+    );
+
+    let restore_entry =
+        instruction::restore_entry(&registry::id(), "", &payer, &[], &payer, &mint, "GONE");
+    test_registry.process(&[restore_entry], &[]).await.unwrap();
+    assert_eq!(test_registry.node(&mint).await.state, EntryState::Active);
+
+    let user = test_registry.new_user().await;
+    let restore_entry = instruction::restore_entry(
+        &registry::id(),
+        "",
+        &user.pubkey(),
+        &[],
+        &user.pubkey(),
+        &mint,
+        "GONE",
+    );
+    assert_eq!(
+        test_registry
+            .process(&[restore_entry], &[&user])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::InvalidTokenUpdateAuthority)
+    );
+    let restore_entry =
+        instruction::restore_entry(&registry::id(), "", &payer, &[], &payer, &mint, "GONE");
+    let update_entry_limits =
+        instruction::update_entry_limits(&registry::id(), "", &payer, &[], 8, 16);
+    assert_eq!(
+        test_registry
+            .process(&[update_entry_limits, restore_entry], &[])
+            .await
+            .unwrap_err(),
+        registry_error(1, RegistryError::PreviouslyRegisteredMint)
+    );
+}
+
+#[tokio::test]
+async fn replace_deleted_entry() {
+    let mut test_registry = TestRegistry::start(0).await;
+    let payer = test_registry.payer();
+    let mint = test_registry.register("GONE").await;
+    let delete_entry =
+        instruction::delete_entry(&registry::id(), "", &payer, &[], &mint, "GONE", &payer);
+    test_registry.process(&[delete_entry], &[]).await.unwrap();
+
+    /* Anyone who can sign for the mint may register a Deleted entry again, in place. */
+    let user = test_registry.new_user().await;
+    test_registry.register_as(&user, &mint, "BACK").await;
+    let registry_node = test_registry.node(&mint).await;
+    assert_eq!(registry_node.state, EntryState::Active);
+    assert_eq!(registry_node.token_symbol, "BACK");
+    assert_eq!(
+        registry_node.token_update_authority,
+        user.pubkey().to_bytes()
+    );
+    assert_eq!(test_registry.mints().await, vec![mint]);
+}
+
+#[tokio::test]
+async fn create_entries() {
+    let mut test_registry = TestRegistry::start(100).await;
+    let payer = test_registry.payer();
+    let fee_destination = test_registry.fee_destination;
+    let old = test_registry.register("OLD").await;
+    let deleted = test_registry.register("DELETED").await;
+    let delete_entry = instruction::delete_entry(
+        &registry::id(),
+        "",
+        &payer,
+        &[],
+        &deleted,
+        "DELETED",
+        &payer,
+    );
+    test_registry.process(&[delete_entry], &[]).await.unwrap();
+    let first = test_registry.create_mint().await;
+    let second = test_registry.create_mint().await;
+    let mint_authority = Pubkey::new_unique();
+    let foreign = Keypair::new();
+    test_registry
+        .create_mint_at(&foreign, &mint_authority, None)
+        .await;
+
+    /* Entries that fail their checks are skipped, and the others are created in order. */
+    let user = test_registry.new_fee_payer().await;
+    let entry = |mint: &Pubkey, token_symbol: &str| instruction::BatchEntry {
+        mint: *mint,
+        mint_authority: payer,
+        signers: vec![],
+        data: entry_data(token_symbol),
+    };
+    let entries = vec![
+        entry(&first, "FIRST"),
+        entry(&foreign.pubkey(), "FOREIGN"),
+        entry(&second, "SECOND"),
+        entry(&old, "AGAIN"),
+        /* Longer than the deleted symbol, so the node grows: native CPIs in tests only balance
+         * the accounts passed to them, which a refund of the shrunk node would upset. */
+        entry(&deleted, "RETURNED"),
+        entry(&first, "TWICE"),
+    ];
+    let registry_meta = test_registry.meta().await;
+    let registry_first = test_registry.registry_first().await;
+    let create_entries = instruction::create_entries(
+        &registry::id(),
+        &user.pubkey(),
+        &registry_meta,
+        &registry_first,
+        entries,
+    );
+    test_registry
+        .process(&[create_entries], &[&user])
+        .await
+        .unwrap();
+
+    /* Deleted entries are replaced in place, rather than moved to the front. */
+    assert_eq!(
+        test_registry.mints().await,
+        vec![first, second, deleted, old]
+    );
+    for (mint, token_symbol) in [(first, "FIRST"), (second, "SECOND"), (deleted, "RETURNED")] {
+        let registry_node = test_registry.node(&mint).await;
+        assert_eq!(registry_node.state, EntryState::Active);
+        assert_eq!(registry_node.token_symbol, token_symbol);
+        assert_eq!(
+            registry_node.token_update_authority,
+            user.pubkey().to_bytes()
+        );
+    }
+    assert_eq!(test_registry.node(&old).await.token_symbol, "OLD");
+    let node_address =
+        instruction::get_registry_node_address(&registry::id(), "", &foreign.pubkey());
+    assert!(test_registry.get_account(&node_address).await.is_none());
+
+    /* The fee is charged once for every created entry. */
+    assert_eq!(test_registry.token_balance(&fee_destination).await, 300);
+    assert_eq!(
+        test_registry.token_balance(&user.pubkey()).await,
+        USER_FEE_TOKENS - 300
+    );
+
+    /* A batch without any entries to create succeeds, and charges nothing. */
+    let registry_first = test_registry.registry_first().await;
+    let create_entries = instruction::create_entries(
+        &registry::id(),
+        &user.pubkey(),
+        &registry_meta,
+        &registry_first,
+        vec![entry(&second, "SECOND")],
+    );
+    test_registry
+        .process(&[create_entries], &[&user])
+        .await
+        .unwrap();
+    assert_eq!(test_registry.token_balance(&fee_destination).await, 300);
+
+    let mut create_entries = instruction::create_entries(
+        &registry::id(),
+        &user.pubkey(),
+        &registry_meta,
+        &registry_first,
+        vec![entry(&foreign.pubkey(), "FOREIGN")],
+    );
+    create_entries.accounts.pop();
+    assert_eq!(
+        test_registry
+            .process(&[create_entries], &[&user])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::InvalidNumberOfAccounts)
+    );
+}
+
+#[tokio::test]
+async fn create_entries_fee_failure() {
+    let mut test_registry = TestRegistry::start(USER_FEE_TOKENS / 2 + 1).await;
+    let payer = test_registry.payer();
+    let fee_mint = test_registry.fee_mint;
+    let fee_destination = test_registry.fee_destination;
+    let mints = [
+        test_registry.create_mint().await,
+        test_registry.create_mint().await,
+    ];
+    let user = test_registry.new_fee_payer().await;
+    let create_entries = |registry_meta: &RegistryMetaAccount, registry_first: &Pubkey| {
+        let entries = mints
+            .iter()
+            .zip(["FIRST", "SECOND"])
+            .map(|(mint, token_symbol)| instruction::BatchEntry {
+                mint: *mint,
+                mint_authority: payer,
+                signers: vec![],
+                data: entry_data(token_symbol),
+            })
+            .collect();
+        instruction::create_entries(
+            &registry::id(),
+            &user.pubkey(),
+            registry_meta,
+            registry_first,
+            entries,
+        )
+    };
+
+    /* The user can pay for either entry, but not both, so neither is created. */
+    let registry_meta = test_registry.meta().await;
+    let registry_first = test_registry.registry_first().await;
+    assert_eq!(
+        test_registry
+            .process(&[create_entries(&registry_meta, &registry_first)], &[&user])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::InsufficientFeeBalance)
+    );
+    assert!(test_registry.mints().await.is_empty());
+    assert_eq!(
+        test_registry.token_balance(&user.pubkey()).await,
+        USER_FEE_TOKENS
+    );
+
+    /* A total fee that overflows fails the batch too. */
+    let update_fees = instruction::update_fees(
+        &registry::id(),
+        "",
+        &payer,
+        &[],
+        &payer,
+        &fee_mint,
+        &fee_destination,
+        u64::MAX,
+    );
+    test_registry.process(&[update_fees], &[]).await.unwrap();
+    let registry_meta = test_registry.meta().await;
+    assert_eq!(
+        test_registry
+            .process(&[create_entries(&registry_meta, &registry_first)], &[&user])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::FeeOverflow)
+    );
+    assert!(test_registry.mints().await.is_empty());
+}
+
+#[tokio::test]
+async fn close_entry() {
+    let mut test_registry = TestRegistry::start(0).await;
+    let payer = test_registry.payer();
+    let first = test_registry.register("FIRST").await;
+    let second = test_registry.register("SECOND").await;
+    let third = test_registry.register("THIRD").await;
+
+    let registry_node = test_registry.node(&second).await;
+    let mut close_entry = instruction::close_entry(
+        &registry::id(),
+        "",
+        &payer,
+        &[],
+        &second,
+        &registry_node,
+        &payer,
+    );
+    close_entry.accounts[4].pubkey =
+        instruction::get_registry_node_address(&registry::id(), "", &first);
+    assert_eq!(
+        test_registry
+            .process(&[close_entry], &[])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::InvalidRegistryNodeNeighbor)
+    );
+
+    let close_entry = instruction::close_entry(
+        &registry::id(),
+        "",
+        &payer,
+        &[],
+        &second,
+        &registry_node,
+        &payer,
+    );
+    test_registry.process(&[close_entry], &[]).await.unwrap();
+    assert_eq!(test_registry.mints().await, vec![third, first]);
+    let node_address = instruction::get_registry_node_address(&registry::id(), "", &second);
+    assert!(test_registry.get_account(&node_address).await.is_none());
+
+    /* A closed mint can be registered again, as a new entry at the front. */
+    let create_entry = test_registry
+        .create_entry_instruction(&payer, &second, &payer, entry_data("AGAIN"))
+        .await;
+    test_registry.process(&[create_entry], &[]).await.unwrap();
+    assert_eq!(test_registry.mints().await, vec![second, third, first]);
+}
+
+#[tokio::test]
+async fn closed_entry() {
+    let mut test_registry = TestRegistry::start(0).await;
+    let payer = test_registry.payer();
+    let mint = test_registry.register("SHUT").await;
+
+    /* The node is wiped by the instruction that closes it, not at the end of the transaction. */
+    let registry_node = test_registry.node(&mint).await;
+    let close_entry = instruction::close_entry(
+        &registry::id(),
+        "",
+        &payer,
+        &[],
+        &mint,
+        &registry_node,
+        &payer,
+    );
+    let delete_entry =
+        instruction::delete_entry(&registry::id(), "", &payer, &[], &mint, "SHUT", &payer);
+    assert_eq!(
+        test_registry
+            .process(&[close_entry, delete_entry], &[])
+            .await
+            .unwrap_err(),
+        registry_error(1, RegistryError::NotYetRegisteredMint)
+    );
+}
+
+#[tokio::test]
+async fn closed_entry_funded_again() {
+    let mut test_registry = TestRegistry::start(0).await;
+    let payer = test_registry.payer();
+    let mint = test_registry.register("SHUT").await;
+
+    /* Funding the node again in the transaction that closes it leaves an empty system account. */
+    let registry_node = test_registry.node(&mint).await;
+    let node_address = instruction::get_registry_node_address(&registry::id(), "", &mint);
+    let lamports = test_registry
+        .get_account(&node_address)
+        .await
+        .unwrap()
+        .lamports;
+    let close_entry = instruction::close_entry(
+        &registry::id(),
+        "",
+        &payer,
+        &[],
+        &mint,
+        &registry_node,
+        &payer,
+    );
+    let fund = system_instruction::transfer(&payer, &node_address, lamports);
+    test_registry
+        .process(&[close_entry, fund], &[])
+        .await
+        .unwrap();
+    let account = test_registry.get_account(&node_address).await.unwrap();
+    assert_eq!(account.owner, system_program::id());
+    assert!(account.data.is_empty());
+    assert!(test_registry.mints().await.is_empty());
+
+    /* The mint can be registered again, on top of the lamports already at the address. */
+    let create_entry = test_registry
+        .create_entry_instruction(&payer, &mint, &payer, entry_data("AGAIN"))
+        .await;
+    test_registry.process(&[create_entry], &[]).await.unwrap();
+    assert_eq!(test_registry.mints().await, vec![mint]);
+    assert_eq!(test_registry.node(&mint).await.token_symbol, "AGAIN");
+}
+
+#[tokio::test]
+async fn unique_symbols() {
+    let mut test_registry = TestRegistry::start(0).await;
+    let payer = test_registry.payer();
+    let update_symbol_uniqueness =
+        instruction::update_symbol_uniqueness(&registry::id(), "", &payer, &[], true);
+    test_registry
+        .process(&[update_symbol_uniqueness], &[])
+        .await
+        .unwrap();
+    let usdc = test_registry.register("USDC").await;
+
+    let mint = test_registry.create_mint().await;
+    let create_entry = test_registry
+        .create_entry_instruction(&payer, &mint, &payer, entry_data("usdc"))
+        .await;
+    assert_eq!(
+        test_registry
+            .process(&[create_entry], &[])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::SymbolAlreadyClaimed)
+    );
+
+    /* Deleting an entry releases its symbol. */
+    let delete_entry =
+        instruction::delete_entry(&registry::id(), "", &payer, &[], &usdc, "USDC", &payer);
+    test_registry.process(&[delete_entry], &[]).await.unwrap();
+    let create_entry = test_registry
+        .create_entry_instruction(&payer, &mint, &payer, entry_data("usdc"))
+        .await;
+    test_registry.process(&[create_entry], &[]).await.unwrap();
+    let restore_entry =
+        instruction::restore_entry(&registry::id(), "", &payer, &[], &payer, &usdc, "USDC");
+    assert_eq!(
+        test_registry
+            .process(&[restore_entry], &[])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::SymbolAlreadyClaimed)
+    );
+}
+
+#[tokio::test]
+async fn symbol_claims_of_other_registries() {
+    let mut test_registry = TestRegistry::start(0).await;
+    let payer = test_registry.payer();
+    let update_symbol_uniqueness =
+        instruction::update_symbol_uniqueness(&registry::id(), "", &payer, &[], true);
+    let (fee_mint, fee_destination) = (test_registry.fee_mint, test_registry.fee_destination);
+    let initialize_registry = instruction::initialize_registry(
+        &registry::id(),
+        "symbolx",
+        &payer,
+        &fee_mint,
+        &fee_destination,
+        0,
+    );
+    test_registry
+        .process(&[update_symbol_uniqueness, initialize_registry], &[])
+        .await
+        .unwrap();
+
+    /* Concatenated, the seeds of this claim would spell out those of the meta of "symbolx". */
+    let claim = instruction::get_symbol_claim_address(&registry::id(), "", "XMETA");
+    assert_ne!(
+        claim,
+        Some(instruction::get_registry_meta_address(
+            &registry::id(),
+            "symbolx"
+        ))
+    );
+    test_registry.register("XMETA").await;
+    assert_eq!(
+        instruction::get_symbol_claim_address(&registry::id(), "", "\u{200b}"),
+        None
+    );
+    assert_ne!(
+        claim,
+        instruction::get_symbol_claim_address(&registry::id(), "symbolx", "XMETA")
+    );
+}
+
+#[tokio::test]
+async fn verify_entry() {
+    let mut test_registry = TestRegistry::start(0).await;
+    let payer = test_registry.payer();
+    let mint = test_registry.register("SAFE").await;
+    let curator = test_registry.new_user().await;
+
+    let verify_entry =
+        instruction::verify_entry(&registry::id(), "", &curator.pubkey(), &[], &mint);
+    assert_eq!(
+        test_registry
+            .process(std::slice::from_ref(&verify_entry), &[&curator])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::InvalidCurator)
+    );
+
+    let update_curators =
+        instruction::update_curators(&registry::id(), "", &payer, &[], &[curator.pubkey()]);
+    test_registry
+        .process(&[update_curators, verify_entry], &[&curator])
+        .await
+        .unwrap();
+    let registry_node = test_registry.node(&mint).await;
+    assert!(registry_node.verified);
+    assert_eq!(registry_node.verifier, curator.pubkey().to_bytes());
+
+    let unverify_entry = instruction::unverify_entry(&registry::id(), "", &payer, &[], &mint);
+    test_registry.process(&[unverify_entry], &[]).await.unwrap();
+    assert!(!test_registry.node(&mint).await.verified);
+
+    /* Updating an entry clears its verification. */
+    let verify_entry = instruction::verify_entry(&registry::id(), "", &payer, &[], &mint);
+    let update_entry = instruction::update_entry(
+        &registry::id(),
+        "",
+        &payer,
+        &[],
+        &payer,
+        &mint,
+        "SAFE",
+        entry_data("UNSAFE"),
+    );
+    test_registry
+        .process(&[verify_entry, update_entry], &[])
+        .await
+        .unwrap();
+    assert!(!test_registry.node(&mint).await.verified);
+}
+
+#[tokio::test]
+async fn transfer_token_authority() {
+    let mut test_registry = TestRegistry::start(0).await;
+    let payer = test_registry.payer();
+    let mint = test_registry.register("MOVE").await;
+    let new_authority = test_registry.new_user().await;
+
+    let accept_token_authority = instruction::accept_token_authority(
+        &registry::id(),
+        "",
+        &new_authority.pubkey(),
+        &[],
+        &mint,
+    );
+    assert_eq!(
+        test_registry
+            .process(
+                std::slice::from_ref(&accept_token_authority),
+                &[&new_authority]
+            )
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::NoPendingAuthority)
+    );
+
+    let transfer_token_authority = instruction::transfer_token_authority(
+        &registry::id(),
+        "",
+        &new_authority.pubkey(),
+        &[],
+        &mint,
+        &new_authority.pubkey(),
+    );
+    assert_eq!(
+        test_registry
+            .process(&[transfer_token_authority], &[&new_authority])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::InvalidTokenUpdateAuthority)
+    );
+
+    let transfer_token_authority = instruction::transfer_token_authority(
+        &registry::id(),
+        "",
+        &payer,
+        &[],
+        &mint,
+        &new_authority.pubkey(),
+    );
+    test_registry
+        .process(std::slice::from_ref(&transfer_token_authority), &[])
+        .await
+        .unwrap();
+    assert_eq!(
+        test_registry
+            .node(&mint)
+            .await
+            .pending_token_update_authority,
+        new_authority.pubkey().to_bytes()
+    );
+
+    let other = test_registry.new_user().await;
+    let accept_by_other =
+        instruction::accept_token_authority(&registry::id(), "", &other.pubkey(), &[], &mint);
+    assert_eq!(
+        test_registry
+            .process(&[accept_by_other], &[&other])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::InvalidPendingAuthority)
+    );
+
+    let cancel_token_authority_transfer =
+        instruction::cancel_token_authority_transfer(&registry::id(), "", &payer, &[], &mint);
+    test_registry
+        .process(&[cancel_token_authority_transfer], &[])
+        .await
+        .unwrap();
+    assert_eq!(
+        test_registry
+            .node(&mint)
+            .await
+            .pending_token_update_authority,
+        [0; 32]
+    );
+
+    test_registry
+        .process(
+            &[transfer_token_authority, accept_token_authority],
+            &[&new_authority],
+        )
+        .await
+        .unwrap();
+    let registry_node = test_registry.node(&mint).await;
+    assert_eq!(
+        registry_node.token_update_authority,
+        new_authority.pubkey().to_bytes()
+    );
+    assert_eq!(registry_node.pending_token_update_authority, [0; 32]);
+}
+
+#[tokio::test]
+async fn multisig_token_authority() {
+    let mut test_registry = TestRegistry::start(0).await;
+    let payer = test_registry.payer();
+    let mint = test_registry.register("MULTI").await;
+    let signers = [Keypair::new(), Keypair::new(), Keypair::new()];
+    let signer_pubkeys: Vec<Pubkey> = signers.iter().map(Signer::pubkey).collect();
+    let multisig = test_registry
+        .create_multisig(&signer_pubkeys.iter().collect::<Vec<_>>(), 2)
+        .await;
+
+    let transfer_token_authority =
+        instruction::transfer_token_authority(&registry::id(), "", &payer, &[], &mint, &multisig);
+    let accept_token_authority = instruction::accept_token_authority(
+        &registry::id(),
+        "",
+        &multisig,
+        &[&signer_pubkeys[0], &signer_pubkeys[2]],
+        &mint,
+    );
+    test_registry
+        .process(
+            &[transfer_token_authority, accept_token_authority],
+            &[&signers[0], &signers[2]],
+        )
+        .await
+        .unwrap();
+
+    let update_entry = |signers: &[&Pubkey], token_symbol: &str| {
+        instruction::update_entry(
+            &registry::id(),
+            "",
+            &multisig,
+            signers,
+            &payer,
+            &mint,
+            "MULTI",
+            entry_data(token_symbol),
+        )
+    };
+    assert_eq!(
+        test_registry
+            .process(
+                &[update_entry(&[&signer_pubkeys[0]], "ONE")],
+                &[&signers[0]]
+            )
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::MissingMultisigSigners)
+    );
+    test_registry
+        .process(
+            &[update_entry(
+                &[&signer_pubkeys[0], &signer_pubkeys[1]],
+                "TWO",
+            )],
+            &[&signers[0], &signers[1]],
+        )
+        .await
+        .unwrap();
+    assert_eq!(test_registry.node(&mint).await.token_symbol, "TWO");
+
+    /* The multisig's signers also authorize the entry's other instructions. */
+    let two_signers = [&signer_pubkeys[1], &signer_pubkeys[2]];
+    let instructions = [
+        instruction::transfer_token_authority(
+            &registry::id(),
+            "",
+            &multisig,
+            &two_signers,
+            &mint,
+            &payer,
+        ),
+        instruction::cancel_token_authority_transfer(
+            &registry::id(),
+            "",
+            &multisig,
+            &two_signers,
+            &mint,
+        ),
+        instruction::delete_entry(
+            &registry::id(),
+            "",
+            &multisig,
+            &two_signers,
+            &mint,
+            "TWO",
+            &payer,
+        ),
+        instruction::restore_entry(
+            &registry::id(),
+            "",
+            &multisig,
+            &two_signers,
+            &payer,
+            &mint,
+            "TWO",
+        ),
+    ];
+    test_registry
+        .process(&instructions, &[&signers[1], &signers[2]])
+        .await
+        .unwrap();
+    let registry_node = test_registry.node(&mint).await;
+    assert_eq!(registry_node.state, EntryState::Active);
+    assert_eq!(registry_node.pending_token_update_authority, [0; 32]);
+    let close_entry = instruction::close_entry(
+        &registry::id(),
+        "",
+        &multisig,
+        &two_signers,
+        &mint,
+        &registry_node,
+        &payer,
+    );
+    test_registry
+        .process(&[close_entry], &[&signers[1], &signers[2]])
+        .await
+        .unwrap();
+    assert_eq!(test_registry.mints().await, vec![]);
+}
+
+#[tokio::test]
+async fn multisig_mint_authority() {
+    let mut test_registry = TestRegistry::start(0).await;
+    let payer = test_registry.payer();
+    let signers = [Keypair::new(), Keypair::new(), Keypair::new()];
+    let signer_pubkeys: Vec<Pubkey> = signers.iter().map(Signer::pubkey).collect();
+    let multisig = test_registry
+        .create_multisig(&signer_pubkeys.iter().collect::<Vec<_>>(), 2)
+        .await;
+    let mints = [Keypair::new(), Keypair::new(), Keypair::new()];
+    for mint in &mints {
+        test_registry.create_mint_at(mint, &multisig, None).await;
+    }
+    let mint = mints[0].pubkey();
+
+    let registry_meta = test_registry.meta().await;
+    let registry_first = test_registry.registry_first().await;
+    let create_entry = |signers: &[&Pubkey]| {
+        instruction::create_entry(
+            &registry::id(),
+            &payer,
+            &mint,
+            &multisig,
+            signers,
+            &registry_meta,
+            &registry_first,
+            entry_data("MULTI"),
+        )
+    };
+    assert_eq!(
+        test_registry
+            .process(&[create_entry(&[&signer_pubkeys[0]])], &[&signers[0]])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::MissingMultisigSigners)
+    );
+    let two_signers = [&signer_pubkeys[0], &signer_pubkeys[1]];
+    test_registry
+        .process(&[create_entry(&two_signers)], &[&signers[0], &signers[1]])
+        .await
+        .unwrap();
+    assert_eq!(test_registry.mints().await, vec![mint]);
+
+    /* The signers shared by the entries of a batch are only passed once. */
+    let registry_meta = test_registry.meta().await;
+    let registry_first = test_registry.registry_first().await;
+    let entries = mints[1..]
+        .iter()
+        .zip(["FIRST", "SECOND"])
+        .map(|(mint, token_symbol)| instruction::BatchEntry {
+            mint: mint.pubkey(),
+            mint_authority: multisig,
+            signers: vec![signer_pubkeys[1], signer_pubkeys[2]],
+            data: entry_data(token_symbol),
+        })
+        .collect();
+    let create_entries = instruction::create_entries(
+        &registry::id(),
+        &payer,
+        &registry_meta,
+        &registry_first,
+        entries,
+    );
+    assert_eq!(create_entries.accounts.len(), 8 + 4 * 2 + 2);
+    test_registry
+        .process(&[create_entries], &[&signers[1], &signers[2]])
+        .await
+        .unwrap();
+    assert_eq!(
+        test_registry.mints().await,
+        vec![mints[1].pubkey(), mints[2].pubkey(), mint]
+    );
+
+    let reclaim_token_authority = instruction::reclaim_token_authority(
+        &registry::id(),
+        "",
+        &multisig,
+        &two_signers,
+        &mint,
+        &multisig,
+    );
+    test_registry
+        .process(&[reclaim_token_authority], &[&signers[0], &signers[1]])
+        .await
+        .unwrap();
+    assert_eq!(
+        test_registry.node(&mint).await.token_update_authority,
+        multisig.to_bytes()
+    );
+}
+
+#[tokio::test]
+async fn multisig_curator() {
+    let mut test_registry = TestRegistry::start(0).await;
+    let payer = test_registry.payer();
+    let mint = test_registry.register("SAFE").await;
+    let signers = [Keypair::new(), Keypair::new(), Keypair::new()];
+    let signer_pubkeys: Vec<Pubkey> = signers.iter().map(Signer::pubkey).collect();
+    let multisig = test_registry
+        .create_multisig(&signer_pubkeys.iter().collect::<Vec<_>>(), 2)
+        .await;
+    let update_curators =
+        instruction::update_curators(&registry::id(), "", &payer, &[], &[multisig]);
+    test_registry
+        .process(&[update_curators], &[])
+        .await
+        .unwrap();
+
+    let verify_entry = |signers: &[&Pubkey]| {
+        instruction::verify_entry(&registry::id(), "", &multisig, signers, &mint)
+    };
+    assert_eq!(
+        test_registry
+            .process(&[verify_entry(&[&signer_pubkeys[2]])], &[&signers[2]])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::MissingMultisigSigners)
+    );
+    let two_signers = [&signer_pubkeys[0], &signer_pubkeys[2]];
+    test_registry
+        .process(&[verify_entry(&two_signers)], &[&signers[0], &signers[2]])
+        .await
+        .unwrap();
+    let registry_node = test_registry.node(&mint).await;
+    assert!(registry_node.verified);
+    assert_eq!(registry_node.verifier, multisig.to_bytes());
+
+    let unverify_entry =
+        instruction::unverify_entry(&registry::id(), "", &multisig, &two_signers, &mint);
+    test_registry
+        .process(&[unverify_entry], &[&signers[0], &signers[2]])
+        .await
+        .unwrap();
+    assert!(!test_registry.node(&mint).await.verified);
+
+    /* A multisig fee update authority registers mints without a mint authority. */
+    let transfer_fee_authority =
+        instruction::transfer_fee_authority(&registry::id(), "", &payer, &[], &multisig);
+    let accept_fee_authority =
+        instruction::accept_fee_authority(&registry::id(), "", &multisig, &two_signers);
+    test_registry
+        .process(
+            &[transfer_fee_authority, accept_fee_authority],
+            &[&signers[0], &signers[2]],
+        )
+        .await
+        .unwrap();
+    let revoked = Keypair::new();
+    test_registry
+        .create_mint_at(&revoked, &multisig, None)
+        .await;
+    let set_authority = spl_token::instruction::set_authority(
+        &spl_token::id(),
+        &revoked.pubkey(),
+        None,
+        spl_token::instruction::AuthorityType::MintTokens,
+        &multisig,
+        &two_signers,
+    )
+    .unwrap();
+    test_registry
+        .process(&[set_authority], &[&signers[0], &signers[2]])
+        .await
+        .unwrap();
+    let registry_meta = test_registry.meta().await;
+    let registry_first = test_registry.registry_first().await;
+    let create_entry = instruction::create_entry(
+        &registry::id(),
+        &payer,
+        &revoked.pubkey(),
+        &multisig,
+        &two_signers,
+        &registry_meta,
+        &registry_first,
+        entry_data("REVOKED"),
+    );
+    test_registry
+        .process(&[create_entry], &[&signers[0], &signers[2]])
+        .await
+        .unwrap();
+    assert_eq!(
+        test_registry.node(&revoked.pubkey()).await.mint_authority,
+        [0; 32]
+    );
+}
+
+#[tokio::test]
+async fn reclaim_token_authority() {
+    let mut test_registry = TestRegistry::start(0).await;
+    let payer = test_registry.payer();
+    let user = test_registry.new_user().await;
+    let mint = test_registry.create_mint().await;
+    test_registry.register_as(&user, &mint, "LOST").await;
+
+    let reclaim_token_authority = instruction::reclaim_token_authority(
+        &registry::id(),
+        "",
+        &user.pubkey(),
+        &[],
+        &mint,
+        &user.pubkey(),
+    );
+    assert_eq!(
+        test_registry
+            .process(&[reclaim_token_authority], &[&user])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::InvalidMintAuthority)
+    );
+
+    let reclaim_token_authority =
+        instruction::reclaim_token_authority(&registry::id(), "", &payer, &[], &mint, &payer);
+    test_registry
+        .process(&[reclaim_token_authority], &[])
+        .await
+        .unwrap();
+    assert_eq!(
+        test_registry.node(&mint).await.token_update_authority,
+        payer.to_bytes()
+    );
+}
+
+#[tokio::test]
+async fn refresh_mint_info() {
+    let mut test_registry = TestRegistry::start(0).await;
+    let payer = test_registry.payer();
+    let mint = test_registry.register("SUPPLY").await;
+    assert_eq!(test_registry.node(&mint).await.mint_supply, 0);
+
+    let ata = spl_associated_token_account::get_associated_token_address(&payer, &mint);
+    let instructions = [
+        spl_associated_token_account::instruction::create_associated_token_account(
+            &payer, &payer, &mint,
+        ),
+        spl_token::instruction::mint_to(&spl_token::id(), &mint, &ata, &payer, &[], 42).unwrap(),
+        instruction::refresh_mint_info(&registry::id(), "", &mint),
+    ];
+    test_registry.process(&instructions, &[]).await.unwrap();
+    assert_eq!(test_registry.node(&mint).await.mint_supply, 42);
+
+    let unregistered_mint = test_registry.create_mint().await;
+    let refresh_mint_info = instruction::refresh_mint_info(&registry::id(), "", &unregistered_mint);
+    assert_eq!(
+        test_registry
+            .process(&[refresh_mint_info], &[])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::NotYetRegisteredMint)
+    );
+}
+
+#[tokio::test]
+async fn invalid_node_accounts() {
+    /* Nodes that the program did not write, stored at the PDAs of two mints. */
+    let (wrong_type_mint, wrong_version_mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut wrong_version_data = vec![0; AccountHeader::LEN + 8];
+    wrong_version_data[..8].copy_from_slice(&REGISTRY_NODE_DISCRIMINATOR);
+    wrong_version_data[8] = REGISTRY_ACCOUNT_VERSION + 1;
+    let mut program_test = program_test();
+    for (mint, node_data) in [
+        (wrong_type_mint, vec![1; AccountHeader::LEN + 8]),
+        (wrong_version_mint, wrong_version_data),
+    ] {
+        program_test.add_account(mint, program_account(&spl_token::id(), mint_data(None)));
+        program_test.add_account(
+            instruction::get_registry_node_address(&registry::id(), "", &mint),
+            program_account(&registry::id(), node_data),
+        );
+    }
+    let mut test_registry = TestRegistry::start_with(program_test, "", Keypair::new(), 0).await;
+
+    let refresh_mint_info = instruction::refresh_mint_info(&registry::id(), "", &wrong_type_mint);
+    assert_eq!(
+        test_registry
+            .process(&[refresh_mint_info], &[])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::InvalidAccountType)
+    );
+    let refresh_mint_info =
+        instruction::refresh_mint_info(&registry::id(), "", &wrong_version_mint);
+    assert_eq!(
+        test_registry
+            .process(&[refresh_mint_info], &[])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::InvalidAccountVersion)
+    );
+}
@@ -0,0 +1,607 @@
+//! Tests of the instructions that initialize and configure a registry through its
+//! RegistryMetaAccount.
+
+mod common;
+
+use common::{program_test, registry_error, TestRegistry};
+use registry::{
+    error::RegistryError,
+    instruction,
+    state::{MAX_CURATORS, MAX_FEE_EXEMPT_ACCOUNTS},
+};
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    sysvar,
+};
+
+#[tokio::test]
+async fn initialize_registry() {
+    let mut test_registry = TestRegistry::start(100).await;
+    let payer = test_registry.payer();
+    let registry_meta = test_registry.meta().await;
+    assert_eq!(registry_meta.fee_amount, 100);
+    assert_eq!(registry_meta.fee_mint, test_registry.fee_mint.to_bytes());
+    assert_eq!(
+        registry_meta.fee_destination,
+        test_registry.fee_destination.to_bytes()
+    );
+    assert_eq!(registry_meta.fee_update_authority, payer.to_bytes());
+    assert_eq!(registry_meta.registry_id, "");
+
+    /* The list starts out empty, with the head pointing at the tail. */
+    let tail = instruction::get_registry_tail_address(&registry::id(), "");
+    assert_eq!(test_registry.registry_first().await, tail);
+    let fee_destination = test_registry.fee_destination;
+    assert_eq!(test_registry.token_balance(&fee_destination).await, 0);
+}
+
+#[tokio::test]
+async fn initialize_named_registries() {
+    let mut test_registry =
+        TestRegistry::start_with(program_test(), "verified", Keypair::new(), 0).await;
+    assert_eq!(test_registry.meta().await.registry_id, "verified");
+
+    let payer = test_registry.payer();
+    let (fee_mint, fee_destination) = (test_registry.fee_mint, test_registry.fee_destination);
+    let initialize_registry = instruction::initialize_registry(
+        &registry::id(),
+        "community",
+        &payer,
+        &fee_mint,
+        &fee_destination,
+        0,
+    );
+    test_registry
+        .process(&[initialize_registry], &[])
+        .await
+        .unwrap();
+
+    let initialize_registry = instruction::initialize_registry(
+        &registry::id(),
+        "not valid",
+        &payer,
+        &fee_mint,
+        &fee_destination,
+        0,
+    );
+    assert_eq!(
+        test_registry
+            .process(&[initialize_registry], &[])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::InvalidRegistryId)
+    );
+}
+
+#[tokio::test]
+async fn initialize_registry_errors() {
+    let mut test_registry = TestRegistry::start(0).await;
+    let payer = test_registry.payer();
+    let (fee_mint, fee_destination) = (test_registry.fee_mint, test_registry.fee_destination);
+
+    let initialize_registry = instruction::initialize_registry(
+        &registry::id(),
+        "",
+        &payer,
+        &fee_mint,
+        &fee_destination,
+        1,
+    );
+    assert_eq!(
+        test_registry
+            .process(&[initialize_registry], &[])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::AlreadyInitialized)
+    );
+
+    /* The fee destination must be a wallet, rather than e.g, a mint. */
+    let initialize_registry =
+        instruction::initialize_registry(&registry::id(), "other", &payer, &fee_mint, &fee_mint, 0);
+    assert_eq!(
+        test_registry
+            .process(&[initialize_registry], &[])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::InvalidSystemAccount)
+    );
+
+    let initialize_registry = instruction::initialize_registry(
+        &registry::id(),
+        "other",
+        &payer,
+        &fee_destination,
+        &fee_destination,
+        0,
+    );
+    assert_eq!(
+        test_registry
+            .process(&[initialize_registry], &[])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::InvalidMint)
+    );
+}
+
+#[tokio::test]
+async fn uninitialized_registry() {
+    let mut context = program_test().start_with_context().await;
+    let payer = context.payer.pubkey();
+    let update_fee_exemptions =
+        instruction::update_fee_exemptions(&registry::id(), "", &payer, &[], &[]);
+    assert_eq!(
+        common::process(&mut context, &[update_fee_exemptions], &[])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::NotYetInitialized)
+    );
+}
+
+#[tokio::test]
+async fn update_fees() {
+    let mut test_registry = TestRegistry::start(100).await;
+    let payer = test_registry.payer();
+    let fee_mint = test_registry.fee_mint;
+    let new_fee_destination = Pubkey::new_unique();
+    let update_fees = instruction::update_fees(
+        &registry::id(),
+        "",
+        &payer,
+        &[],
+        &payer,
+        &fee_mint,
+        &new_fee_destination,
+        200,
+    );
+    test_registry.process(&[update_fees], &[]).await.unwrap();
+
+    let registry_meta = test_registry.meta().await;
+    assert_eq!(registry_meta.fee_amount, 200);
+    assert_eq!(
+        registry_meta.fee_destination,
+        new_fee_destination.to_bytes()
+    );
+    /* The ATA of the new fee destination is created by the payer. */
+    assert_eq!(test_registry.token_balance(&new_fee_destination).await, 0);
+}
+
+#[tokio::test]
+async fn update_fees_errors() {
+    let mut test_registry = TestRegistry::start(100).await;
+    let payer = test_registry.payer();
+    let (fee_mint, fee_destination) = (test_registry.fee_mint, test_registry.fee_destination);
+    let update_fees = |fee_update_authority: &Pubkey, fee_amount| {
+        instruction::update_fees(
+            &registry::id(),
+            "",
+            fee_update_authority,
+            &[],
+            &payer,
+            &fee_mint,
+            &fee_destination,
+            fee_amount,
+        )
+    };
+
+    let user = test_registry.new_user().await;
+    assert_eq!(
+        test_registry
+            .process(&[update_fees(&user.pubkey(), 1)], &[&user])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::InvalidFeeUpdateAuthority)
+    );
+
+    /* Replace each of the fixed accounts with the wrong account. */
+    let invalid_accounts = [
+        (
+            3,
+            Pubkey::new_unique(),
+            RegistryError::InvalidAssociatedTokenAccount,
+        ),
+        (4, spl_token::id(), RegistryError::InvalidSystemProgram),
+        (5, Pubkey::new_unique(), RegistryError::InvalidTokenProgram),
+        (6, spl_token::id(), RegistryError::InvalidATAProgram),
+        (
+            7,
+            sysvar::clock::id(),
+            RegistryError::InvalidSysvarRentProgram,
+        ),
+    ];
+    for (fee_amount, (index, pubkey, error)) in (2..).zip(invalid_accounts) {
+        let mut instruction = update_fees(&payer, fee_amount);
+        instruction.accounts[index].pubkey = pubkey;
+        assert_eq!(
+            test_registry
+                .process(&[instruction], &[])
+                .await
+                .unwrap_err(),
+            registry_error(0, error)
+        );
+    }
+
+    let mut instruction = update_fees(&payer, 10);
+    instruction.accounts.truncate(9);
+    assert_eq!(
+        test_registry
+            .process(&[instruction], &[])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::InvalidNumberOfAccounts)
+    );
+}
+
+#[tokio::test]
+async fn transfer_fee_authority() {
+    let mut test_registry = TestRegistry::start(0).await;
+    let payer = test_registry.payer();
+    let new_authority = test_registry.new_user().await;
+
+    let accept_fee_authority =
+        instruction::accept_fee_authority(&registry::id(), "", &new_authority.pubkey(), &[]);
+    assert_eq!(
+        test_registry
+            .process(
+                std::slice::from_ref(&accept_fee_authority),
+                &[&new_authority]
+            )
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::NoPendingAuthority)
+    );
+    let transfer_fee_authority = instruction::transfer_fee_authority(
+        &registry::id(),
+        "",
+        &payer,
+        &[],
+        &new_authority.pubkey(),
+    );
+    test_registry
+        .process(&[transfer_fee_authority], &[])
+        .await
+        .unwrap();
+    assert_eq!(
+        test_registry.meta().await.pending_fee_update_authority,
+        new_authority.pubkey().to_bytes()
+    );
+
+    /* Only the proposed authority can accept, and the current one stays until then. */
+    let other = test_registry.new_user().await;
+    let accept_by_other =
+        instruction::accept_fee_authority(&registry::id(), "", &other.pubkey(), &[]);
+    assert_eq!(
+        test_registry
+            .process(&[accept_by_other], &[&other])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::InvalidPendingAuthority)
+    );
+    assert_eq!(
+        test_registry.meta().await.fee_update_authority,
+        payer.to_bytes()
+    );
+
+    let cancel_fee_authority_transfer =
+        instruction::cancel_fee_authority_transfer(&registry::id(), "", &payer, &[]);
+    test_registry
+        .process(&[cancel_fee_authority_transfer], &[])
+        .await
+        .unwrap();
+    assert_eq!(
+        test_registry.meta().await.pending_fee_update_authority,
+        [0; 32]
+    );
+
+    let transfer_fee_authority = instruction::transfer_fee_authority(
+        &registry::id(),
+        "",
+        &payer,
+        &[],
+        &new_authority.pubkey(),
+    );
+    test_registry
+        .process(
+            &[transfer_fee_authority, accept_fee_authority],
+            &[&new_authority],
+        )
+        .await
+        .unwrap();
+    let registry_meta = test_registry.meta().await;
+    assert_eq!(
+        registry_meta.fee_update_authority,
+        new_authority.pubkey().to_bytes()
+    );
+    assert_eq!(registry_meta.pending_fee_update_authority, [0; 32]);
+    let cancel_fee_authority_transfer = instruction::cancel_fee_authority_transfer(
+        &registry::id(),
+        "",
+        &new_authority.pubkey(),
+        &[],
+    );
+    assert_eq!(
+        test_registry
+            .process(&[cancel_fee_authority_transfer], &[&new_authority])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::NoPendingAuthority)
+    );
+
+    /* The former authority has lost its rights. */
+    let update_entry_limits =
+        instruction::update_entry_limits(&registry::id(), "", &payer, &[], 1, 1);
+    assert_eq!(
+        test_registry
+            .process(&[update_entry_limits], &[])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::InvalidFeeUpdateAuthority)
+    );
+}
+
+#[tokio::test]
+async fn multisig_fee_authority() {
+    let mut test_registry = TestRegistry::start(0).await;
+    let payer = test_registry.payer();
+    let signers = [Keypair::new(), Keypair::new(), Keypair::new()];
+    let signer_pubkeys: Vec<Pubkey> = signers.iter().map(Signer::pubkey).collect();
+    let multisig = test_registry
+        .create_multisig(&signer_pubkeys.iter().collect::<Vec<_>>(), 2)
+        .await;
+
+    let transfer_fee_authority =
+        instruction::transfer_fee_authority(&registry::id(), "", &payer, &[], &multisig);
+    let accept_fee_authority = instruction::accept_fee_authority(
+        &registry::id(),
+        "",
+        &multisig,
+        &[&signer_pubkeys[0], &signer_pubkeys[1]],
+    );
+    test_registry
+        .process(
+            &[transfer_fee_authority, accept_fee_authority],
+            &[&signers[0], &signers[1]],
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        test_registry.meta().await.fee_update_authority,
+        multisig.to_bytes()
+    );
+
+    let (fee_mint, fee_destination) = (test_registry.fee_mint, test_registry.fee_destination);
+    let update_fees = |signers: &[&Pubkey], fee_amount| {
+        instruction::update_fees(
+            &registry::id(),
+            "",
+            &multisig,
+            signers,
+            &payer,
+            &fee_mint,
+            &fee_destination,
+            fee_amount,
+        )
+    };
+    assert_eq!(
+        test_registry
+            .process(&[update_fees(&[&signer_pubkeys[2]], 5)], &[&signers[2]])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::MissingMultisigSigners)
+    );
+    test_registry
+        .process(
+            &[update_fees(&[&signer_pubkeys[1], &signer_pubkeys[2]], 5)],
+            &[&signers[1], &signers[2]],
+        )
+        .await
+        .unwrap();
+    assert_eq!(test_registry.meta().await.fee_amount, 5);
+
+    /* The multisig's signers also authorize the registry's other settings. */
+    let two_signers = [&signer_pubkeys[0], &signer_pubkeys[2]];
+    let instructions = [
+        instruction::update_fee_exemptions(&registry::id(), "", &multisig, &two_signers, &[payer]),
+        instruction::update_entry_limits(&registry::id(), "", &multisig, &two_signers, 2, 3),
+        instruction::update_symbol_uniqueness(&registry::id(), "", &multisig, &two_signers, true),
+        instruction::update_curators(&registry::id(), "", &multisig, &two_signers, &[payer]),
+        instruction::transfer_fee_authority(&registry::id(), "", &multisig, &two_signers, &payer),
+        instruction::cancel_fee_authority_transfer(&registry::id(), "", &multisig, &two_signers),
+    ];
+    test_registry
+        .process(&instructions, &[&signers[0], &signers[2]])
+        .await
+        .unwrap();
+    let registry_meta = test_registry.meta().await;
+    assert_eq!(registry_meta.fee_exempt_accounts[0], payer.to_bytes());
+    assert_eq!(
+        (registry_meta.max_tags, registry_meta.max_extensions),
+        (2, 3)
+    );
+    assert!(registry_meta.unique_symbols);
+    assert_eq!(registry_meta.curators[0], payer.to_bytes());
+    assert_eq!(registry_meta.pending_fee_update_authority, [0; 32]);
+}
+
+#[tokio::test]
+async fn multisig_duplicate_signers() {
+    let mut test_registry = TestRegistry::start(0).await;
+    let payer = test_registry.payer();
+    let signers = [Keypair::new(), Keypair::new()];
+    let (first, second) = (signers[0].pubkey(), signers[1].pubkey());
+
+    /* A key in two slots of a 2-of-3 multisig is still only one signer. */
+    let multisig = test_registry
+        .create_multisig(&[&first, &first, &second], 2)
+        .await;
+    let transfer_fee_authority =
+        instruction::transfer_fee_authority(&registry::id(), "", &payer, &[], &multisig);
+    test_registry
+        .process(&[transfer_fee_authority], &[])
+        .await
+        .unwrap();
+    let accept_fee_authority = |signers: &[&Pubkey]| {
+        instruction::accept_fee_authority(&registry::id(), "", &multisig, signers)
+    };
+    for duplicate_signers in [&[&first][..], &[&first, &first]] {
+        assert_eq!(
+            test_registry
+                .process(&[accept_fee_authority(duplicate_signers)], &[&signers[0]])
+                .await
+                .unwrap_err(),
+            registry_error(0, RegistryError::MissingMultisigSigners)
+        );
+    }
+    test_registry
+        .process(
+            &[accept_fee_authority(&[&first, &second])],
+            &[&signers[0], &signers[1]],
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        test_registry.meta().await.fee_update_authority,
+        multisig.to_bytes()
+    );
+}
+
+#[tokio::test]
+async fn update_fee_exemptions() {
+    let mut test_registry = TestRegistry::start(0).await;
+    let payer = test_registry.payer();
+    let exempt_accounts = [Pubkey::new_unique(), Pubkey::new_unique()];
+    let update_fee_exemptions =
+        instruction::update_fee_exemptions(&registry::id(), "", &payer, &[], &exempt_accounts);
+    test_registry
+        .process(&[update_fee_exemptions], &[])
+        .await
+        .unwrap();
+    let registry_meta = test_registry.meta().await;
+    assert!(exempt_accounts
+        .iter()
+        .all(|account| registry_meta.is_fee_exempt(&account.to_bytes())));
+
+    let too_many_accounts: Vec<Pubkey> = (0..=MAX_FEE_EXEMPT_ACCOUNTS)
+        .map(|_| Pubkey::new_unique())
+        .collect();
+    let update_fee_exemptions =
+        instruction::update_fee_exemptions(&registry::id(), "", &payer, &[], &too_many_accounts);
+    assert_eq!(
+        test_registry
+            .process(&[update_fee_exemptions], &[])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::TooManyFeeExemptAccounts)
+    );
+
+    /* The fee update authority must sign. */
+    let user = test_registry.new_user().await;
+    let instructions = [
+        instruction::transfer_fee_authority(&registry::id(), "", &payer, &[], &user.pubkey()),
+        instruction::accept_fee_authority(&registry::id(), "", &user.pubkey(), &[]),
+    ];
+    test_registry
+        .process(&instructions, &[&user])
+        .await
+        .unwrap();
+    let mut update_fee_exemptions =
+        instruction::update_fee_exemptions(&registry::id(), "", &user.pubkey(), &[], &[]);
+    update_fee_exemptions.accounts[0].is_signer = false;
+    assert_eq!(
+        test_registry
+            .process(&[update_fee_exemptions], &[])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::InvalidUserAccount)
+    );
+}
+
+#[tokio::test]
+async fn update_entry_limits() {
+    let mut test_registry = TestRegistry::start(0).await;
+    let payer = test_registry.payer();
+    let update_entry_limits =
+        instruction::update_entry_limits(&registry::id(), "", &payer, &[], 2, 3);
+    test_registry
+        .process(&[update_entry_limits], &[])
+        .await
+        .unwrap();
+    let registry_meta = test_registry.meta().await;
+    assert_eq!(
+        (registry_meta.max_tags, registry_meta.max_extensions),
+        (2, 3)
+    );
+
+    let user = test_registry.new_user().await;
+    let update_entry_limits =
+        instruction::update_entry_limits(&registry::id(), "", &user.pubkey(), &[], 4, 4);
+    assert_eq!(
+        test_registry
+            .process(&[update_entry_limits], &[&user])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::InvalidFeeUpdateAuthority)
+    );
+}
+
+#[tokio::test]
+async fn update_symbol_uniqueness() {
+    let mut test_registry = TestRegistry::start(0).await;
+    let payer = test_registry.payer();
+    let update_symbol_uniqueness =
+        instruction::update_symbol_uniqueness(&registry::id(), "", &payer, &[], true);
+    test_registry
+        .process(&[update_symbol_uniqueness], &[])
+        .await
+        .unwrap();
+    assert!(test_registry.meta().await.unique_symbols);
+
+    let user = test_registry.new_user().await;
+    let update_symbol_uniqueness =
+        instruction::update_symbol_uniqueness(&registry::id(), "", &user.pubkey(), &[], false);
+    assert_eq!(
+        test_registry
+            .process(&[update_symbol_uniqueness], &[&user])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::InvalidFeeUpdateAuthority)
+    );
+}
+
+#[tokio::test]
+async fn update_curators() {
+    let mut test_registry = TestRegistry::start(0).await;
+    let payer = test_registry.payer();
+    let curator = Pubkey::new_unique();
+    let update_curators =
+        instruction::update_curators(&registry::id(), "", &payer, &[], &[curator]);
+    test_registry
+        .process(&[update_curators], &[])
+        .await
+        .unwrap();
+    let registry_meta = test_registry.meta().await;
+    assert!(registry_meta.is_curator(&curator.to_bytes()));
+    assert!(registry_meta.is_curator(&payer.to_bytes()));
+
+    let too_many_curators: Vec<Pubkey> = (0..=MAX_CURATORS).map(|_| Pubkey::new_unique()).collect();
+    let update_curators =
+        instruction::update_curators(&registry::id(), "", &payer, &[], &too_many_curators);
+    assert_eq!(
+        test_registry
+            .process(&[update_curators], &[])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::TooManyCurators)
+    );
+
+    let user = test_registry.new_user().await;
+    let update_curators =
+        instruction::update_curators(&registry::id(), "", &user.pubkey(), &[], &[]);
+    assert_eq!(
+        test_registry
+            .process(&[update_curators], &[&user])
+            .await
+            .unwrap_err(),
+        registry_error(0, RegistryError::InvalidFeeUpdateAuthority)
+    );
+}